@@ -17,6 +17,28 @@ pub enum PbCommitMessageLintsError {
     LintNotFoundError(String),
     YamlParseError(String),
     IoError(String),
+    InvalidConfigurationValue(String, String),
+    FormatNotFoundError(String),
+    SerializeError(String),
+    HistoryError(String),
+    DefaultBranchNotFoundError,
+    SeverityNotFoundError(String),
+    RepoFileError(String),
+    ForeignHookError(Vec<String>),
+    ChainedHookError(String, String),
+    LintOptionNotFoundError(String, String),
+    InvalidLintOption(String, String, String),
+    CustomLintError(String, String),
+    PluginError(String, String),
+    PluginStartError(String, String),
+    PluginTimeoutError(String, u64),
+    PluginCrashError(String, Option<i32>, String),
+    PluginOutputError(String, String),
+    ScriptError(String, String),
+    ScriptBudgetError(String, u64),
+    LintAlreadyRegisteredError(String),
+    ProfileError(String, String),
+    ProfileNotFoundError(String),
 }
 
 impl Display for PbCommitMessageLintsError {
@@ -50,6 +72,103 @@ impl Display for PbCommitMessageLintsError {
             PbCommitMessageLintsError::IoError(error) => {
                 write!(f, "Failed to read file:\n{}", error)
             },
+            PbCommitMessageLintsError::InvalidConfigurationValue(key, value) => {
+                write!(f, "The value \"{}\" is not valid for \"{}\"", value, key)
+            },
+            PbCommitMessageLintsError::FormatNotFoundError(error) => {
+                write!(f, "Format \"{}\" not found", error)
+            },
+            PbCommitMessageLintsError::SerializeError(error) => {
+                write!(f, "Couldn't write the lint results:\n{}", error)
+            },
+            PbCommitMessageLintsError::HistoryError(error) => {
+                write!(f, "Couldn't read the commit history:\n{}", error)
+            },
+            PbCommitMessageLintsError::SeverityNotFoundError(error) => {
+                write!(f, "Severity \"{}\" not found", error)
+            },
+            PbCommitMessageLintsError::RepoFileError(error) => {
+                write!(f, "Couldn't use the repository config file:\n{}", error)
+            },
+            PbCommitMessageLintsError::ForeignHookError(paths) => write!(
+                f,
+                "These hooks weren't installed by pb-git-hooks, so they've been left \
+                 alone:\n{}\n\nYou can replace them with `pb-git-hooks install --force`, which \
+                 keeps them as chained hooks that run after ours",
+                paths.join("\n")
+            ),
+            PbCommitMessageLintsError::ChainedHookError(hook, error) => {
+                write!(f, "Couldn't run the chained hook `{}`:\n{}", hook, error)
+            },
+            PbCommitMessageLintsError::LintOptionNotFoundError(lint, option) => {
+                write!(f, "Lint \"{}\" has no option \"{}\"", lint, option)
+            },
+            PbCommitMessageLintsError::InvalidLintOption(key, value, reason) => write!(
+                f,
+                "The value \"{}\" is not valid for \"{}\":\n{}",
+                value, key, reason
+            ),
+            PbCommitMessageLintsError::CustomLintError(lint, reason) => {
+                write!(f, "The custom lint \"{}\" isn't valid:\n{}", lint, reason)
+            },
+            PbCommitMessageLintsError::PluginError(plugin, reason) => {
+                write!(f, "The plugin \"{}\" isn't valid:\n{}", plugin, reason)
+            },
+            PbCommitMessageLintsError::PluginStartError(plugin, error) => {
+                write!(f, "Couldn't start the plugin \"{}\":\n{}", plugin, error)
+            },
+            PbCommitMessageLintsError::PluginTimeoutError(plugin, seconds) => write!(
+                f,
+                "The plugin \"{}\" didn't finish within {} seconds, so it was stopped",
+                plugin, seconds
+            ),
+            PbCommitMessageLintsError::PluginCrashError(plugin, code, stderr) => {
+                match code {
+                    Some(code) => write!(
+                        f,
+                        "The plugin \"{}\" failed with exit code {}",
+                        plugin, code
+                    )?,
+                    None => write!(
+                        f,
+                        "The plugin \"{}\" was stopped before it finished",
+                        plugin
+                    )?,
+                }
+
+                if stderr.trim().is_empty() {
+                    Ok(())
+                } else {
+                    write!(f, ":\n{}", stderr.trim_end())
+                }
+            },
+            PbCommitMessageLintsError::PluginOutputError(plugin, reason) => write!(
+                f,
+                "The plugin \"{}\" didn't write a list of problems as JSON:\n{}",
+                plugin, reason
+            ),
+            PbCommitMessageLintsError::ScriptError(script, reason) => {
+                write!(f, "Couldn't run the script \"{}\":\n{}", script, reason)
+            },
+            PbCommitMessageLintsError::ScriptBudgetError(script, budget) => write!(
+                f,
+                "The script \"{}\" used more than its budget of {} operations, so it was stopped",
+                script, budget
+            ),
+            PbCommitMessageLintsError::LintAlreadyRegisteredError(name) => {
+                write!(f, "A lint called \"{}\" has already been registered", name)
+            },
+            PbCommitMessageLintsError::ProfileError(name, reason) => {
+                write!(f, "The profile \"{}\" isn't valid:\n{}", name, reason)
+            },
+            PbCommitMessageLintsError::ProfileNotFoundError(name) => {
+                write!(f, "There's no profile called \"{}\"", name)
+            },
+            PbCommitMessageLintsError::DefaultBranchNotFoundError => write!(
+                f,
+                "Couldn't find the default branch of \"origin\", you can give a range of commits \
+                 to check instead"
+            ),
         }
     }
 }
@@ -90,6 +209,24 @@ impl From<serde_yaml::Error> for PbCommitMessageLintsError {
     }
 }
 
+impl From<serde_json::Error> for PbCommitMessageLintsError {
+    fn from(error: serde_json::Error) -> Self {
+        PbCommitMessageLintsError::SerializeError(format!("{}", error))
+    }
+}
+
+impl From<toml::de::Error> for PbCommitMessageLintsError {
+    fn from(error: toml::de::Error) -> Self {
+        PbCommitMessageLintsError::RepoFileError(format!("{}", error))
+    }
+}
+
+impl From<toml::ser::Error> for PbCommitMessageLintsError {
+    fn from(error: toml::ser::Error) -> Self {
+        PbCommitMessageLintsError::RepoFileError(format!("{}", error))
+    }
+}
+
 impl From<std::io::Error> for PbCommitMessageLintsError {
     fn from(error: std::io::Error) -> Self {
         PbCommitMessageLintsError::IoError(format!("{}", error))
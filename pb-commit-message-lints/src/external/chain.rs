@@ -0,0 +1,289 @@
+use std::{
+    fmt::{Display, Formatter},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use git2::Repository;
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::{
+        hooks::repository_hooks_dir,
+        vcs::{Git2, Vcs},
+    },
+};
+
+/// The suffix of a hook in the hooks directory that runs after ours
+pub const CHAINED_HOOK_SUFFIX: &str = ".local";
+
+fn config_key(hook: &str) -> String {
+    format!("pb.hooks.{}.chain", hook)
+}
+
+/// A chained hook that didn't succeed
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainFailure {
+    hook: PathBuf,
+    code: Option<i32>,
+}
+
+impl ChainFailure {
+    #[must_use]
+    pub fn new(hook: PathBuf, code: Option<i32>) -> ChainFailure {
+        ChainFailure { hook, code }
+    }
+
+    #[must_use]
+    pub fn hook(&self) -> &Path {
+        &self.hook
+    }
+
+    /// The code the hook exited with, or 1 if it was stopped by a signal
+    #[must_use]
+    pub fn code(&self) -> i32 {
+        self.code.unwrap_or(1)
+    }
+}
+
+impl Display for ChainFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            Some(code) => write!(
+                f,
+                "error: The chained hook `{}` failed with exit code {}",
+                self.hook.display(),
+                code
+            ),
+            None => write!(
+                f,
+                "error: The chained hook `{}` was stopped before it finished",
+                self.hook.display()
+            ),
+        }
+    }
+}
+
+/// The hooks to run after one of ours
+///
+/// This is `<hook>.local` in the hooks directory if there is one, followed by
+/// any listed in `pb.hooks.<hook>.chain`
+///
+/// # Errors
+///
+/// If the config can't be read
+pub fn chained_hooks(
+    config: &dyn Vcs,
+    hooks_dir: Option<&Path>,
+    hook: &str,
+) -> Result<Vec<PathBuf>, PbCommitMessageLintsError> {
+    let local = hooks_dir
+        .map(|dir| dir.join(format!("{}{}", hook, CHAINED_HOOK_SUFFIX)))
+        .filter(|path| path.is_file());
+    let configured = config
+        .get_str(&config_key(hook))?
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+
+    Ok(local.into_iter().chain(configured).collect())
+}
+
+/// Run the chained hooks in order, with the arguments and input our hook was
+/// given, and return the ones that failed
+///
+/// Every hook is run even if an earlier one fails. Without any input the
+/// hooks read from the same stdin as ours
+///
+/// # Errors
+///
+/// If a hook can't be started
+pub fn run_chained_hooks(
+    hooks: &[PathBuf],
+    arguments: &[String],
+    stdin: Option<&[u8]>,
+) -> Result<Vec<ChainFailure>, PbCommitMessageLintsError> {
+    let mut failures = vec![];
+
+    for hook in hooks {
+        let status = run_hook(hook, arguments, stdin).map_err(|error| {
+            PbCommitMessageLintsError::ChainedHookError(
+                hook.to_string_lossy().to_string(),
+                format!("{}", error),
+            )
+        })?;
+
+        if !status.success() {
+            failures.push(ChainFailure::new(hook.clone(), status.code()));
+        }
+    }
+
+    Ok(failures)
+}
+
+fn run_hook(
+    hook: &Path,
+    arguments: &[String],
+    stdin: Option<&[u8]>,
+) -> std::io::Result<std::process::ExitStatus> {
+    let mut child = Command::new(hook)
+        .args(arguments)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        })
+        .spawn()?;
+
+    if let (Some(input), Some(mut child_stdin)) = (stdin, child.stdin.take()) {
+        child_stdin.write_all(input)?;
+    }
+
+    child.wait()
+}
+
+/// Run the hooks chained to one of ours, for the repository we're in
+///
+/// Outside of a repository there's nothing to chain to. The chain is only
+/// read from git config, never the repository's own config file, so cloning
+/// a repository can't make us run its commands
+///
+/// # Errors
+///
+/// If the config can't be read, or a hook can't be started
+pub fn run_chain(
+    hook: &str,
+    current_dir: &Path,
+    arguments: &[String],
+    stdin: Option<&[u8]>,
+) -> Result<Vec<ChainFailure>, PbCommitMessageLintsError> {
+    let repository = match Repository::discover(current_dir) {
+        Ok(repository) => repository,
+        Err(_) => return Ok(vec![]),
+    };
+    let config = Git2::for_repository(&repository)?;
+    let hooks_dir = repository_hooks_dir(&repository)?;
+
+    run_chained_hooks(
+        &chained_hooks(&config, Some(&hooks_dir), hook)?,
+        arguments,
+        stdin,
+    )
+}
+
+#[cfg(test)]
+mod tests_chained_hooks {
+    use std::{collections::HashMap, fs, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::external::{chain::chained_hooks, vcs::InMemory};
+
+    #[test]
+    fn there_are_none_by_default() {
+        let dir = TempDir::new().unwrap();
+        let mut strings = HashMap::new();
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(vec![]),
+            chained_hooks(&config, Some(dir.path()), "commit-msg")
+        );
+    }
+
+    #[test]
+    fn the_local_hook_runs_before_configured_ones() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("commit-msg.local"), "#!/bin/sh\n").unwrap();
+        fs::write(dir.path().join("pre-commit.local"), "#!/bin/sh\n").unwrap();
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.hooks.commit-msg.chain".into(),
+            "scripts/check-message /opt/hooks/commit-msg".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(vec![
+                dir.path().join("commit-msg.local"),
+                PathBuf::from("scripts/check-message"),
+                PathBuf::from("/opt/hooks/commit-msg")
+            ]),
+            chained_hooks(&config, Some(dir.path()), "commit-msg")
+        );
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests_run_chained_hooks {
+    use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::chain::{run_chained_hooks, ChainFailure},
+    };
+
+    fn write_hook(dir: &TempDir, name: &str, script: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn the_arguments_and_input_are_passed_on() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("output");
+        let hook = write_hook(
+            &dir,
+            "pre-push.local",
+            &format!("echo \"$@\" > {0}; cat >> {0}", output.display()),
+        );
+
+        let failures = run_chained_hooks(
+            &[hook],
+            &["origin".into(), "git@example.com:repo.git".into()],
+            Some(b"refs/heads/main 1 refs/heads/main 2\n"),
+        );
+
+        assert_eq!(Ok(vec![]), failures);
+        assert_eq!(
+            "origin git@example.com:repo.git\nrefs/heads/main 1 refs/heads/main 2\n",
+            fs::read_to_string(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn every_failure_is_returned() {
+        let dir = TempDir::new().unwrap();
+        let first = write_hook(&dir, "first", "exit 7");
+        let second = write_hook(&dir, "second", "exit 0");
+        let third = write_hook(&dir, "third", "exit 1");
+
+        assert_eq!(
+            Ok(vec![
+                ChainFailure::new(first.clone(), Some(7)),
+                ChainFailure::new(third.clone(), Some(1))
+            ]),
+            run_chained_hooks(&[first, second, third], &[], Some(b""))
+        );
+    }
+
+    #[test]
+    fn missing_hooks_are_an_error() {
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("missing");
+
+        assert!(matches!(
+            run_chained_hooks(&[missing], &[], Some(b"")),
+            Err(PbCommitMessageLintsError::ChainedHookError(_, _))
+        ));
+    }
+}
@@ -0,0 +1,453 @@
+use std::iter;
+
+use git2::{Oid, Repository, Revwalk, Sort};
+
+use crate::errors::PbCommitMessageLintsError;
+
+const DEFAULT_REMOTE: &str = "origin";
+const REMOTE_HEAD: &str = "HEAD";
+const FALLBACK_DEFAULT_BRANCHES: [&str; 2] = ["main", "master"];
+const RANGE_SEPARATOR: &str = "..";
+
+/// A commit that is already in the history of the repository
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Commit {
+    id: String,
+    message: String,
+}
+
+impl Commit {
+    #[must_use]
+    pub fn new(id: &str, message: &str) -> Commit {
+        Commit {
+            id: id.into(),
+            message: message.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Get the commits in a range like `main..HEAD`, oldest first
+///
+/// A single revision gets that commit and all of its ancestors, like
+/// `git log` does
+///
+/// # Errors
+///
+/// If the range isn't valid, or the history can't be read
+pub fn commits_in_range(
+    repository: &Repository,
+    range: &str,
+) -> Result<Vec<Commit>, PbCommitMessageLintsError> {
+    let mut revwalk = oldest_first(repository)?;
+
+    if range.contains(RANGE_SEPARATOR) {
+        revwalk.push_range(range).map_err(history_error)?;
+    } else {
+        let id = repository
+            .revparse_single(range)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(history_error)?
+            .id();
+        revwalk.push(id).map_err(history_error)?;
+    }
+
+    collect_commits(repository, revwalk)
+}
+
+/// The commits on this branch that aren't on the default branch of `origin`
+///
+/// # Errors
+///
+/// If there's no default branch, or the history can't be read
+pub fn default_range(repository: &Repository) -> Result<String, PbCommitMessageLintsError> {
+    let default_branch = remote_default_branch(repository, DEFAULT_REMOTE)
+        .ok_or(PbCommitMessageLintsError::DefaultBranchNotFoundError)?;
+    let head = repository
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(history_error)?
+        .id();
+    let base = repository
+        .merge_base(head, default_branch)
+        .map_err(history_error)?;
+
+    Ok(format!("{}{}HEAD", base, RANGE_SEPARATOR))
+}
+
+/// Where the `HEAD` of a remote points, or failing that its `main` or
+/// `master` branch
+#[must_use]
+pub fn remote_default_branch(repository: &Repository, remote: &str) -> Option<Oid> {
+    iter::once(REMOTE_HEAD)
+        .chain(FALLBACK_DEFAULT_BRANCHES.iter().copied())
+        .find_map(|branch| {
+            repository
+                .find_reference(&format!("refs/remotes/{}/{}", remote, branch))
+                .and_then(|reference| reference.resolve())
+                .and_then(|reference| reference.peel_to_commit())
+                .ok()
+        })
+        .map(|commit| commit.id())
+}
+
+/// The commits that moving a ref from `old` to `new` adds, like a push does,
+/// oldest first
+///
+/// Deleted refs, and refs to something other than a commit, add nothing. Tags
+/// are peeled to the commit they point at. New refs, or refs where we don't
+/// have the old commit, add every commit that isn't already reachable from
+/// the refs matching `known_refs` or from `base`, like `git rev-list <new>
+/// --not --glob=<known_refs> <base>`
+///
+/// # Errors
+///
+/// If the ids aren't valid, or the history can't be read
+pub fn updated_commits(
+    repository: &Repository,
+    old: &str,
+    new: &str,
+    known_refs: &str,
+    base: Option<Oid>,
+) -> Result<Vec<Commit>, PbCommitMessageLintsError> {
+    let new_id = Oid::from_str(new).map_err(history_error)?;
+
+    if new_id.is_zero() {
+        return Ok(vec![]);
+    }
+
+    let new_commit = match peel_to_commit(repository, new_id).map_err(history_error)? {
+        Some(commit) => commit,
+        None => return Ok(vec![]),
+    };
+    let old_commit = Oid::from_str(old)
+        .ok()
+        .filter(|id| !id.is_zero())
+        .and_then(|id| peel_to_commit(repository, id).ok().flatten());
+
+    let mut revwalk = oldest_first(repository)?;
+    revwalk.push(new_commit).map_err(history_error)?;
+    match old_commit {
+        Some(old_commit) => revwalk.hide(old_commit),
+        None => revwalk
+            .hide_glob(known_refs)
+            .and_then(|_| base.map_or(Ok(()), |base| revwalk.hide(base))),
+    }
+    .map_err(history_error)?;
+
+    collect_commits(repository, revwalk)
+}
+
+/// The commit an object is, or points to, if it's a commit or a tag of one
+fn peel_to_commit(repository: &Repository, id: Oid) -> Result<Option<Oid>, git2::Error> {
+    repository
+        .find_object(id, None)
+        .map(|object| object.peel_to_commit().ok().map(|commit| commit.id()))
+}
+
+fn oldest_first(repository: &Repository) -> Result<Revwalk<'_>, PbCommitMessageLintsError> {
+    let mut revwalk = repository.revwalk().map_err(history_error)?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)
+        .map_err(history_error)?;
+
+    Ok(revwalk)
+}
+
+fn collect_commits(
+    repository: &Repository,
+    revwalk: Revwalk<'_>,
+) -> Result<Vec<Commit>, PbCommitMessageLintsError> {
+    revwalk
+        .map(|id| id.and_then(|id| find_commit(repository, id)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(history_error)
+}
+
+fn find_commit(repository: &Repository, id: Oid) -> Result<Commit, git2::Error> {
+    repository.find_commit(id).map(|commit| {
+        Commit::new(
+            &id.to_string(),
+            &String::from_utf8_lossy(commit.message_bytes()),
+        )
+    })
+}
+
+fn history_error(error: git2::Error) -> PbCommitMessageLintsError {
+    PbCommitMessageLintsError::HistoryError(error.message().into())
+}
+
+#[cfg(test)]
+mod tests_commits_in_range {
+    use git2::{Oid, Repository, Signature};
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::commits::{commits_in_range, default_range, updated_commits},
+    };
+
+    const ZERO_ID: &str = "0000000000000000000000000000000000000000";
+
+    fn commit(repository: &Repository, message: &str) -> Oid {
+        let signature = Signature::now("Billie Thompson", "billie@example.com").unwrap();
+        let tree = repository
+            .index()
+            .and_then(|mut index| index.write_tree())
+            .and_then(|id| repository.find_tree(id))
+            .unwrap();
+        let parents = repository
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents.iter().collect::<Vec<_>>(),
+            )
+            .unwrap()
+    }
+
+    fn messages(repository: &Repository, range: &str) -> Vec<String> {
+        commits_in_range(repository, range)
+            .unwrap()
+            .iter()
+            .map(|commit| commit.message().into())
+            .collect()
+    }
+
+    #[test]
+    fn ranges_are_oldest_first() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+        commit(&repository, "Second\n");
+        commit(&repository, "Third\n");
+
+        assert_eq!(
+            vec!["Second\n".to_string(), "Third\n".into()],
+            messages(&repository, &format!("{}..HEAD", first))
+        );
+    }
+
+    #[test]
+    fn a_single_revision_includes_its_ancestors() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, "First\n");
+        commit(&repository, "Second\n");
+
+        assert_eq!(
+            vec!["First\n".to_string(), "Second\n".into()],
+            messages(&repository, "HEAD")
+        );
+    }
+
+    #[test]
+    fn the_default_range_starts_where_the_branch_left_origin() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+        repository
+            .reference("refs/remotes/origin/main", first, false, "test")
+            .unwrap();
+        repository
+            .reference_symbolic(
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/main",
+                false,
+                "test",
+            )
+            .unwrap();
+        commit(&repository, "Second\n");
+
+        assert_eq!(Ok(format!("{}..HEAD", first)), default_range(&repository));
+    }
+
+    #[test]
+    fn there_is_no_default_range_without_origin() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, "First\n");
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::DefaultBranchNotFoundError),
+            default_range(&repository)
+        );
+    }
+
+    fn tag(repository: &Repository, name: &str, target: Oid) -> Oid {
+        let signature = Signature::now("Billie Thompson", "billie@example.com").unwrap();
+        let target = repository.find_object(target, None).unwrap();
+
+        repository
+            .tag(name, &target, &signature, "An annotated tag\n", false)
+            .unwrap()
+    }
+
+    fn updated_messages(
+        repository: &Repository,
+        old: &str,
+        new: &str,
+        known_refs: &str,
+    ) -> Vec<String> {
+        updated_commits(repository, old, new, known_refs, None)
+            .unwrap()
+            .iter()
+            .map(|commit| commit.message().into())
+            .collect()
+    }
+
+    #[test]
+    fn updating_a_ref_adds_the_commits_since_the_old_one() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+        let second = commit(&repository, "Second\n");
+
+        assert_eq!(
+            vec!["Second\n".to_string()],
+            updated_messages(
+                &repository,
+                &first.to_string(),
+                &second.to_string(),
+                "refs/*"
+            )
+        );
+    }
+
+    #[test]
+    fn deleting_a_ref_adds_nothing() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+
+        assert_eq!(
+            Vec::<String>::new(),
+            updated_messages(&repository, &first.to_string(), ZERO_ID, "refs/*")
+        );
+    }
+
+    #[test]
+    fn new_refs_add_the_commits_no_known_ref_has() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+        repository
+            .reference("refs/heads/main", first, true, "test")
+            .unwrap();
+        repository
+            .reference("refs/remotes/origin/main", first, false, "test")
+            .unwrap();
+        repository.set_head_detached(first).unwrap();
+        let second = commit(&repository, "Second\n");
+
+        assert_eq!(
+            vec!["Second\n".to_string()],
+            updated_messages(&repository, ZERO_ID, &second.to_string(), "refs/*")
+        );
+        assert_eq!(
+            vec!["Second\n".to_string()],
+            updated_messages(
+                &repository,
+                ZERO_ID,
+                &second.to_string(),
+                "refs/remotes/origin/*"
+            )
+        );
+        assert_eq!(
+            vec!["First\n".to_string(), "Second\n".into()],
+            updated_messages(
+                &repository,
+                ZERO_ID,
+                &second.to_string(),
+                "refs/remotes/upstream/*"
+            )
+        );
+    }
+
+    #[test]
+    fn new_refs_add_nothing_their_base_has() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+        let second = commit(&repository, "Second\n");
+
+        assert_eq!(
+            vec!["Second\n".to_string()],
+            updated_commits(
+                &repository,
+                ZERO_ID,
+                &second.to_string(),
+                "refs/remotes/upstream/*",
+                Some(first)
+            )
+            .unwrap()
+            .iter()
+            .map(|commit| commit.message().to_string())
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn annotated_tags_are_peeled_to_their_commit() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        let first = commit(&repository, "First\n");
+        let first_tag = tag(&repository, "v1", first);
+        let second = commit(&repository, "Second\n");
+        let second_tag = tag(&repository, "v2", second);
+
+        assert_eq!(
+            vec!["Second\n".to_string()],
+            updated_messages(
+                &repository,
+                &first_tag.to_string(),
+                &second_tag.to_string(),
+                "refs/*"
+            )
+        );
+        assert_eq!(
+            Vec::<String>::new(),
+            updated_messages(
+                &repository,
+                ZERO_ID,
+                &second_tag.to_string(),
+                "refs/heads/*"
+            )
+        );
+    }
+
+    #[test]
+    fn objects_that_are_not_commits_add_nothing() {
+        let directory = TempDir::new().unwrap();
+        let repository = Repository::init(directory.path()).unwrap();
+        commit(&repository, "First\n");
+        let tree = repository
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .unwrap()
+            .id();
+
+        assert_eq!(
+            Vec::<String>::new(),
+            updated_messages(&repository, ZERO_ID, &tree.to_string(), "refs/*")
+        );
+    }
+}
@@ -0,0 +1,556 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use git2::Repository;
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::{chain::CHAINED_HOOK_SUFFIX, vcs::Vcs},
+};
+
+/// The hooks that get installed, and the binary each one runs
+pub const HOOKS: [(&str, &str); 4] = [
+    ("commit-msg", "pb-commit-msg"),
+    ("pre-commit", "pb-pre-commit"),
+    ("pre-push", "pb-pre-push"),
+    ("prepare-commit-msg", "pb-prepare-commit-msg"),
+];
+
+/// The config key git reads the shared hooks directory from
+pub const CONFIG_HOOKS_PATH: &str = "core.hooksPath";
+/// The config key git reads the init template directory from
+pub const CONFIG_TEMPLATE_DIR: &str = "init.templateDir";
+
+const MANIFEST_FILE: &str = ".pb-git-hooks-installed";
+const TEMPLATE_HOOKS_DIR: &str = "hooks";
+const GIT_DIR: &str = ".git";
+const COMMON_DIR_FILE: &str = "commondir";
+
+fn hook_script(binary: &str) -> String {
+    format!(
+        "#!/bin/sh\n# Installed by pb-git-hooks, remove it with `pb-git-hooks uninstall`\nexec {} \
+         \"$@\"\n",
+        binary
+    )
+}
+
+/// The directory git runs the hooks of a repository from
+///
+/// This is `core.hooksPath` if it's set, otherwise the hooks directory of
+/// the main repository, which worktrees share
+///
+/// # Errors
+///
+/// If the repository config can't be read
+pub fn repository_hooks_dir(repository: &Repository) -> Result<PathBuf, PbCommitMessageLintsError> {
+    let git_dir = repository.path();
+    let base = repository.workdir().unwrap_or(git_dir);
+
+    if let Ok(hooks_path) = repository.config()?.get_path(CONFIG_HOOKS_PATH) {
+        return Ok(base.join(hooks_path));
+    }
+
+    let common_dir = fs::read_to_string(git_dir.join(COMMON_DIR_FILE))
+        .map(|common_dir| git_dir.join(common_dir.trim()))
+        .unwrap_or_else(|_| git_dir.to_path_buf());
+
+    Ok(common_dir.join("hooks"))
+}
+
+/// Hooks that are shared by repositories, rather than being in one
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum SharedHooks {
+    /// Every repository runs the hooks in `core.hooksPath`
+    Global,
+    /// New repositories get a copy of the hooks in `init.templateDir`
+    Template,
+}
+
+impl SharedHooks {
+    #[must_use]
+    pub fn config_key(self) -> &'static str {
+        match self {
+            SharedHooks::Global => CONFIG_HOOKS_PATH,
+            SharedHooks::Template => CONFIG_TEMPLATE_DIR,
+        }
+    }
+
+    fn hooks_dir(self, configured: &Path) -> PathBuf {
+        match self {
+            SharedHooks::Global => configured.to_path_buf(),
+            SharedHooks::Template => configured.join(TEMPLATE_HOOKS_DIR),
+        }
+    }
+
+    /// The hooks directory, if one has been configured
+    ///
+    /// # Errors
+    ///
+    /// If the config can't be read
+    pub fn find(self, config: &dyn Vcs) -> Result<Option<PathBuf>, PbCommitMessageLintsError> {
+        Ok(config
+            .get_str(self.config_key())?
+            .map(|configured| self.hooks_dir(Path::new(configured))))
+    }
+
+    /// The hooks directory, configuring the default if there isn't one
+    ///
+    /// # Errors
+    ///
+    /// If the config can't be read or written
+    pub fn find_or_configure(
+        self,
+        config: &mut dyn Vcs,
+        default: &Path,
+    ) -> Result<PathBuf, PbCommitMessageLintsError> {
+        if let Some(dir) = self.find(config)? {
+            return Ok(dir);
+        }
+
+        config.set_str(self.config_key(), &default.to_string_lossy())?;
+
+        Ok(self.hooks_dir(default))
+    }
+
+    /// Remove the directories once nothing is left in them, and the config
+    /// that points to them
+    ///
+    /// # Errors
+    ///
+    /// If the config can't be written, or the directories can't be removed
+    pub fn remove_if_empty(self, config: &mut dyn Vcs) -> Result<(), PbCommitMessageLintsError> {
+        let configured = match config.get_str(self.config_key())? {
+            Some(configured) => PathBuf::from(configured),
+            None => return Ok(()),
+        };
+
+        remove_dir_if_empty(&self.hooks_dir(&configured))?;
+        remove_dir_if_empty(&configured)?;
+
+        if !configured.exists() {
+            config.remove(self.config_key())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn remove_dir_if_empty(dir: &Path) -> Result<(), PbCommitMessageLintsError> {
+    let is_empty = fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false);
+
+    if is_empty {
+        fs::remove_dir(dir)?;
+    }
+
+    Ok(())
+}
+
+/// Write the hooks into a hooks directory, keeping a note of what was
+/// written so it can be uninstalled
+///
+/// Hooks that we didn't install are only replaced if `force` is set, and
+/// they're kept as `<hook>.local`, which is chained to run after ours
+///
+/// # Errors
+///
+/// If there are hooks we didn't install, or the files can't be written
+pub fn install(dir: &Path, force: bool) -> Result<Vec<String>, PbCommitMessageLintsError> {
+    let installed = read_manifest(dir);
+    let foreign = HOOKS
+        .iter()
+        .filter(|(hook, binary)| {
+            is_foreign(
+                &dir.join(hook),
+                binary,
+                installed.contains(&(*hook).to_string()),
+            )
+        })
+        .map(|(hook, _)| dir.join(hook))
+        .collect::<Vec<_>>();
+
+    if !foreign.is_empty() && !force {
+        return Err(PbCommitMessageLintsError::ForeignHookError(
+            foreign
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+        ));
+    }
+
+    fs::create_dir_all(dir)?;
+
+    for path in &foreign {
+        let chained = chained_path(path);
+        if chained.exists() {
+            return Err(PbCommitMessageLintsError::IoError(format!(
+                "There's already a chained hook at {}",
+                chained.to_string_lossy()
+            )));
+        }
+
+        fs::rename(path, chained)?;
+    }
+
+    for (hook, binary) in &HOOKS {
+        write_hook(&dir.join(hook), binary)?;
+    }
+
+    let hooks = HOOKS
+        .iter()
+        .map(|(hook, _)| (*hook).to_string())
+        .collect::<Vec<_>>();
+    fs::write(dir.join(MANIFEST_FILE), format!("{}\n", hooks.join("\n")))?;
+
+    Ok(hooks)
+}
+
+/// Remove the hooks that were installed into a hooks directory, putting
+/// back any hooks they replaced
+///
+/// Hooks that have been changed since they were installed are left alone
+///
+/// # Errors
+///
+/// If the files can't be removed
+pub fn uninstall(dir: &Path) -> Result<Vec<String>, PbCommitMessageLintsError> {
+    let mut removed = vec![];
+
+    for (hook, binary) in HOOKS
+        .iter()
+        .filter(|(hook, _)| read_manifest(dir).contains(&(*hook).to_string()))
+    {
+        let path = dir.join(hook);
+        if is_foreign(&path, binary, true) {
+            continue;
+        }
+
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+
+        let chained = chained_path(&path);
+        if chained.exists() {
+            fs::rename(chained, &path)?;
+        }
+
+        removed.push((*hook).to_string());
+    }
+
+    let manifest = dir.join(MANIFEST_FILE);
+    if manifest.exists() {
+        fs::remove_file(manifest)?;
+    }
+
+    Ok(removed)
+}
+
+/// Every repository in a directory, or any directory under it
+#[must_use]
+pub fn find_repositories(dir: &Path) -> Vec<PathBuf> {
+    let mut repositories = vec![];
+
+    if dir.join(GIT_DIR).exists() {
+        repositories.push(dir.to_path_buf());
+    }
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        let mut children = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name() != GIT_DIR)
+            .filter(|entry| matches!(entry.file_type(), Ok(file_type) if file_type.is_dir()))
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+        children.sort();
+
+        for child in children {
+            repositories.extend(find_repositories(&child));
+        }
+    }
+
+    repositories
+}
+
+fn read_manifest(dir: &Path) -> Vec<String> {
+    fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map(|manifest| manifest.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// A hook is ours if we installed it and it hasn't been changed since
+fn is_foreign(path: &Path, binary: &str, installed: bool) -> bool {
+    match fs::read_to_string(path) {
+        Ok(contents) => !installed || contents != hook_script(binary),
+        Err(_) => path.exists(),
+    }
+}
+
+fn chained_path(path: &Path) -> PathBuf {
+    let mut chained = path.as_os_str().to_owned();
+    chained.push(CHAINED_HOOK_SUFFIX);
+    PathBuf::from(chained)
+}
+
+fn write_hook(path: &Path, binary: &str) -> Result<(), PbCommitMessageLintsError> {
+    fs::write(path, hook_script(binary))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_install {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::hooks::{install, uninstall},
+    };
+
+    #[test]
+    fn every_hook_is_written() {
+        let dir = TempDir::new().unwrap();
+
+        install(dir.path(), false).unwrap();
+
+        assert_eq!(
+            "#!/bin/sh\n# Installed by pb-git-hooks, remove it with `pb-git-hooks \
+             uninstall`\nexec pb-commit-msg \"$@\"\n",
+            fs::read_to_string(dir.path().join("commit-msg")).unwrap()
+        );
+        assert!(dir.path().join("pre-commit").exists());
+        assert!(dir.path().join("pre-push").exists());
+        assert!(dir.path().join("prepare-commit-msg").exists());
+    }
+
+    #[test]
+    fn installing_twice_is_fine() {
+        let dir = TempDir::new().unwrap();
+
+        install(dir.path(), false).unwrap();
+
+        assert!(install(dir.path(), false).is_ok());
+    }
+
+    #[test]
+    fn other_hooks_are_not_replaced() {
+        let dir = TempDir::new().unwrap();
+        let hook = dir.path().join("pre-commit");
+        fs::write(&hook, "#!/bin/sh\nmake lint\n").unwrap();
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ForeignHookError(vec![hook
+                .to_string_lossy()
+                .to_string()])),
+            install(dir.path(), false)
+        );
+        assert!(!dir.path().join("commit-msg").exists());
+    }
+
+    #[test]
+    fn other_hooks_are_put_back_after_being_replaced() {
+        let dir = TempDir::new().unwrap();
+        let hook = dir.path().join("pre-commit");
+        fs::write(&hook, "#!/bin/sh\nmake lint\n").unwrap();
+
+        install(dir.path(), true).unwrap();
+        assert!(fs::read_to_string(&hook).unwrap().contains("pb-pre-commit"));
+        assert_eq!(
+            "#!/bin/sh\nmake lint\n",
+            fs::read_to_string(dir.path().join("pre-commit.local")).unwrap()
+        );
+
+        uninstall(dir.path()).unwrap();
+        assert_eq!("#!/bin/sh\nmake lint\n", fs::read_to_string(&hook).unwrap());
+        assert_eq!(
+            vec!["pre-commit"],
+            fs::read_dir(dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn changed_hooks_are_left_when_uninstalling() {
+        let dir = TempDir::new().unwrap();
+        install(dir.path(), false).unwrap();
+        let hook = dir.path().join("pre-push");
+        fs::write(&hook, "#!/bin/sh\nmake test\n").unwrap();
+
+        assert_eq!(
+            Ok(vec![
+                "commit-msg".to_string(),
+                "pre-commit".to_string(),
+                "prepare-commit-msg".to_string()
+            ]),
+            uninstall(dir.path())
+        );
+        assert!(hook.exists());
+    }
+}
+
+#[cfg(test)]
+mod tests_hooks_dirs {
+    use std::{collections::HashMap, fs, path::PathBuf, process::Command};
+
+    use git2::Repository;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::external::{
+        hooks::{find_repositories, repository_hooks_dir, SharedHooks},
+        vcs::InMemory,
+    };
+
+    #[test]
+    fn repositories_use_their_own_hooks_dir() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+
+        assert_eq!(
+            repository.path().join("hooks"),
+            repository_hooks_dir(&repository).unwrap()
+        );
+    }
+
+    #[test]
+    fn the_hooks_path_is_relative_to_the_working_directory() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        repository
+            .config()
+            .unwrap()
+            .set_str("core.hooksPath", ".githooks")
+            .unwrap();
+
+        assert_eq!(
+            dir.path().join(".githooks"),
+            repository_hooks_dir(&repository).unwrap()
+        );
+    }
+
+    #[test]
+    fn worktrees_share_the_hooks_of_the_main_repository() {
+        let dir = TempDir::new().unwrap();
+        let main = dir.path().join("main");
+        let repository = Repository::init(&main).unwrap();
+        for arguments in &[
+            vec!["commit", "--allow-empty", "-m", "Initial commit"],
+            vec!["worktree", "add", "../other"],
+        ] {
+            Command::new("git")
+                .current_dir(&main)
+                .args([
+                    "-c",
+                    "user.name=Billie",
+                    "-c",
+                    "user.email=billie@example.com",
+                ])
+                .args(arguments)
+                .output()
+                .unwrap();
+        }
+        let worktree = Repository::open(dir.path().join("other")).unwrap();
+        fs::create_dir_all(repository.path().join("hooks")).unwrap();
+
+        assert_eq!(
+            fs::canonicalize(repository.path().join("hooks")).unwrap(),
+            fs::canonicalize(repository_hooks_dir(&worktree).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn the_global_hooks_dir_is_configured_if_missing() {
+        let mut strings = HashMap::new();
+
+        {
+            let mut config = InMemory::new(&mut strings);
+
+            assert_eq!(
+                Ok(PathBuf::from("/hooks")),
+                SharedHooks::Global.find_or_configure(&mut config, &PathBuf::from("/hooks"))
+            );
+        }
+
+        assert_eq!(Some(&"/hooks".to_string()), strings.get("core.hooksPath"));
+    }
+
+    #[test]
+    fn the_template_hooks_are_in_the_template() {
+        let mut strings = HashMap::new();
+        strings.insert("init.templateDir".into(), "/template".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(Some(PathBuf::from("/template/hooks"))),
+            SharedHooks::Template.find(&config)
+        );
+    }
+
+    #[test]
+    fn the_template_is_forgotten_once_empty() {
+        let dir = TempDir::new().unwrap();
+        let template = dir.path().join("template");
+        fs::create_dir_all(template.join("hooks")).unwrap();
+        let mut strings = HashMap::new();
+        strings.insert(
+            "init.templateDir".into(),
+            template.to_string_lossy().to_string(),
+        );
+
+        {
+            let mut config = InMemory::new(&mut strings);
+
+            SharedHooks::Template.remove_if_empty(&mut config).unwrap();
+        }
+
+        assert!(!template.exists());
+        assert_eq!(None, strings.get("init.templateDir"));
+    }
+
+    #[test]
+    fn directories_with_other_things_in_are_kept() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("pre-commit"), "#!/bin/sh\n").unwrap();
+        let mut strings = HashMap::new();
+        strings.insert(
+            "core.hooksPath".into(),
+            dir.path().to_string_lossy().to_string(),
+        );
+
+        {
+            let mut config = InMemory::new(&mut strings);
+
+            SharedHooks::Global.remove_if_empty(&mut config).unwrap();
+        }
+
+        assert!(dir.path().exists());
+        assert!(strings.contains_key("core.hooksPath"));
+    }
+
+    #[test]
+    fn repositories_are_found_under_a_directory() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path().join("one")).unwrap();
+        Repository::init(dir.path().join("nested/two")).unwrap();
+        fs::create_dir(dir.path().join("not-a-repository")).unwrap();
+
+        assert_eq!(
+            vec![dir.path().join("nested/two"), dir.path().join("one")],
+            find_repositories(dir.path())
+        );
+    }
+}
@@ -1 +1,4 @@
+pub mod chain;
+pub mod commits;
+pub mod hooks;
 pub mod vcs;
@@ -1,7 +1,50 @@
 use crate::errors::PbCommitMessageLintsError;
-use git2::{Config, Repository};
+use git2::{Config, ConfigLevel, Repository};
 use serde::export::TryFrom;
-use std::{collections::HashMap, path::PathBuf, string::String};
+use std::{collections::HashMap, fs, path::PathBuf, string::String};
+use toml::{value::Table, Value};
+
+/// The file in the root of a repository that shares its configuration
+pub const REPO_CONFIG_FILE: &str = ".pb-git-hooks.toml";
+const REPO_CONFIG_SECTION: &str = "pb";
+/// The parts of the `pb` section the file can set, as anyone who can commit
+/// can change it, so it can't be trusted with settings that run commands
+const REPO_CONFIG_ALLOWED: [&str; 4] = ["allow-lint-skip", "customlint", "lint", "profile"];
+const WORKTREE_CONFIG_FILE: &str = "config.worktree";
+const CONFIG_WORKTREE_EXTENSION: &str = "extensions.worktreeConfig";
+
+/// Where a setting came from
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ConfigSource {
+    System,
+    Global,
+    Local,
+    Worktree,
+    RepoFile,
+    Profile,
+    Default,
+}
+
+impl ConfigSource {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigSource::System => "system",
+            ConfigSource::Global => "global",
+            ConfigSource::Local => "local",
+            ConfigSource::Worktree => "worktree",
+            ConfigSource::RepoFile => "repo-file",
+            ConfigSource::Profile => "profile",
+            ConfigSource::Default => "default",
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
 
 pub trait Vcs {
     /// # Errors
@@ -28,6 +71,19 @@ pub trait Vcs {
     ///
     /// If the config fails to writ
     fn remove(&mut self, name: &str) -> Result<(), PbCommitMessageLintsError>;
+    /// Where the value of a setting comes from, if it has been set
+    ///
+    /// # Errors
+    ///
+    /// If we can't read the config
+    fn get_source(&self, name: &str) -> Result<Option<ConfigSource>, PbCommitMessageLintsError>;
+    /// The name of every setting that starts with the prefix, in no
+    /// particular order
+    ///
+    /// # Errors
+    ///
+    /// If we can't read the config
+    fn get_keys(&self, prefix: &str) -> Result<Vec<String>, PbCommitMessageLintsError>;
 }
 
 pub struct InMemory<'a> {
@@ -74,11 +130,26 @@ impl Vcs for InMemory<'_> {
         self.store.remove(name);
         Ok(())
     }
+
+    fn get_source(&self, name: &str) -> Result<Option<ConfigSource>, PbCommitMessageLintsError> {
+        Ok(self.store.get(name).map(|_| ConfigSource::Local))
+    }
+
+    fn get_keys(&self, prefix: &str) -> Result<Vec<String>, PbCommitMessageLintsError> {
+        Ok(keys_with_prefix(self.store.keys(), prefix))
+    }
+}
+
+fn keys_with_prefix<'a>(keys: impl Iterator<Item = &'a String>, prefix: &str) -> Vec<String> {
+    keys.filter(|key| key.starts_with(prefix))
+        .cloned()
+        .collect()
 }
 
 pub struct Git2 {
     config_snapshot: git2::Config,
     config_live: git2::Config,
+    write_level: Option<ConfigLevel>,
 }
 
 impl Git2 {
@@ -87,14 +158,70 @@ impl Git2 {
         Git2 {
             config_snapshot: config.snapshot().unwrap(),
             config_live: config,
+            write_level: None,
+        }
+    }
+
+    /// The config git reads in a repository, written to the repository's
+    /// own config file
+    ///
+    /// This includes the worktree's config if `extensions.worktreeConfig` is
+    /// turned on, which takes priority over the rest
+    ///
+    /// # Errors
+    ///
+    /// If the config can't be opened
+    pub fn for_repository(repository: &Repository) -> Result<Git2, PbCommitMessageLintsError> {
+        Ok(Git2::new(repository_config(repository)?).with_write_level(ConfigLevel::Local))
+    }
+
+    /// The same config as [`Git2::for_repository`], written to the
+    /// worktree's config file
+    ///
+    /// Like git, this writes to the repository's config file instead if
+    /// `extensions.worktreeConfig` isn't turned on
+    ///
+    /// # Errors
+    ///
+    /// If the config can't be opened
+    pub fn for_worktree(repository: &Repository) -> Result<Git2, PbCommitMessageLintsError> {
+        let config = repository_config(repository)?;
+        let level = if worktree_config_enabled(&config)? {
+            ConfigLevel::App
+        } else {
+            ConfigLevel::Local
+        };
+
+        Ok(Git2::new(config).with_write_level(level))
+    }
+
+    /// Write to one level of the config, rather than the highest one there is
+    #[must_use]
+    pub fn with_write_level(mut self, level: ConfigLevel) -> Git2 {
+        self.write_level = Some(level);
+        self
+    }
+
+    fn write(
+        &mut self,
+        change: impl FnOnce(&mut Config) -> Result<(), git2::Error>,
+    ) -> Result<(), PbCommitMessageLintsError> {
+        match self.write_level {
+            Some(level) => change(&mut self.config_live.open_level(level)?)?,
+            None => change(&mut self.config_live)?,
         }
+
+        self.config_snapshot = self.config_live.snapshot()?;
+
+        Ok(())
     }
 
     fn config_defined(&self, lint_name: &str) -> Result<bool, PbCommitMessageLintsError> {
-        self.config_snapshot
-            .entries(Some(lint_name))
-            .map(|entries| entries.count() > 0)
-            .map_err(PbCommitMessageLintsError::from)
+        match self.config_snapshot.get_entry(lint_name) {
+            Ok(_) => Ok(true),
+            Err(error) if error.code() == git2::ErrorCode::NotFound => Ok(false),
+            Err(error) => Err(PbCommitMessageLintsError::from(error)),
+        }
     }
 }
 
@@ -134,42 +261,650 @@ impl Vcs for Git2 {
     }
 
     fn set_str(&mut self, name: &str, value: &str) -> Result<(), PbCommitMessageLintsError> {
-        self.config_live.set_str(name, value)?;
+        self.write(|config| config.set_str(name, value))
+    }
+
+    fn set_i64(&mut self, name: &str, value: i64) -> Result<(), PbCommitMessageLintsError> {
+        self.write(|config| config.set_i64(name, value))
+    }
+
+    fn remove(&mut self, name: &str) -> Result<(), PbCommitMessageLintsError> {
+        self.write(|config| config.remove(name))
+    }
+
+    /// Both of the user's files count as global, and the only config added
+    /// by an application is the worktree's
+    fn get_source(&self, name: &str) -> Result<Option<ConfigSource>, PbCommitMessageLintsError> {
+        match self.config_snapshot.get_entry(name) {
+            Ok(entry) => Ok(Some(match entry.level() {
+                ConfigLevel::ProgramData | ConfigLevel::System => ConfigSource::System,
+                ConfigLevel::XDG | ConfigLevel::Global => ConfigSource::Global,
+                ConfigLevel::Local | ConfigLevel::Highest => ConfigSource::Local,
+                ConfigLevel::App => ConfigSource::Worktree,
+            })),
+            Err(error) if error.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(error) => Err(PbCommitMessageLintsError::from(error)),
+        }
+    }
+
+    fn get_keys(&self, prefix: &str) -> Result<Vec<String>, PbCommitMessageLintsError> {
+        let mut keys = vec![];
+
+        for entry in &self.config_snapshot.entries(None)? {
+            if let Some(name) = entry?.name() {
+                if name.starts_with(prefix) && !keys.iter().any(|key| key == name) {
+                    keys.push(name.to_string());
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+/// The config of a repository, with the worktree's config at the application
+/// level, as git2 has no level for it
+fn repository_config(repository: &Repository) -> Result<Config, PbCommitMessageLintsError> {
+    let mut config = repository.config()?;
+
+    if worktree_config_enabled(&config)? {
+        config.add_file(
+            &repository.path().join(WORKTREE_CONFIG_FILE),
+            ConfigLevel::App,
+            false,
+        )?;
+    }
+
+    Ok(config)
+}
+
+fn worktree_config_enabled(config: &Config) -> Result<bool, PbCommitMessageLintsError> {
+    match config.get_bool(CONFIG_WORKTREE_EXTENSION) {
+        Ok(enabled) => Ok(enabled),
+        Err(error) if error.code() == git2::ErrorCode::NotFound => Ok(false),
+        Err(error) => Err(PbCommitMessageLintsError::from(error)),
+    }
+}
+
+/// The repository the directory is in, if it's in one
+fn discover(current_dir: PathBuf) -> Result<Option<Repository>, PbCommitMessageLintsError> {
+    match Repository::discover(current_dir) {
+        Ok(repository) => Ok(Some(repository)),
+        Err(error) if error.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(error) => Err(PbCommitMessageLintsError::from(error)),
+    }
+}
+
+/// The config of the repository the directory is in, or the user's config
+/// outside of a repository
+impl TryFrom<PathBuf> for Git2 {
+    type Error = PbCommitMessageLintsError;
+
+    fn try_from(current_dir: PathBuf) -> Result<Self, Self::Error> {
+        match discover(current_dir)? {
+            Some(repository) => Git2::for_repository(&repository),
+            None => Ok(Git2::new(Config::open_default()?)),
+        }
+    }
+}
+
+/// Configuration from a TOML file, where `pb.lint.duplicated-trailers` is
+/// the `duplicated-trailers` key of the `[lint]` table
+///
+/// Only lint settings are read from the file, anything else in it is ignored
+///
+/// A file that doesn't exist yet has no configuration, and is made when
+/// something is set
+pub struct TomlFile {
+    path: PathBuf,
+    document: Table,
+    store: HashMap<String, String>,
+}
+
+impl TomlFile {
+    /// # Errors
+    ///
+    /// If the file exists, but can't be read or isn't valid TOML
+    pub fn open(path: PathBuf) -> Result<TomlFile, PbCommitMessageLintsError> {
+        let document = if path.exists() {
+            toml::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Table::new()
+        };
+
+        Ok(TomlFile {
+            store: allowed_settings(&document),
+            path,
+            document,
+        })
+    }
+
+    fn set_value(&mut self, name: &str, value: Value) -> Result<(), PbCommitMessageLintsError> {
+        let (table, key) = self.table_for(name)?;
+        table.insert(key, value);
+        self.save()
+    }
+
+    /// The table a key belongs in, made if it isn't there yet
+    fn table_for(&mut self, name: &str) -> Result<(&mut Table, String), PbCommitMessageLintsError> {
+        let mut parts = key_parts(name)?;
+        let key = parts.pop().unwrap_or_default();
+        let mut table = &mut self.document;
 
-        let config = self.config_live.snapshot()?;
+        for part in parts {
+            table = match table
+                .entry(part.clone())
+                .or_insert_with(|| Value::Table(Table::new()))
+            {
+                Value::Table(child) => child,
+                _ => {
+                    return Err(PbCommitMessageLintsError::InvalidConfigurationValue(
+                        name.into(),
+                        part,
+                    ))
+                },
+            };
+        }
+
+        Ok((table, key))
+    }
 
-        self.config_snapshot = config;
+    fn save(&mut self) -> Result<(), PbCommitMessageLintsError> {
+        fs::write(&self.path, toml::to_string(&self.document)?)?;
+        self.store = allowed_settings(&self.document);
 
         Ok(())
     }
+}
+
+impl Vcs for TomlFile {
+    fn get_bool(&self, name: &str) -> Result<Option<bool>, PbCommitMessageLintsError> {
+        match self.store.get(name) {
+            None => Ok(None),
+            Some(raw_value) => Ok(Some(raw_value.parse()?)),
+        }
+    }
+
+    fn get_str(&self, name: &str) -> Result<Option<&str>, PbCommitMessageLintsError> {
+        Ok(self.store.get(name).map(String::as_str))
+    }
+
+    fn get_i64(&self, name: &str) -> Result<Option<i64>, PbCommitMessageLintsError> {
+        match self.store.get(name) {
+            None => Ok(None),
+            Some(raw_value) => Ok(Some(raw_value.parse()?)),
+        }
+    }
+
+    fn set_str(&mut self, name: &str, value: &str) -> Result<(), PbCommitMessageLintsError> {
+        let value = match value.parse() {
+            Ok(boolean) => Value::Boolean(boolean),
+            Err(_) => Value::String(value.into()),
+        };
+
+        self.set_value(name, value)
+    }
 
     fn set_i64(&mut self, name: &str, value: i64) -> Result<(), PbCommitMessageLintsError> {
-        self.config_live.set_i64(name, value)?;
+        self.set_value(name, Value::Integer(value))
+    }
 
-        let config = self.config_live.snapshot()?;
-        self.config_snapshot = config;
+    fn remove(&mut self, name: &str) -> Result<(), PbCommitMessageLintsError> {
+        let (table, key) = self.table_for(name)?;
+        table.remove(&key);
+        self.save()
+    }
 
-        Ok(())
+    fn get_source(&self, name: &str) -> Result<Option<ConfigSource>, PbCommitMessageLintsError> {
+        Ok(self.store.get(name).map(|_| ConfigSource::RepoFile))
+    }
+
+    fn get_keys(&self, prefix: &str) -> Result<Vec<String>, PbCommitMessageLintsError> {
+        Ok(keys_with_prefix(self.store.keys(), prefix))
+    }
+}
+
+/// Only the lint settings in the `pb` section of the configuration can go in
+/// the file
+fn key_parts(name: &str) -> Result<Vec<String>, PbCommitMessageLintsError> {
+    let mut parts = name.split('.').map(String::from).collect::<Vec<_>>();
+
+    if parts.len() < 2
+        || parts.remove(0) != REPO_CONFIG_SECTION
+        || !REPO_CONFIG_ALLOWED.contains(&parts[0].as_str())
+    {
+        return Err(PbCommitMessageLintsError::RepoFileError(format!(
+            "\"{}\" can't be set in {}",
+            name, REPO_CONFIG_FILE
+        )));
+    }
+
+    Ok(parts)
+}
+
+fn allowed_settings(document: &Table) -> HashMap<String, String> {
+    flatten(REPO_CONFIG_SECTION, document)
+        .into_iter()
+        .filter(|(name, _)| key_parts(name).is_ok())
+        .collect()
+}
+
+/// Turn the tables into dotted keys, and the values into the strings git
+/// config would have given, with lists separated by spaces
+fn flatten(prefix: &str, table: &Table) -> HashMap<String, String> {
+    table
+        .iter()
+        .flat_map(|(key, value)| {
+            let name = format!("{}.{}", prefix, key);
+
+            match value {
+                Value::Table(child) => flatten(&name, child).into_iter().collect::<Vec<_>>(),
+                Value::Array(items) => vec![(
+                    name,
+                    items
+                        .iter()
+                        .map(flatten_value)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                )],
+                other => vec![(name, flatten_value(other))],
+            }
+        })
+        .collect()
+}
+
+fn flatten_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Reads from the first layer that has a value, and writes to the first
+/// layer, so later layers are the defaults for earlier ones
+pub struct Layered<'a> {
+    layers: Vec<Box<dyn Vcs + 'a>>,
+}
+
+impl<'a> Layered<'a> {
+    #[must_use]
+    pub fn new(layers: Vec<Box<dyn Vcs + 'a>>) -> Layered<'a> {
+        Layered { layers }
+    }
+
+    /// The git config of a repository, over the configuration file in the
+    /// root of its working directory
+    ///
+    /// # Errors
+    ///
+    /// If the git config can't be opened, or the file can't be read
+    pub fn for_repository(
+        repository: &Repository,
+    ) -> Result<Layered<'a>, PbCommitMessageLintsError> {
+        Layered::with_repo_file(repository, Git2::for_repository(repository)?)
+    }
+
+    /// Some git config, over the configuration file in the root of the
+    /// repository's working directory
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be read
+    pub fn with_repo_file(
+        repository: &Repository,
+        config: Git2,
+    ) -> Result<Layered<'a>, PbCommitMessageLintsError> {
+        let mut layers: Vec<Box<dyn Vcs>> = vec![Box::new(config)];
+
+        if let Some(workdir) = repository.workdir() {
+            layers.push(Box::new(TomlFile::open(workdir.join(REPO_CONFIG_FILE))?));
+        }
+
+        Ok(Layered::new(layers))
+    }
+
+    fn top(&mut self) -> Result<&mut Box<dyn Vcs + 'a>, PbCommitMessageLintsError> {
+        self.layers.first_mut().ok_or_else(|| {
+            PbCommitMessageLintsError::RepoFileError("No configuration to write to".into())
+        })
+    }
+}
+
+impl Vcs for Layered<'_> {
+    fn get_bool(&self, name: &str) -> Result<Option<bool>, PbCommitMessageLintsError> {
+        for layer in &self.layers {
+            if let Some(value) = layer.get_bool(name)? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn get_str(&self, name: &str) -> Result<Option<&str>, PbCommitMessageLintsError> {
+        for layer in &self.layers {
+            if let Some(value) = layer.get_str(name)? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn get_i64(&self, name: &str) -> Result<Option<i64>, PbCommitMessageLintsError> {
+        for layer in &self.layers {
+            if let Some(value) = layer.get_i64(name)? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn set_str(&mut self, name: &str, value: &str) -> Result<(), PbCommitMessageLintsError> {
+        self.top()?.set_str(name, value)
+    }
+
+    fn set_i64(&mut self, name: &str, value: i64) -> Result<(), PbCommitMessageLintsError> {
+        self.top()?.set_i64(name, value)
     }
 
     fn remove(&mut self, name: &str) -> Result<(), PbCommitMessageLintsError> {
-        self.config_live.remove(name)?;
+        self.top()?.remove(name)
+    }
 
-        let config = self.config_live.snapshot()?;
-        self.config_snapshot = config;
+    fn get_source(&self, name: &str) -> Result<Option<ConfigSource>, PbCommitMessageLintsError> {
+        for layer in &self.layers {
+            if let Some(source) = layer.get_source(name)? {
+                return Ok(Some(source));
+            }
+        }
 
-        Ok(())
+        Ok(None)
+    }
+
+    fn get_keys(&self, prefix: &str) -> Result<Vec<String>, PbCommitMessageLintsError> {
+        let mut keys: Vec<String> = vec![];
+
+        for layer in &self.layers {
+            for key in layer.get_keys(prefix)? {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        Ok(keys)
     }
 }
 
-impl TryFrom<PathBuf> for Git2 {
+impl TryFrom<PathBuf> for Layered<'_> {
     type Error = PbCommitMessageLintsError;
 
     fn try_from(current_dir: PathBuf) -> Result<Self, Self::Error> {
-        Repository::discover(current_dir)
-            .and_then(|x| x.config())
-            .or_else(|_| Config::open_default())
-            .map(Git2::new)
-            .map_err(PbCommitMessageLintsError::from)
+        match discover(current_dir)? {
+            Some(repository) => Layered::for_repository(&repository),
+            None => Ok(Layered::new(vec![Box::new(Git2::new(
+                Config::open_default()?,
+            ))])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_toml_file {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::external::vcs::{TomlFile, Vcs};
+
+    #[test]
+    fn tables_are_read_as_sections_of_pb() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".pb-git-hooks.toml");
+        fs::write(
+            &path,
+            "allow-lint-skip = false\n\n[lint]\njira-issue-key-missing = \
+             \"warn\"\n\n[lint.duplicated-trailers]\ntrailers = [\"Signed-off-by\", \
+             \"Reviewed-by\"]\n",
+        )
+        .unwrap();
+
+        let file = TomlFile::open(path).unwrap();
+
+        assert_eq!(Ok(Some(false)), file.get_bool("pb.allow-lint-skip"));
+        assert_eq!(
+            Ok(Some("warn")),
+            file.get_str("pb.lint.jira-issue-key-missing")
+        );
+        assert_eq!(
+            Ok(Some("Signed-off-by Reviewed-by")),
+            file.get_str("pb.lint.duplicated-trailers.trailers")
+        );
+        assert_eq!(Ok(None), file.get_str("pb.lint.duplicated-trailers"));
+    }
+
+    #[test]
+    fn settings_other_than_lints_are_ignored() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".pb-git-hooks.toml");
+        fs::write(
+            &path,
+            "fix = true\n\n[plugin.example]\ncommand = \"touch \
+             PWNED\"\n\n[hooks.commit-msg]\nchain = \"touch \
+             PWNED\"\n\n[profile.platform]\njira-issue-key-missing = \"error\"\n",
+        )
+        .unwrap();
+
+        let file = TomlFile::open(path).unwrap();
+
+        assert_eq!(Ok(None), file.get_bool("pb.fix"));
+        assert_eq!(Ok(None), file.get_str("pb.plugin.example.command"));
+        assert_eq!(Ok(None), file.get_str("pb.hooks.commit-msg.chain"));
+        assert_eq!(Ok(Vec::<String>::new()), file.get_keys("pb.plugin."));
+        assert_eq!(
+            Ok(Some("error")),
+            file.get_str("pb.profile.platform.jira-issue-key-missing")
+        );
+    }
+
+    #[test]
+    fn setting_a_value_writes_the_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".pb-git-hooks.toml");
+        let mut file = TomlFile::open(path.clone()).unwrap();
+
+        file.set_str("pb.lint.jira-issue-key-missing", "warn")
+            .unwrap();
+        file.set_str("pb.lint.duplicated-trailers", "false")
+            .unwrap();
+
+        assert_eq!(
+            "[lint]\nduplicated-trailers = false\njira-issue-key-missing = \"warn\"\n",
+            fs::read_to_string(path).unwrap()
+        );
+        assert_eq!(
+            Ok(Some(false)),
+            file.get_bool("pb.lint.duplicated-trailers")
+        );
+    }
+
+    #[test]
+    fn only_pb_settings_can_be_set() {
+        let dir = TempDir::new().unwrap();
+        let mut file = TomlFile::open(dir.path().join(".pb-git-hooks.toml")).unwrap();
+
+        assert!(file.set_str("core.commentChar", ";").is_err());
+        assert!(file
+            .set_str("pb.plugin.example.command", "touch PWNED")
+            .is_err());
+        assert!(file.set_str("pb.hooks.commit-msg.chain", "true").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_layered {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::external::vcs::{InMemory, Layered, Vcs};
+
+    #[test]
+    fn earlier_layers_win_and_are_written_to() {
+        let mut top = HashMap::new();
+        top.insert("pb.lint.duplicated-trailers".into(), "warn".into());
+        let mut bottom = HashMap::new();
+        bottom.insert("pb.lint.duplicated-trailers".into(), "off".into());
+        bottom.insert("pb.lint.jira-issue-key-missing".into(), "error".into());
+
+        {
+            let mut layered = Layered::new(vec![
+                Box::new(InMemory::new(&mut top)),
+                Box::new(InMemory::new(&mut bottom)),
+            ]);
+
+            assert_eq!(
+                Ok(Some("warn")),
+                layered.get_str("pb.lint.duplicated-trailers")
+            );
+            assert_eq!(
+                Ok(Some("error")),
+                layered.get_str("pb.lint.jira-issue-key-missing")
+            );
+
+            layered
+                .set_str("pb.lint.pivotal-tracker-id-missing", "error")
+                .unwrap();
+        }
+
+        assert_eq!(
+            Some(&"error".to_string()),
+            top.get("pb.lint.pivotal-tracker-id-missing")
+        );
+        assert_eq!(None, bottom.get("pb.lint.pivotal-tracker-id-missing"));
+    }
+}
+
+#[cfg(test)]
+mod tests_git2 {
+    use std::fs;
+
+    use git2::Repository;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use crate::external::vcs::{ConfigSource, Git2, Vcs};
+
+    fn repository_with_worktree_config(dir: &TempDir, enabled: bool) -> Repository {
+        let repository = Repository::init(dir.path()).unwrap();
+        let mut config = repository.config().unwrap();
+        config.set_i32("core.repositoryformatversion", 1).unwrap();
+        config
+            .set_bool("extensions.worktreeConfig", enabled)
+            .unwrap();
+        fs::write(
+            repository.path().join("config.worktree"),
+            "[pb \"lint\"]\n\tduplicated-trailers = warn\n",
+        )
+        .unwrap();
+
+        repository
+    }
+
+    #[test]
+    fn worktree_config_wins_when_the_extension_is_on() {
+        let dir = TempDir::new().unwrap();
+        let repository = repository_with_worktree_config(&dir, true);
+        repository
+            .config()
+            .unwrap()
+            .set_str("pb.lint.duplicated-trailers", "off")
+            .unwrap();
+
+        let config = Git2::for_repository(&repository).unwrap();
+
+        assert_eq!(
+            Ok(Some("warn")),
+            config.get_str("pb.lint.duplicated-trailers")
+        );
+        assert_eq!(
+            Ok(Some(ConfigSource::Worktree)),
+            config.get_source("pb.lint.duplicated-trailers")
+        );
+    }
+
+    #[test]
+    fn worktree_config_is_ignored_when_the_extension_is_off() {
+        let dir = TempDir::new().unwrap();
+        let repository = repository_with_worktree_config(&dir, false);
+
+        let config = Git2::for_repository(&repository).unwrap();
+
+        assert_eq!(Ok(None), config.get_str("pb.lint.duplicated-trailers"));
+    }
+
+    #[test]
+    fn the_repository_config_is_written_to_by_default() {
+        let dir = TempDir::new().unwrap();
+        let repository = repository_with_worktree_config(&dir, true);
+
+        let mut config = Git2::for_repository(&repository).unwrap();
+        config
+            .set_str("pb.lint.jira-issue-key-missing", "error")
+            .unwrap();
+
+        let written = fs::read_to_string(repository.path().join("config")).unwrap();
+        assert!(
+            written.contains("jira-issue-key-missing = error"),
+            "{}",
+            written
+        );
+        assert_eq!(
+            Ok(Some(ConfigSource::Local)),
+            config.get_source("pb.lint.jira-issue-key-missing")
+        );
+    }
+
+    #[test]
+    fn the_worktree_config_can_be_written_to() {
+        let dir = TempDir::new().unwrap();
+        let repository = repository_with_worktree_config(&dir, true);
+
+        let mut config = Git2::for_worktree(&repository).unwrap();
+        config
+            .set_str("pb.lint.jira-issue-key-missing", "error")
+            .unwrap();
+
+        let written = fs::read_to_string(repository.path().join("config.worktree")).unwrap();
+        assert!(
+            written.contains("jira-issue-key-missing = error"),
+            "{}",
+            written
+        );
+        assert_eq!(
+            Ok(Some("error")),
+            config.get_str("pb.lint.jira-issue-key-missing")
+        );
+    }
+
+    #[test]
+    fn the_worktree_is_written_to_the_repository_config_without_the_extension() {
+        let dir = TempDir::new().unwrap();
+        let repository = repository_with_worktree_config(&dir, false);
+
+        let mut config = Git2::for_worktree(&repository).unwrap();
+        config
+            .set_str("pb.lint.jira-issue-key-missing", "error")
+            .unwrap();
+
+        let written = fs::read_to_string(repository.path().join("config")).unwrap();
+        assert!(
+            written.contains("jira-issue-key-missing = error"),
+            "{}",
+            written
+        );
     }
 }
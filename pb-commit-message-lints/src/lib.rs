@@ -4,3 +4,4 @@ pub mod author;
 pub mod errors;
 pub mod external;
 pub mod lints;
+pub mod output;
@@ -0,0 +1,1114 @@
+use std::{convert::TryFrom, fmt::Display, fs::File, io::Read, path::PathBuf};
+
+use regex::Regex;
+
+use crate::{errors::PbCommitMessageLintsError, external::vcs::Vcs};
+
+const CONFIG_COMMENT_CHAR: &str = "core.commentChar";
+const CONFIG_CLEANUP: &str = "commit.cleanup";
+const CONFIG_TRAILER_SEPARATORS: &str = "trailer.separators";
+const DEFAULT_COMMENT_CHAR: char = '#';
+const AUTO_COMMENT_CHAR: &str = "auto";
+const AUTO_COMMENT_CHAR_CANDIDATES: &str = "#;@!$%^&|:";
+const DEFAULT_TRAILER_SEPARATORS: &str = ":";
+const GIT_GENERATED_PREFIXES: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+const AUTOSQUASH_PREFIXES: [&str; 3] = ["fixup! ", "squash! ", "amend! "];
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+
+/// The character that starts a comment line, as set by `core.commentChar`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CommentChar {
+    Char(char),
+    /// Pick a character that no line of the message started with, as git does
+    Auto,
+}
+
+impl CommentChar {
+    fn resolve(self, lines: &[&str]) -> char {
+        match self {
+            CommentChar::Char(character) => character,
+            CommentChar::Auto => detect_comment_char(lines),
+        }
+    }
+}
+
+/// How git will clean up the message, as set by `commit.cleanup`
+///
+/// Whatever the mode, git cuts the message off at the scissors line when
+/// there is one, as `git commit --verbose` puts its diff below it
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CleanupMode {
+    /// Remove comments and blank lines at the start and end
+    Strip,
+    /// Remove blank lines at the start and end, but keep comments as part of
+    /// the message
+    Whitespace,
+    /// Leave the message exactly as it was written
+    Verbatim,
+    /// Like whitespace, and cut the message off at the scissors line even
+    /// when there's no diff below it
+    Scissors,
+    /// Git strips the message when it has been through an editor, which is
+    /// the only time there are comments for us to find
+    Default,
+}
+
+impl CleanupMode {
+    fn strips_comments(self) -> bool {
+        self == CleanupMode::Strip || self == CleanupMode::Default
+    }
+}
+
+impl std::convert::TryFrom<&str> for CleanupMode {
+    type Error = PbCommitMessageLintsError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        match from {
+            "strip" => Ok(CleanupMode::Strip),
+            "whitespace" => Ok(CleanupMode::Whitespace),
+            "verbatim" => Ok(CleanupMode::Verbatim),
+            "scissors" => Ok(CleanupMode::Scissors),
+            "default" => Ok(CleanupMode::Default),
+            _ => Err(PbCommitMessageLintsError::InvalidConfigurationValue(
+                CONFIG_CLEANUP.into(),
+                from.into(),
+            )),
+        }
+    }
+}
+
+/// The settings that change how git splits a commit message into its parts
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseConfiguration {
+    comment_char: CommentChar,
+    trailer_separators: String,
+    cleanup: CleanupMode,
+}
+
+impl ParseConfiguration {
+    #[must_use]
+    pub fn new(
+        comment_char: CommentChar,
+        trailer_separators: &str,
+        cleanup: CleanupMode,
+    ) -> ParseConfiguration {
+        ParseConfiguration {
+            comment_char,
+            trailer_separators: trailer_separators.into(),
+            cleanup,
+        }
+    }
+
+    #[must_use]
+    pub fn comment_char(&self) -> CommentChar {
+        self.comment_char
+    }
+
+    #[must_use]
+    pub fn trailer_separators(&self) -> &str {
+        &self.trailer_separators
+    }
+
+    #[must_use]
+    pub fn cleanup(&self) -> CleanupMode {
+        self.cleanup
+    }
+}
+
+impl Default for ParseConfiguration {
+    fn default() -> Self {
+        ParseConfiguration::new(
+            CommentChar::Char(DEFAULT_COMMENT_CHAR),
+            DEFAULT_TRAILER_SEPARATORS,
+            CleanupMode::Default,
+        )
+    }
+}
+
+/// Get the settings git would use to parse a commit message
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or
+/// `commit.cleanup` isn't a mode git knows about
+pub fn get_parse_configuration(
+    config: &dyn Vcs,
+) -> Result<ParseConfiguration, PbCommitMessageLintsError> {
+    let comment_char = match config.get_str(CONFIG_COMMENT_CHAR)? {
+        Some(AUTO_COMMENT_CHAR) => CommentChar::Auto,
+        Some(value) => CommentChar::Char(value.chars().next().unwrap_or(DEFAULT_COMMENT_CHAR)),
+        None => CommentChar::Char(DEFAULT_COMMENT_CHAR),
+    };
+    let cleanup = config
+        .get_str(CONFIG_CLEANUP)?
+        .map_or(Ok(CleanupMode::Default), CleanupMode::try_from)?;
+
+    Ok(ParseConfiguration::new(
+        comment_char,
+        config
+            .get_str(CONFIG_TRAILER_SEPARATORS)?
+            .unwrap_or(DEFAULT_TRAILER_SEPARATORS),
+        cleanup,
+    ))
+}
+
+/// Git picks the first candidate that no line of the message started with
+/// before it added its comments, so the first candidate that only ever starts
+/// comment-shaped lines is the one it chose
+fn detect_comment_char(lines: &[&str]) -> char {
+    AUTO_COMMENT_CHAR_CANDIDATES
+        .chars()
+        .find(|candidate| {
+            lines
+                .iter()
+                .filter(|line| line.starts_with(*candidate))
+                .all(|line| line.len() == 1 || line[1..].starts_with(' '))
+        })
+        .unwrap_or(DEFAULT_COMMENT_CHAR)
+}
+
+/// A single `Key: Value` line from the trailer block at the end of a message
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Trailer {
+    key: String,
+    value: String,
+    separator: char,
+    line: usize,
+}
+
+impl Trailer {
+    #[must_use]
+    pub fn new(key: &str, value: &str, separator: char, line: usize) -> Trailer {
+        Trailer {
+            key: key.into(),
+            value: value.into(),
+            separator,
+            line,
+        }
+    }
+
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// The line number (starting at 1) the trailer starts on
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Trailer keys are compared case-insensitively, as git does
+    #[must_use]
+    pub fn has_key(&self, key: &str) -> bool {
+        self.key.eq_ignore_ascii_case(key)
+    }
+}
+
+impl Display for Trailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{} {}", self.key, self.separator, self.value)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum LineKind {
+    Blank,
+    Comment,
+    Subject,
+    Body,
+    Trailer,
+    /// Everything from the scissors line down, such as the diff added by
+    /// `git commit --verbose`
+    Scissors,
+}
+
+impl LineKind {
+    fn is_content(self) -> bool {
+        self != LineKind::Comment && self != LineKind::Scissors
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CommitMessage {
+    contents: String,
+    kinds: Vec<LineKind>,
+    trailers: Vec<Trailer>,
+}
+
+impl CommitMessage {
+    #[must_use]
+    pub fn new(contents: String) -> CommitMessage {
+        CommitMessage::parse(contents, &ParseConfiguration::default())
+    }
+
+    #[must_use]
+    pub fn parse(contents: String, configuration: &ParseConfiguration) -> CommitMessage {
+        let lines = contents.lines().collect::<Vec<_>>();
+        let comment_char = configuration.comment_char().resolve(&lines);
+        let scissors = format!("{} {}", comment_char, SCISSORS);
+        let scissors_start = lines
+            .iter()
+            .position(|line| *line == scissors)
+            .unwrap_or(lines.len());
+        let mut kinds = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                if index >= scissors_start {
+                    LineKind::Scissors
+                } else {
+                    classify_line(line, comment_char, configuration.cleanup())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(subject) = kinds.iter().position(|kind| *kind == LineKind::Body) {
+            kinds[subject] = LineKind::Subject;
+        }
+
+        let trailers = match find_trailer_block(&lines, &kinds, configuration) {
+            Some((start, end)) => {
+                let trailers = parse_trailers(&lines, &kinds, start, end, configuration);
+                kinds[start..end]
+                    .iter_mut()
+                    .filter(|kind| **kind != LineKind::Comment)
+                    .for_each(|kind| *kind = LineKind::Trailer);
+                trailers
+            },
+            None => vec![],
+        };
+
+        CommitMessage {
+            contents,
+            kinds,
+            trailers,
+        }
+    }
+
+    /// Read a commit message from a file, parsing it with the given settings
+    ///
+    /// # Errors
+    ///
+    /// If the file can't be read
+    pub fn from_path(
+        path: PathBuf,
+        configuration: &ParseConfiguration,
+    ) -> Result<CommitMessage, PbCommitMessageLintsError> {
+        let mut file = File::open(path)?;
+        let mut buffer = String::new();
+
+        file.read_to_string(&mut buffer)
+            .map_err(PbCommitMessageLintsError::from)
+            .map(move |_| CommitMessage::parse(buffer, configuration))
+    }
+
+    /// The message without any comment lines, or anything below the scissors
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.lines_where(LineKind::is_content)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn matches_pattern(&self, re: &Regex) -> bool {
+        re.is_match(&self.text())
+    }
+
+    #[must_use]
+    pub fn subject(&self) -> Option<&str> {
+        self.lines_where(|kind| kind == LineKind::Subject).next()
+    }
+
+    /// The subject without the `fixup! `, `squash! ` and `amend! ` prefixes
+    /// git adds to commits it will later fold into another
+    #[must_use]
+    pub fn subject_without_autosquash(&self) -> Option<&str> {
+        self.subject().map(|subject| {
+            let mut rest = subject;
+
+            while let Some(stripped) = AUTOSQUASH_PREFIXES
+                .iter()
+                .find_map(|prefix| rest.strip_prefix(prefix))
+            {
+                rest = stripped;
+            }
+
+            rest
+        })
+    }
+
+    /// The line number (starting at 1) the subject is on
+    #[must_use]
+    pub fn subject_line_number(&self) -> Option<usize> {
+        self.kinds
+            .iter()
+            .position(|kind| *kind == LineKind::Subject)
+            .map(|index| index + 1)
+    }
+
+    /// The paragraphs between the subject and the trailers
+    #[must_use]
+    pub fn body(&self) -> Vec<String> {
+        self.contents
+            .lines()
+            .zip(self.kinds.iter())
+            .filter(|(_, kind)| kind.is_content())
+            .skip_while(|(_, kind)| **kind != LineKind::Subject)
+            .skip(1)
+            .fold(vec![], |mut paragraphs: Vec<Vec<&str>>, (line, kind)| {
+                match (kind, paragraphs.last_mut()) {
+                    (LineKind::Body, Some(paragraph)) if !paragraph.is_empty() => {
+                        paragraph.push(line)
+                    },
+                    (LineKind::Body, _) => paragraphs.push(vec![line]),
+                    (_, Some(paragraph)) if !paragraph.is_empty() => paragraphs.push(vec![]),
+                    _ => {},
+                };
+                paragraphs
+            })
+            .into_iter()
+            .filter(|paragraph| !paragraph.is_empty())
+            .map(|paragraph| paragraph.join("\n"))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn trailers(&self) -> &[Trailer] {
+        &self.trailers
+    }
+
+    #[must_use]
+    pub fn get_trailer(&self, key: &str) -> Vec<&Trailer> {
+        self.trailers
+            .iter()
+            .filter(|trailer| trailer.has_key(key))
+            .collect()
+    }
+
+    /// The lines of the body, numbered from 1
+    #[must_use]
+    pub fn body_lines(&self) -> Vec<(usize, &str)> {
+        self.numbered_lines_where(|kind| kind == LineKind::Body)
+    }
+
+    /// The lines of the trailers, including any they continue onto, numbered
+    /// from 1
+    #[must_use]
+    pub fn trailer_lines(&self) -> Vec<(usize, &str)> {
+        self.numbered_lines_where(|kind| kind == LineKind::Trailer)
+    }
+
+    /// Every line that isn't a comment or below the scissors, numbered from 1
+    #[must_use]
+    pub fn content_lines(&self) -> Vec<(usize, &str)> {
+        self.numbered_lines_where(LineKind::is_content)
+    }
+
+    #[must_use]
+    pub fn comments(&self) -> Vec<&str> {
+        self.lines_where(|kind| kind == LineKind::Comment).collect()
+    }
+
+    /// Get a line of the original message, numbered from 1
+    #[must_use]
+    pub fn line(&self, number: usize) -> Option<&str> {
+        number
+            .checked_sub(1)
+            .and_then(|index| self.contents.lines().nth(index))
+    }
+
+    fn numbered_lines_where(&self, predicate: impl Fn(LineKind) -> bool) -> Vec<(usize, &str)> {
+        self.contents
+            .lines()
+            .zip(self.kinds.iter())
+            .enumerate()
+            .filter(|(_, (_, kind))| predicate(**kind))
+            .map(|(index, (line, _))| (index + 1, line))
+            .collect()
+    }
+
+    fn lines_where<'a>(
+        &'a self,
+        predicate: impl Fn(LineKind) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.contents
+            .lines()
+            .zip(self.kinds.iter())
+            .filter(move |(_, kind)| predicate(**kind))
+            .map(|(line, _)| line)
+    }
+}
+
+fn classify_line(line: &str, comment_char: char, cleanup: CleanupMode) -> LineKind {
+    if cleanup.strips_comments() && line.starts_with(comment_char) {
+        LineKind::Comment
+    } else if line.trim().is_empty() {
+        LineKind::Blank
+    } else {
+        LineKind::Body
+    }
+}
+
+/// Find where the separator of a `Key: Value` line is, following the rules
+/// of `git interpret-trailers`
+fn find_separator(line: &str, separators: &str) -> Option<(usize, char)> {
+    let mut whitespace_found = false;
+
+    for (index, character) in line.char_indices() {
+        if separators.contains(character) {
+            return Some((index, character)).filter(|(index, _)| *index > 0);
+        }
+
+        if !whitespace_found && (character.is_ascii_alphanumeric() || character == '-') {
+            continue;
+        }
+
+        if index != 0 && (character == ' ' || character == '\t') {
+            whitespace_found = true;
+            continue;
+        }
+
+        return None;
+    }
+
+    None
+}
+
+fn is_continuation(line: &str) -> bool {
+    line.starts_with(char::is_whitespace)
+}
+
+/// The trailer block is the last paragraph, provided it isn't the subject
+/// paragraph and either consists only of trailers, or has a git generated
+/// trailer and at least 25% trailers
+fn find_trailer_block(
+    lines: &[&str],
+    kinds: &[LineKind],
+    configuration: &ParseConfiguration,
+) -> Option<(usize, usize)> {
+    let end = kinds
+        .iter()
+        .rposition(|kind| *kind == LineKind::Body)
+        .map(|index| index + 1)?;
+    let start = kinds[..end]
+        .iter()
+        .rposition(|kind| *kind == LineKind::Blank || *kind == LineKind::Subject)
+        .map(|index| index + 1)?;
+
+    if kinds[start - 1] == LineKind::Subject {
+        return None;
+    }
+
+    let mut trailer_lines = 0;
+    let mut non_trailer_lines = 0;
+    let mut recognised_prefix = false;
+    let mut in_trailer = false;
+
+    for (line, _) in lines[start..end]
+        .iter()
+        .zip(kinds[start..end].iter())
+        .filter(|(_, kind)| **kind != LineKind::Comment)
+    {
+        if GIT_GENERATED_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+        {
+            trailer_lines += 1;
+            recognised_prefix = true;
+            in_trailer = true;
+        } else if is_continuation(line) {
+            if !in_trailer {
+                non_trailer_lines += 1;
+            }
+        } else if find_separator(line, configuration.trailer_separators()).is_some() {
+            trailer_lines += 1;
+            in_trailer = true;
+        } else {
+            non_trailer_lines += 1;
+            in_trailer = false;
+        }
+    }
+
+    if (trailer_lines > 0 && non_trailer_lines == 0)
+        || (recognised_prefix && trailer_lines * 3 >= non_trailer_lines)
+    {
+        Some((start, end))
+    } else {
+        None
+    }
+}
+
+fn parse_trailers(
+    lines: &[&str],
+    kinds: &[LineKind],
+    start: usize,
+    end: usize,
+    configuration: &ParseConfiguration,
+) -> Vec<Trailer> {
+    let mut trailers: Vec<Trailer> = vec![];
+    let mut in_trailer = false;
+
+    for index in (start..end).filter(|index| kinds[*index] != LineKind::Comment) {
+        let line = lines[index];
+
+        if is_continuation(line) {
+            if let (true, Some(trailer)) = (in_trailer, trailers.last_mut()) {
+                trailer.value = format!("{} {}", trailer.value, line.trim());
+            }
+        } else if let Some((position, separator)) =
+            find_separator(line, configuration.trailer_separators())
+        {
+            trailers.push(Trailer::new(
+                line[..position].trim(),
+                line[position + separator.len_utf8()..].trim(),
+                separator,
+                index + 1,
+            ));
+            in_trailer = true;
+        } else {
+            in_trailer = false;
+        }
+    }
+
+    trailers
+}
+
+impl TryFrom<PathBuf> for CommitMessage {
+    type Error = PbCommitMessageLintsError;
+
+    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
+        CommitMessage::from_path(value, &ParseConfiguration::default())
+    }
+}
+
+impl Display for CommitMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.contents)
+    }
+}
+
+#[cfg(test)]
+mod test_commit_message {
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+
+    use crate::lints::{CleanupMode, CommentChar, CommitMessage, ParseConfiguration, Trailer};
+
+    #[test]
+    fn with_trailers() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+Anything: Some Trailer
+Anything: Some Trailer
+Another: Trailer
+"#
+            .into(),
+        );
+
+        assert_eq!(
+            vec![&Trailer::new("Another", "Trailer", ':', 5)],
+            commit.get_trailer("Another")
+        );
+        assert_eq!(
+            vec![
+                &Trailer::new("Anything", "Some Trailer", ':', 3),
+                &Trailer::new("Anything", "Some Trailer", ':', 4)
+            ],
+            commit.get_trailer("Anything")
+        )
+    }
+
+    #[test]
+    fn regex_matching() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+Anything: Some Trailer
+Anything: Some Trailer
+Another: Trailer
+"#
+            .into(),
+        );
+
+        assert_eq!(
+            true,
+            commit.matches_pattern(&Regex::new("[AB]nything:").unwrap())
+        );
+        assert_eq!(
+            false,
+            commit.matches_pattern(&Regex::new("N[oO]thing:").unwrap())
+        );
+    }
+
+    #[test]
+    fn regex_matching_ignores_comments() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+# Anything: Some Trailer
+"#
+            .into(),
+        );
+
+        assert_eq!(
+            false,
+            commit.matches_pattern(&Regex::new("Anything:").unwrap())
+        );
+    }
+
+    #[test]
+    fn has_subject_body_and_comments() {
+        let commit = CommitMessage::new(
+            r#"
+Some Commit Message
+
+The first paragraph
+is over two lines
+
+The second paragraph
+# A comment
+Co-authored-by: Billie Thompson <billie@example.com>
+# Another comment
+"#
+            .into(),
+        );
+
+        assert_eq!(Some("Some Commit Message"), commit.subject());
+        assert_eq!(Some(2), commit.subject_line_number());
+        assert_eq!(
+            vec![
+                "The first paragraph\nis over two lines".to_string(),
+                "The second paragraph\nCo-authored-by: Billie Thompson <billie@example.com>"
+                    .to_string(),
+            ],
+            commit.body()
+        );
+        assert_eq!(vec!["# A comment", "# Another comment"], commit.comments());
+        assert_eq!(Vec::<Trailer>::new(), commit.trailers());
+    }
+
+    #[test]
+    fn lines_are_numbered_by_where_they_are_in_the_message() {
+        let commit = CommitMessage::new(
+            "Subject\n\nFirst line\n# A comment\nSecond line\n\nSigned-off-by: Billie\n".into(),
+        );
+
+        assert_eq!(
+            vec![(3, "First line"), (5, "Second line")],
+            commit.body_lines()
+        );
+        assert_eq!(vec![(7, "Signed-off-by: Billie")], commit.trailer_lines());
+        assert_eq!(
+            vec![
+                (1, "Subject"),
+                (2, ""),
+                (3, "First line"),
+                (5, "Second line"),
+                (6, ""),
+                (7, "Signed-off-by: Billie")
+            ],
+            commit.content_lines()
+        );
+    }
+
+    #[test]
+    fn trailers_mentioned_in_the_body_are_not_trailers() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+Signed-off-by: Billie Thompson <billie@example.com>
+is what you should add to these messages
+
+Co-authored-by: Someone Else <someone@example.com>
+"#
+            .into(),
+        );
+
+        assert_eq!(Vec::<&Trailer>::new(), commit.get_trailer("Signed-off-by"));
+        assert_eq!(
+            vec![&Trailer::new(
+                "Co-authored-by",
+                "Someone Else <someone@example.com>",
+                ':',
+                6
+            )],
+            commit.get_trailer("Co-authored-by")
+        );
+    }
+
+    #[test]
+    fn the_subject_is_never_a_trailer() {
+        let commit = CommitMessage::new("Fix: Some Commit Message\n".into());
+
+        assert_eq!(Some("Fix: Some Commit Message"), commit.subject());
+        assert_eq!(Vec::<Trailer>::new(), commit.trailers());
+    }
+
+    #[test]
+    fn autosquash_prefixes_can_be_left_off_the_subject() {
+        let commit = CommitMessage::new("fixup! squash! Some Commit Message\n".into());
+
+        assert_eq!(
+            Some("Some Commit Message"),
+            commit.subject_without_autosquash()
+        );
+        assert_eq!(
+            Some("Some Commit Message"),
+            CommitMessage::new("Some Commit Message\n".into()).subject_without_autosquash()
+        );
+    }
+
+    #[test]
+    fn trailer_keys_are_case_insensitive() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+signed-off-by: Billie Thompson <billie@example.com>
+"#
+            .into(),
+        );
+
+        assert_eq!(
+            vec![&Trailer::new(
+                "signed-off-by",
+                "Billie Thompson <billie@example.com>",
+                ':',
+                3
+            )],
+            commit.get_trailer("Signed-Off-By")
+        );
+    }
+
+    #[test]
+    fn trailers_can_have_continuation_lines() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+Reviewed-by: Billie Thompson
+  <billie@example.com>
+Signed-off-by: Billie Thompson <billie@example.com>
+"#
+            .into(),
+        );
+
+        assert_eq!(
+            vec![
+                &Trailer::new(
+                    "Reviewed-by",
+                    "Billie Thompson <billie@example.com>",
+                    ':',
+                    3
+                ),
+                &Trailer::new(
+                    "Signed-off-by",
+                    "Billie Thompson <billie@example.com>",
+                    ':',
+                    5
+                ),
+            ],
+            commit.trailers().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn a_git_generated_trailer_allows_some_non_trailer_lines() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+This is not a trailer
+Signed-off-by: Billie Thompson <billie@example.com>
+"#
+            .into(),
+        );
+
+        assert_eq!(
+            vec![&Trailer::new(
+                "Signed-off-by",
+                "Billie Thompson <billie@example.com>",
+                ':',
+                4
+            )],
+            commit.get_trailer("Signed-off-by")
+        );
+        assert_eq!(Vec::<String>::new(), commit.body());
+    }
+
+    #[test]
+    fn without_a_git_generated_trailer_all_lines_must_be_trailers() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+This is not a trailer
+Co-authored-by: Billie Thompson <billie@example.com>
+"#
+            .into(),
+        );
+
+        assert_eq!(Vec::<Trailer>::new(), commit.trailers());
+    }
+
+    #[test]
+    fn trailers_can_use_other_separators() {
+        let commit = CommitMessage::parse(
+            r#"Some Commit Message
+
+Fixes #123
+Co-authored-by: Billie Thompson <billie@example.com>
+"#
+            .into(),
+            &ParseConfiguration::new(CommentChar::Char('#'), ":#", CleanupMode::Default),
+        );
+
+        assert_eq!(
+            vec![
+                &Trailer::new("Fixes", "123", '#', 3),
+                &Trailer::new(
+                    "Co-authored-by",
+                    "Billie Thompson <billie@example.com>",
+                    ':',
+                    4
+                ),
+            ],
+            commit.trailers().iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn lines_are_numbered_from_one() {
+        let commit = CommitMessage::new("Subject\n\nBody\n".into());
+
+        assert_eq!(Some("Subject"), commit.line(1));
+        assert_eq!(Some("Body"), commit.line(3));
+        assert_eq!(None, commit.line(0));
+        assert_eq!(None, commit.line(4));
+    }
+
+    #[test]
+    fn everything_below_the_scissors_is_ignored() {
+        let commit = CommitMessage::new(
+            r#"Some Commit Message
+
+# Please enter the commit message for your changes.
+# ------------------------ >8 ------------------------
+# Do not modify or remove the line above.
+diff --git a/README.md b/README.md
++JRA-123
+"#
+            .into(),
+        );
+
+        assert_eq!("Some Commit Message\n", commit.text());
+        assert_eq!(
+            false,
+            commit.matches_pattern(&Regex::new("JRA-123").unwrap())
+        );
+        assert_eq!(
+            vec!["# Please enter the commit message for your changes."],
+            commit.comments()
+        );
+    }
+
+    #[test]
+    fn the_comment_char_can_be_changed() {
+        let commit = CommitMessage::parse(
+            r#"Some Commit Message
+
+#123 is fixed by this
+; A comment
+; ------------------------ >8 ------------------------
++JRA-123
+"#
+            .into(),
+            &ParseConfiguration::new(CommentChar::Char(';'), ":", CleanupMode::Default),
+        );
+
+        assert_eq!(vec!["#123 is fixed by this".to_string()], commit.body());
+        assert_eq!(vec!["; A comment"], commit.comments());
+        assert_eq!(
+            false,
+            commit.matches_pattern(&Regex::new("JRA-123").unwrap())
+        );
+    }
+
+    #[test]
+    fn the_comment_char_can_be_detected() {
+        let commit = CommitMessage::parse(
+            r#"Some Commit Message
+
+#123 is fixed by this
+;
+; A comment
+"#
+            .into(),
+            &ParseConfiguration::new(CommentChar::Auto, ":", CleanupMode::Default),
+        );
+
+        assert_eq!(vec!["#123 is fixed by this".to_string()], commit.body());
+        assert_eq!(vec![";", "; A comment"], commit.comments());
+    }
+
+    #[test]
+    fn comments_are_kept_when_cleaning_up_whitespace() {
+        let commit = CommitMessage::parse(
+            r#"Some Commit Message
+
+# Not a comment
+# ------------------------ >8 ------------------------
++JRA-123
+"#
+            .into(),
+            &ParseConfiguration::new(CommentChar::Char('#'), ":", CleanupMode::Whitespace),
+        );
+
+        assert_eq!(vec!["# Not a comment".to_string()], commit.body());
+        assert_eq!(Vec::<&str>::new(), commit.comments());
+        assert_eq!(
+            false,
+            commit.matches_pattern(&Regex::new("JRA-123").unwrap())
+        );
+    }
+
+    #[test]
+    fn nothing_above_the_scissors_is_removed_when_verbatim() {
+        let commit = CommitMessage::parse(
+            r#"Some Commit Message
+
+# Not a comment
+"#
+            .into(),
+            &ParseConfiguration::new(CommentChar::Char('#'), ":", CleanupMode::Verbatim),
+        );
+
+        assert_eq!(vec!["# Not a comment".to_string()], commit.body());
+        assert_eq!(Vec::<&str>::new(), commit.comments());
+    }
+
+    fn parse_verbose_message(cleanup: CleanupMode) -> CommitMessage {
+        CommitMessage::parse(
+            r#"Some Commit Message
+# ------------------------ >8 ------------------------
+# Do not modify or remove the line above.
+diff --git a/README.md b/README.md
++Co-authored-by: Someone Else <someone@example.com>
+"#
+            .into(),
+            &ParseConfiguration::new(CommentChar::Char('#'), ":", cleanup),
+        )
+    }
+
+    fn assert_the_diff_is_ignored(commit: &CommitMessage) {
+        assert_eq!("Some Commit Message", commit.text());
+        assert_eq!(Vec::<&str>::new(), commit.comments());
+        assert_eq!(Vec::<Trailer>::new(), commit.trailers());
+        assert_eq!(
+            false,
+            commit.matches_pattern(&Regex::new("README").unwrap())
+        );
+    }
+
+    #[test]
+    fn the_verbose_diff_is_ignored_when_stripping() {
+        assert_the_diff_is_ignored(&parse_verbose_message(CleanupMode::Strip));
+    }
+
+    #[test]
+    fn the_verbose_diff_is_ignored_when_cleaning_up_whitespace() {
+        assert_the_diff_is_ignored(&parse_verbose_message(CleanupMode::Whitespace));
+    }
+
+    #[test]
+    fn the_verbose_diff_is_ignored_when_verbatim() {
+        assert_the_diff_is_ignored(&parse_verbose_message(CleanupMode::Verbatim));
+    }
+
+    #[test]
+    fn the_verbose_diff_is_ignored_when_cutting_at_the_scissors() {
+        assert_the_diff_is_ignored(&parse_verbose_message(CleanupMode::Scissors));
+    }
+
+    #[test]
+    fn the_verbose_diff_is_ignored_by_default() {
+        assert_the_diff_is_ignored(&parse_verbose_message(CleanupMode::Default));
+    }
+}
+
+#[cfg(test)]
+mod tests_get_parse_configuration {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{get_parse_configuration, CleanupMode, CommentChar, ParseConfiguration},
+    };
+
+    #[test]
+    fn defaults() {
+        let mut strings = HashMap::new();
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_parse_configuration(&config);
+        let expected = Ok(ParseConfiguration::default());
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn reads_the_git_settings() {
+        let mut strings = HashMap::new();
+        strings.insert("core.commentChar".into(), ";".into());
+        strings.insert("commit.cleanup".into(), "scissors".into());
+        strings.insert("trailer.separators".into(), ":#".into());
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_parse_configuration(&config);
+        let expected = Ok(ParseConfiguration::new(
+            CommentChar::Char(';'),
+            ":#",
+            CleanupMode::Scissors,
+        ));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn comment_char_can_be_auto() {
+        let mut strings = HashMap::new();
+        strings.insert("core.commentChar".into(), "auto".into());
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_parse_configuration(&config).map(|config| config.comment_char());
+        let expected = Ok(CommentChar::Auto);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn invalid_cleanup_mode() {
+        let mut strings = HashMap::new();
+        strings.insert("commit.cleanup".into(), "banana".into());
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_parse_configuration(&config);
+        let expected = Err(PbCommitMessageLintsError::InvalidConfigurationValue(
+            "commit.cleanup".into(),
+            "banana".into(),
+        ));
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,400 @@
+use regex::Regex;
+
+use crate::lints::{
+    CommitMessage,
+    Documentation,
+    Label,
+    LabelKind,
+    LintCode,
+    LintOption,
+    LintProblem,
+    OptionKind,
+    Span,
+};
+
+const NOT_CONVENTIONAL_HELP: &str = r#"
+Your commit message isn't a Conventional Commit
+
+You can fix this by starting the subject with the type of change, like `feat: add a search box` or `fix(parser): handle empty input`
+"#;
+
+const NOT_CONVENTIONAL_SUGGESTION: &str = "expected `type(scope): description` here";
+
+const BREAKING_MARKER_HELP: &str = r#"
+Your commit has a `BREAKING CHANGE:` footer, but isn't marked as a breaking change
+
+You can fix this by adding a `!` before the `:` in the subject, like `feat!: drop support for Node 6`
+"#;
+
+pub(crate) const OPTION_TYPES: &str = "types";
+pub(crate) const OPTION_SCOPES: &str = "scopes";
+const DEFAULT_TYPES: &str = "build chore ci docs feat fix perf refactor revert style test";
+const REGEX_HEADER: &str =
+    r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()\s]+)\))?(?P<breaking>!)?: (?P<description>\S.*)$";
+const REGEX_TYPE: &str = r"^[A-Za-z]+$";
+const REGEX_SCOPE: &str = r"^[^()\s]+$";
+const REGEX_BREAKING_FOOTER: &str = r"^BREAKING[ -]CHANGE: ";
+
+/// The `type(scope)!: description` subject of a Conventional Commit
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ConventionalHeader {
+    kind: String,
+    scope: Option<String>,
+    breaking: bool,
+    description: String,
+}
+
+impl ConventionalHeader {
+    /// Read the header from a subject line, if it is one
+    ///
+    /// # Panics
+    ///
+    /// If the pattern for headers isn't a valid regular expression
+    #[must_use]
+    pub fn parse(subject: &str) -> Option<ConventionalHeader> {
+        Regex::new(REGEX_HEADER)
+            .unwrap()
+            .captures(subject)
+            .map(|captures| ConventionalHeader {
+                kind: captures["type"].into(),
+                scope: captures.name("scope").map(|scope| scope.as_str().into()),
+                breaking: captures.name("breaking").is_some(),
+                description: captures["description"].into(),
+            })
+    }
+
+    /// Read the header from the subject of a commit message, leaving off any
+    /// `fixup! ` or `squash! ` prefixes
+    #[must_use]
+    pub fn from_commit_message(commit_message: &CommitMessage) -> Option<ConventionalHeader> {
+        commit_message
+            .subject_without_autosquash()
+            .and_then(ConventionalHeader::parse)
+    }
+
+    /// The type of change, like `feat` or `fix`
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    #[must_use]
+    pub fn scope(&self) -> Option<&str> {
+        self.scope.as_deref()
+    }
+
+    /// Whether the header has a `!` marking it as a breaking change
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.breaking
+    }
+
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+pub(crate) fn options() -> Vec<LintOption> {
+    vec![
+        LintOption::new(
+            OPTION_TYPES,
+            "The types of change a commit can have, separated by spaces",
+            OptionKind::List {
+                pattern: REGEX_TYPE,
+                expected: "a type of change, like `feat`",
+            },
+            DEFAULT_TYPES,
+        ),
+        LintOption::new(
+            OPTION_SCOPES,
+            "The scopes a commit can have, separated by spaces. Any scope will do if this isn't \
+             set, and the scope can always be left out",
+            OptionKind::List {
+                pattern: REGEX_SCOPE,
+                expected: "a scope, like `parser`",
+            },
+            "",
+        ),
+    ]
+}
+
+pub(crate) fn documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the subject follows Conventional Commits, as a type of change, an optional \
+         scope in brackets, and a description after a colon, like `fix(parser): handle empty \
+         input`. The type, and the scope if there is one, have to be in the allowed lists. A \
+         `BREAKING CHANGE:` footer has to go with a `!` before the colon marking the subject as a \
+         breaking change, though the `!` is enough on its own.",
+        "Conventional Commits can be read by tools as well as people, to work out the next \
+         version number and write the changelog. A commit that doesn't follow the format is left \
+         out of both, and a breaking change that isn't marked as one can be released without a \
+         major version.",
+    )
+    .with_passing_example("feat: add a search box\n")
+    .with_passing_example("fix(parser): handle empty input\n\nThis used to panic\n")
+    .with_passing_example(
+        "feat(api)!: remove the v1 endpoints\n\nBREAKING CHANGE: use the v2 endpoints instead\n",
+    )
+    .with_passing_example("feat!: remove the v1 endpoints\n")
+    .with_failing_example("Add a search box\n")
+    .with_failing_example("feature: add a search box\n")
+    .with_failing_example("feat: remove the v1 endpoints\n\nBREAKING CHANGE: use v2 instead\n")
+}
+
+pub(crate) fn lint_conventional_commits(
+    commit_message: &CommitMessage,
+    types: &[String],
+    scopes: &[String],
+) -> Option<LintProblem> {
+    let line = commit_message.subject_line_number()?;
+    let subject = commit_message.subject()?;
+    let unprefixed = commit_message.subject_without_autosquash()?;
+    let start = subject[..subject.len() - unprefixed.len()].chars().count() + 1;
+
+    let header = match ConventionalHeader::parse(unprefixed) {
+        Some(header) => header,
+        None => {
+            return Some(
+                LintProblem::new(NOT_CONVENTIONAL_HELP.into(), LintCode::ConventionalCommits)
+                    .with_label(Label::new(
+                        Span::new(line, start, start + unprefixed.chars().count()),
+                        LabelKind::Problem,
+                        NOT_CONVENTIONAL_SUGGESTION,
+                    )),
+            )
+        },
+    };
+
+    if !is_allowed(header.kind(), types) {
+        let end = start + header.kind().chars().count();
+
+        return Some(
+            LintProblem::new(
+                not_allowed_help("type", header.kind(), types),
+                LintCode::ConventionalCommits,
+            )
+            .with_label(Label::new(
+                Span::new(line, start, end),
+                LabelKind::Problem,
+                "not an allowed type",
+            )),
+        );
+    }
+
+    if let Some(scope) = header.scope() {
+        if !scopes.is_empty() && !is_allowed(scope, scopes) {
+            let scope_start = start + header.kind().chars().count() + 1;
+
+            return Some(
+                LintProblem::new(
+                    not_allowed_help("scope", scope, scopes),
+                    LintCode::ConventionalCommits,
+                )
+                .with_label(Label::new(
+                    Span::new(line, scope_start, scope_start + scope.chars().count()),
+                    LabelKind::Problem,
+                    "not an allowed scope",
+                )),
+            );
+        }
+    }
+
+    // The spec lets the `!` stand in for the footer, but not the other way
+    if header.is_breaking() {
+        return None;
+    }
+
+    let footer = Regex::new(REGEX_BREAKING_FOOTER).unwrap();
+
+    commit_message
+        .body_lines()
+        .into_iter()
+        .chain(commit_message.trailer_lines())
+        .find(|(_, text)| footer.is_match(text))
+        .map(|(footer_line, _)| {
+            LintProblem::new(BREAKING_MARKER_HELP.into(), LintCode::ConventionalCommits).with_label(
+                Label::new(
+                    Span::whole_line(commit_message, footer_line),
+                    LabelKind::Problem,
+                    "breaking change described here",
+                ),
+            )
+        })
+}
+
+/// Types and scopes aren't case sensitive
+fn is_allowed(value: &str, allowed: &[String]) -> bool {
+    allowed
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(value))
+}
+
+fn not_allowed_help(kind: &str, value: &str, allowed: &[String]) -> String {
+    format!(
+        "\nThe {} `{}` isn't one of the allowed {}s\n\nYou can fix this by using one of {}\n",
+        kind,
+        value,
+        kind,
+        allowed
+            .iter()
+            .map(|allowed| format!("`{}`", allowed))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests_conventional_header {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{CommitMessage, ConventionalHeader};
+
+    #[test]
+    fn the_type_and_description_are_needed() {
+        let header = ConventionalHeader::parse("feat: add a search box").unwrap();
+
+        assert_eq!("feat", header.kind());
+        assert_eq!(None, header.scope());
+        assert!(!header.is_breaking());
+        assert_eq!("add a search box", header.description());
+    }
+
+    #[test]
+    fn the_scope_and_breaking_marker_are_read() {
+        let header = ConventionalHeader::parse("feat(api)!: remove the v1 endpoints").unwrap();
+
+        assert_eq!("feat", header.kind());
+        assert_eq!(Some("api"), header.scope());
+        assert!(header.is_breaking());
+        assert_eq!("remove the v1 endpoints", header.description());
+    }
+
+    #[test]
+    fn other_subjects_are_not_headers() {
+        assert_eq!(None, ConventionalHeader::parse("Add a search box"));
+        assert_eq!(None, ConventionalHeader::parse("feat:add a search box"));
+        assert_eq!(None, ConventionalHeader::parse("feat(): add a search box"));
+        assert_eq!(None, ConventionalHeader::parse("feat: "));
+    }
+
+    #[test]
+    fn autosquash_prefixes_are_left_off() {
+        assert_eq!(
+            ConventionalHeader::parse("fix: handle empty input"),
+            ConventionalHeader::from_commit_message(&CommitMessage::new(
+                "fixup! fix: handle empty input\n".into()
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_lint_conventional_commits {
+    #![allow(clippy::wildcard_imports)]
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn default_types() -> Vec<String> {
+        DEFAULT_TYPES.split(' ').map(String::from).collect()
+    }
+
+    fn lint(message: &str, scopes: &[String]) -> Option<LintProblem> {
+        lint_conventional_commits(
+            &CommitMessage::new(message.into()),
+            &default_types(),
+            scopes,
+        )
+    }
+
+    #[test]
+    fn conventional_commits_pass() {
+        assert_eq!(None, lint("feat: add a search box\n", &[]));
+        assert_eq!(None, lint("Fix(Parser): handle empty input\n", &[]));
+        assert_eq!(None, lint("fixup! fix: handle empty input\n", &[]));
+    }
+
+    #[test]
+    fn other_subjects_fail() {
+        assert_eq!(
+            Some(
+                LintProblem::new(NOT_CONVENTIONAL_HELP.into(), LintCode::ConventionalCommits)
+                    .with_label(Label::new(
+                        Span::new(1, 8, 24),
+                        LabelKind::Problem,
+                        NOT_CONVENTIONAL_SUGGESTION
+                    ))
+            ),
+            lint("fixup! Add a search box\n", &[])
+        );
+    }
+
+    #[test]
+    fn the_type_has_to_be_allowed() {
+        assert_eq!(
+            Some(
+                LintProblem::new(
+                    "\nThe type `feature` isn't one of the allowed types\n\nYou can fix this by \
+                     using one of `feat`, `fix`\n"
+                        .into(),
+                    LintCode::ConventionalCommits
+                )
+                .with_label(Label::new(
+                    Span::new(1, 1, 8),
+                    LabelKind::Problem,
+                    "not an allowed type"
+                ))
+            ),
+            lint_conventional_commits(
+                &CommitMessage::new("feature: add a search box\n".into()),
+                &["feat".into(), "fix".into()],
+                &[]
+            )
+        );
+    }
+
+    #[test]
+    fn the_scope_has_to_be_allowed_when_there_is_a_list() {
+        let scopes = vec!["parser".to_string()];
+
+        assert_eq!(None, lint("fix(parser): handle empty input\n", &scopes));
+        assert_eq!(None, lint("fix: handle empty input\n", &scopes));
+        assert_eq!(
+            Some(Span::new(1, 5, 10)),
+            lint("fix(lexer): handle empty input\n", &scopes)
+                .map(|problem| problem.labels()[0].span())
+        );
+    }
+
+    #[test]
+    fn breaking_changes_do_not_need_a_footer() {
+        assert_eq!(None, lint("feat!: remove the v1 endpoints\n", &[]));
+    }
+
+    #[test]
+    fn breaking_change_footers_need_the_marker() {
+        assert_eq!(
+            Some(
+                LintProblem::new(BREAKING_MARKER_HELP.into(), LintCode::ConventionalCommits)
+                    .with_label(Label::new(
+                        Span::new(3, 1, 32),
+                        LabelKind::Problem,
+                        "breaking change described here"
+                    ))
+            ),
+            lint(
+                "feat: remove the v1 endpoints\n\nBREAKING-CHANGE: use v2 instead\n",
+                &[]
+            )
+        );
+        assert_eq!(
+            None,
+            lint(
+                "feat!: remove the v1 endpoints\n\nBREAKING-CHANGE: use v2 instead\n",
+                &[]
+            )
+        );
+    }
+}
@@ -0,0 +1,539 @@
+use std::convert::TryFrom;
+
+use regex::Regex;
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::vcs::Vcs,
+    lints::{CommitMessage, Label, LabelKind, LintCode, LintProblem, Lints, Span},
+};
+
+const CONFIG_CUSTOM_LINT_PREFIX: &str = "pb.customlint.";
+const CONFIG_PATTERN: &str = "pattern";
+const CONFIG_MODE: &str = "mode";
+const CONFIG_HELP: &str = "help";
+const CONFIG_TARGET: &str = "target";
+const REGEX_LINT_NAME: &str = r"^[A-Za-z0-9-]+$";
+const RESERVED_LINT_NAME: &str = "profile";
+
+/// Whether a custom lint needs its pattern to be there, or not
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CustomLintMode {
+    Require,
+    Forbid,
+}
+
+impl CustomLintMode {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            CustomLintMode::Require => "require",
+            CustomLintMode::Forbid => "forbid",
+        }
+    }
+}
+
+impl TryFrom<&str> for CustomLintMode {
+    type Error = String;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        [CustomLintMode::Require, CustomLintMode::Forbid]
+            .iter()
+            .copied()
+            .find(|mode| mode.name() == from)
+            .ok_or_else(|| {
+                format!(
+                    "The mode \"{}\" isn't valid, it can be require or forbid",
+                    from
+                )
+            })
+    }
+}
+
+/// The part of the commit message a custom lint checks
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CustomLintTarget {
+    Subject,
+    Body,
+    Trailers,
+    All,
+}
+
+impl CustomLintTarget {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            CustomLintTarget::Subject => "subject",
+            CustomLintTarget::Body => "body",
+            CustomLintTarget::Trailers => "trailers",
+            CustomLintTarget::All => "all",
+        }
+    }
+
+    /// The lines of the commit message in this part, numbered from 1
+    fn lines(self, commit_message: &CommitMessage) -> Vec<(usize, &str)> {
+        match self {
+            CustomLintTarget::Subject => commit_message
+                .content_lines()
+                .into_iter()
+                .filter(|(number, _)| Some(*number) == commit_message.subject_line_number())
+                .collect(),
+            CustomLintTarget::Body => commit_message.body_lines(),
+            CustomLintTarget::Trailers => commit_message.trailer_lines(),
+            CustomLintTarget::All => commit_message.content_lines(),
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            CustomLintTarget::All => "message",
+            other => other.name(),
+        }
+    }
+}
+
+impl TryFrom<&str> for CustomLintTarget {
+    type Error = String;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        [
+            CustomLintTarget::Subject,
+            CustomLintTarget::Body,
+            CustomLintTarget::Trailers,
+            CustomLintTarget::All,
+        ]
+        .iter()
+        .copied()
+        .find(|target| target.name() == from)
+        .ok_or_else(|| {
+            format!(
+                "The target \"{}\" isn't valid, it can be subject, body, trailers or all",
+                from
+            )
+        })
+    }
+}
+
+/// A lint defined in config, under `pb.customlint.<name>`, that checks the
+/// commit message against a regular expression
+#[derive(Debug, Clone)]
+pub struct CustomLint {
+    name: String,
+    pattern: Regex,
+    mode: CustomLintMode,
+    target: CustomLintTarget,
+    help: Option<String>,
+}
+
+impl CustomLint {
+    /// By default the pattern is required somewhere in the commit message
+    ///
+    /// # Errors
+    ///
+    /// If the name can't be used for a lint, or the pattern isn't a valid
+    /// regular expression
+    pub fn new(name: &str, pattern: &str) -> Result<CustomLint, PbCommitMessageLintsError> {
+        let invalid =
+            |reason: String| PbCommitMessageLintsError::CustomLintError(name.into(), reason);
+
+        check_lint_name(name).map_err(invalid)?;
+        let pattern = Regex::new(pattern).map_err(|error| invalid(format!("{}", error)))?;
+
+        Ok(CustomLint {
+            name: name.into(),
+            pattern,
+            mode: CustomLintMode::Require,
+            target: CustomLintTarget::All,
+            help: None,
+        })
+    }
+
+    #[must_use]
+    pub fn with_mode(mut self, mode: CustomLintMode) -> CustomLint {
+        self.mode = mode;
+        self
+    }
+
+    #[must_use]
+    pub fn with_target(mut self, target: CustomLintTarget) -> CustomLint {
+        self.target = target;
+        self
+    }
+
+    /// What to tell someone whose commit message breaks the rule
+    #[must_use]
+    pub fn with_help(mut self, help: &str) -> CustomLint {
+        self.help = Some(help.into());
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        self.pattern.as_str()
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> CustomLintMode {
+        self.mode
+    }
+
+    #[must_use]
+    pub fn target(&self) -> CustomLintTarget {
+        self.target
+    }
+
+    /// The help that was configured, or a description of the rule if there
+    /// isn't any
+    #[must_use]
+    pub fn help(&self) -> String {
+        self.help.clone().unwrap_or_else(|| match self.mode {
+            CustomLintMode::Require => format!(
+                "Your commit {} needs to match `{}`",
+                self.target.description(),
+                self.pattern()
+            ),
+            CustomLintMode::Forbid => format!(
+                "Your commit {} can't match `{}`",
+                self.target.description(),
+                self.pattern()
+            ),
+        })
+    }
+
+    #[must_use]
+    pub fn config_key(&self) -> String {
+        format!("pb.lint.{}", self.name)
+    }
+
+    /// Forbidden text is pointed at wherever it's found on a single line
+    #[must_use]
+    pub fn lint(&self, commit_message: &CommitMessage) -> Option<LintProblem> {
+        let lines = self.target.lines(commit_message);
+        let text = lines
+            .iter()
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let problem =
+            LintProblem::new(self.help(), LintCode::CustomLint).with_lint_name(&self.name);
+
+        match (self.mode, self.pattern.is_match(&text)) {
+            (CustomLintMode::Require, false) => Some(
+                match self.target {
+                    CustomLintTarget::Subject | CustomLintTarget::All => {
+                        Label::subject_suggestion(commit_message, &self.suggestion())
+                    },
+                    CustomLintTarget::Body | CustomLintTarget::Trailers => None,
+                }
+                .into_iter()
+                .fold(problem, LintProblem::with_label),
+            ),
+            (CustomLintMode::Forbid, true) => Some(
+                lines
+                    .iter()
+                    .filter_map(|(number, line)| {
+                        self.pattern.find(line).map(|found| {
+                            Label::new(
+                                Span::new(
+                                    *number,
+                                    line[..found.start()].chars().count() + 1,
+                                    line[..found.end()].chars().count() + 1,
+                                ),
+                                LabelKind::Problem,
+                                "not allowed here",
+                            )
+                        })
+                    })
+                    .fold(problem, LintProblem::with_label),
+            ),
+            _ => None,
+        }
+    }
+
+    fn suggestion(&self) -> String {
+        format!(
+            "consider adding something that matches `{}`",
+            self.pattern()
+        )
+    }
+}
+
+/// Lints defined in config are managed by their name, so it has to be usable
+/// in config keys and can't be mistaken for a built-in lint
+pub(crate) fn check_lint_name(name: &str) -> Result<(), String> {
+    if !Regex::new(REGEX_LINT_NAME).unwrap().is_match(name) {
+        return Err("The name can only have letters, numbers and dashes in it".into());
+    }
+
+    if Lints::try_from(name).is_ok() {
+        return Err("There's already a built-in lint with this name".into());
+    }
+
+    if name == RESERVED_LINT_NAME {
+        return Err(format!(
+            "\"{}\" is kept for choosing a profile",
+            RESERVED_LINT_NAME
+        ));
+    }
+
+    Ok(())
+}
+
+impl PartialEq for CustomLint {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.pattern() == other.pattern()
+            && self.mode == other.mode
+            && self.target == other.target
+            && self.help == other.help
+    }
+}
+
+impl Eq for CustomLint {}
+
+/// Read every custom lint in the config, in order of their names
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or one of the
+/// lints isn't valid, which names the lint
+pub fn get_custom_lints(config: &dyn Vcs) -> Result<Vec<CustomLint>, PbCommitMessageLintsError> {
+    configured_names(config, CONFIG_CUSTOM_LINT_PREFIX)?
+        .iter()
+        .map(|name| get_custom_lint(config, name))
+        .collect()
+}
+
+/// The sorted names in keys like `<prefix><name>.<setting>`
+pub(crate) fn configured_names(
+    config: &dyn Vcs,
+    prefix: &str,
+) -> Result<Vec<String>, PbCommitMessageLintsError> {
+    let mut names = config
+        .get_keys(prefix)?
+        .iter()
+        .filter_map(|key| {
+            key.strip_prefix(prefix)
+                .and_then(|rest| rest.rfind('.').map(|index| rest[..index].to_string()))
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    Ok(names)
+}
+
+fn get_custom_lint(config: &dyn Vcs, name: &str) -> Result<CustomLint, PbCommitMessageLintsError> {
+    let key = |setting: &str| format!("{}{}.{}", CONFIG_CUSTOM_LINT_PREFIX, name, setting);
+    let invalid = |reason: String| PbCommitMessageLintsError::CustomLintError(name.into(), reason);
+
+    let pattern = config.get_str(&key(CONFIG_PATTERN))?.ok_or_else(|| {
+        invalid(format!(
+            "There's no pattern, set one in {}",
+            key(CONFIG_PATTERN)
+        ))
+    })?;
+    let mut lint = CustomLint::new(name, pattern)?;
+
+    if let Some(mode) = config.get_str(&key(CONFIG_MODE))? {
+        lint = lint.with_mode(CustomLintMode::try_from(mode).map_err(invalid)?);
+    }
+
+    if let Some(target) = config.get_str(&key(CONFIG_TARGET))? {
+        lint = lint.with_target(CustomLintTarget::try_from(target).map_err(invalid)?);
+    }
+
+    if let Some(help) = config.get_str(&key(CONFIG_HELP))? {
+        lint = lint.with_help(help);
+    }
+
+    Ok(lint)
+}
+
+#[cfg(test)]
+mod tests_custom_lint {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{
+        CommitMessage,
+        CustomLint,
+        CustomLintMode,
+        CustomLintTarget,
+        Label,
+        LabelKind,
+        LintCode,
+        LintProblem,
+        Span,
+    };
+
+    #[test]
+    fn required_patterns_must_be_somewhere_in_the_message() {
+        let lint = CustomLint::new("service-name", r"\[(api|web)\]").unwrap();
+
+        assert_eq!(
+            None,
+            lint.lint(&CommitMessage::new(
+                "An example commit\n\nChanges the [api]\n".into()
+            ))
+        );
+        assert_eq!(
+            Some(
+                LintProblem::new(
+                    r"Your commit message needs to match `\[(api|web)\]`".into(),
+                    LintCode::CustomLint
+                )
+                .with_lint_name("service-name")
+                .with_label(Label::new(
+                    Span::new(1, 1, 18),
+                    LabelKind::Suggestion,
+                    r"consider adding something that matches `\[(api|web)\]`"
+                ))
+            ),
+            lint.lint(&CommitMessage::new("An example commit\n".into()))
+        );
+    }
+
+    #[test]
+    fn forbidden_patterns_are_pointed_at() {
+        let lint = CustomLint::new("no-hotfix", "(?i)hotfix")
+            .unwrap()
+            .with_mode(CustomLintMode::Forbid)
+            .with_target(CustomLintTarget::Subject)
+            .with_help("Hotfixes go through the release branch");
+
+        assert_eq!(
+            Some(
+                LintProblem::new(
+                    "Hotfixes go through the release branch".into(),
+                    LintCode::CustomLint
+                )
+                .with_lint_name("no-hotfix")
+                .with_label(Label::new(
+                    Span::new(1, 7, 13),
+                    LabelKind::Problem,
+                    "not allowed here"
+                ))
+            ),
+            lint.lint(&CommitMessage::new("Quick HotFix\n".into()))
+        );
+        assert_eq!(
+            None,
+            lint.lint(&CommitMessage::new(
+                "An example commit\n\nNot a hotfix\n".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn trailers_can_be_checked_on_their_own() {
+        let lint = CustomLint::new("reviewed", "^Reviewed-by: ")
+            .unwrap()
+            .with_target(CustomLintTarget::Trailers);
+
+        assert!(lint
+            .lint(&CommitMessage::new(
+                "An example commit\n\nReviewed-by: in the body\n\nThanks everyone\n".into()
+            ))
+            .is_some());
+        assert_eq!(
+            None,
+            lint.lint(&CommitMessage::new(
+                "An example commit\n\nSome body\n\nReviewed-by: Billie\n".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn built_in_lint_names_can_not_be_used() {
+        assert!(CustomLint::new("duplicated-trailers", "x").is_err());
+    }
+
+    #[test]
+    fn the_profile_setting_can_not_be_used_as_a_name() {
+        assert!(CustomLint::new("profile", "x").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_get_custom_lints {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{get_custom_lints, CustomLint, CustomLintMode, CustomLintTarget},
+    };
+
+    #[test]
+    fn lints_are_read_from_config() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.customlint.no-hotfix.pattern".into(), "hotfix".into());
+        strings.insert("pb.customlint.no-hotfix.mode".into(), "forbid".into());
+        strings.insert("pb.customlint.no-hotfix.target".into(), "subject".into());
+        strings.insert("pb.customlint.no-hotfix.help".into(), "No hotfixes".into());
+        strings.insert("pb.customlint.has-service.pattern".into(), "api".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(vec![
+                CustomLint::new("has-service", "api").unwrap(),
+                CustomLint::new("no-hotfix", "hotfix")
+                    .unwrap()
+                    .with_mode(CustomLintMode::Forbid)
+                    .with_target(CustomLintTarget::Subject)
+                    .with_help("No hotfixes")
+            ]),
+            get_custom_lints(&config)
+        );
+    }
+
+    #[test]
+    fn invalid_patterns_name_the_lint() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.customlint.broken.pattern".into(), "(unclosed".into());
+        let config = InMemory::new(&mut strings);
+
+        assert!(matches!(
+            get_custom_lints(&config),
+            Err(PbCommitMessageLintsError::CustomLintError(name, _)) if name == "broken"
+        ));
+    }
+
+    #[test]
+    fn a_pattern_is_needed() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.customlint.broken.mode".into(), "forbid".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::CustomLintError(
+                "broken".into(),
+                "There's no pattern, set one in pb.customlint.broken.pattern".into()
+            )),
+            get_custom_lints(&config)
+        );
+    }
+
+    #[test]
+    fn unknown_modes_are_an_error() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.customlint.broken.pattern".into(), "x".into());
+        strings.insert("pb.customlint.broken.mode".into(), "maybe".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::CustomLintError(
+                "broken".into(),
+                "The mode \"maybe\" isn't valid, it can be require or forbid".into()
+            )),
+            get_custom_lints(&config)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+/// The long-form documentation for a lint, shown by `lint explain`
+///
+/// The examples are checked against the lint in the tests, so they always
+/// show what the lint really does
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Documentation {
+    description: String,
+    rationale: String,
+    options: Vec<(String, String)>,
+    passing: Vec<String>,
+    failing: Vec<String>,
+}
+
+impl Documentation {
+    #[must_use]
+    pub fn new(description: &str, rationale: &str) -> Documentation {
+        Documentation {
+            description: description.into(),
+            rationale: rationale.into(),
+            options: vec![],
+            passing: vec![],
+            failing: vec![],
+        }
+    }
+
+    /// A config key that changes how the lint behaves, and what it does
+    #[must_use]
+    pub fn with_option(mut self, key: &str, description: &str) -> Documentation {
+        self.options.push((key.into(), description.into()));
+        self
+    }
+
+    /// A commit message the lint has no problem with
+    #[must_use]
+    pub fn with_passing_example(mut self, example: &str) -> Documentation {
+        self.passing.push(example.into());
+        self
+    }
+
+    /// A commit message the lint finds a problem in
+    #[must_use]
+    pub fn with_failing_example(mut self, example: &str) -> Documentation {
+        self.failing.push(example.into());
+        self
+    }
+
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[must_use]
+    pub fn rationale(&self) -> &str {
+        &self.rationale
+    }
+
+    #[must_use]
+    pub fn options(&self) -> &[(String, String)] {
+        &self.options
+    }
+
+    #[must_use]
+    pub fn passing(&self) -> &[String] {
+        &self.passing
+    }
+
+    #[must_use]
+    pub fn failing(&self) -> &[String] {
+        &self.failing
+    }
+}
+
+#[cfg(test)]
+mod tests_documentation {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{CommitMessage, LintOptions, Lints};
+
+    #[test]
+    fn every_lint_has_examples() {
+        for lint in Lints::iterator() {
+            let documentation = lint.documentation();
+
+            assert!(!documentation.passing().is_empty(), "{}", lint);
+            assert!(!documentation.failing().is_empty(), "{}", lint);
+        }
+    }
+
+    #[test]
+    fn passing_examples_pass() {
+        for lint in Lints::iterator() {
+            for example in lint.documentation().passing() {
+                assert_eq!(
+                    None,
+                    lint.lint(
+                        &CommitMessage::new(example.clone()),
+                        &LintOptions::default()
+                    ),
+                    "{} should pass:\n{}",
+                    lint,
+                    example
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn failing_examples_fail() {
+        for lint in Lints::iterator() {
+            for example in lint.documentation().failing() {
+                assert!(
+                    lint.lint(
+                        &CommitMessage::new(example.clone()),
+                        &LintOptions::default()
+                    )
+                    .is_some(),
+                    "{} should fail:\n{}",
+                    lint,
+                    example
+                );
+            }
+        }
+    }
+}
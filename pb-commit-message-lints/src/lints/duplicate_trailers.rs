@@ -1,13 +1,30 @@
-use std::{collections::HashSet, iter::FromIterator};
-
-use crate::lints::{CommitMessage, LintCode, LintProblem};
-
-const TRAILERS_TO_CHECK_FOR_DUPLICATES: [&str; 2] = ["Signed-off-by", "Co-authored-by"];
+use std::collections::HashSet;
+
+use crate::lints::{
+    CommitMessage,
+    Documentation,
+    Edit,
+    Fix,
+    Label,
+    LabelKind,
+    LintCode,
+    LintOption,
+    LintProblem,
+    OptionKind,
+    Span,
+    Trailer,
+};
+
+pub(crate) const OPTION_TRAILERS: &str = "trailers";
+const DEFAULT_TRAILERS: &str = "Signed-off-by Co-authored-by";
+const REGEX_TRAILER_KEY: &str = r"^[A-Za-z0-9-]+$";
 const FIELD_SINGULAR: &str = "field";
 const FIELD_PLURAL: &str = "fields";
+const FIX_SINGULAR: &str = "Remove the duplicated trailer";
+const FIX_PLURAL: &str = "Remove the duplicated trailers";
 
-fn has_duplicated_trailers(commit_message: &CommitMessage) -> Vec<String> {
-    TRAILERS_TO_CHECK_FOR_DUPLICATES
+fn has_duplicated_trailers(commit_message: &CommitMessage, trailers: &[String]) -> Vec<String> {
+    trailers
         .iter()
         .filter_map(|trailer| filter_without_duplicates(commit_message, trailer))
         .collect::<Vec<String>>()
@@ -20,33 +37,121 @@ fn filter_without_duplicates(commit_message: &CommitMessage, trailer: &str) -> O
 }
 
 fn has_duplicated_trailer(commit_message: &CommitMessage, trailer: &str) -> bool {
-    Some(commit_message.get_trailer(trailer))
-        .map(|trailers| (trailers.clone(), trailers.clone()))
-        .map(|(commit, unique)| (commit, HashSet::<&str>::from_iter(unique)))
-        .map(|(commit, unique)| commit.len() != unique.len())
-        .unwrap()
+    !duplicated_trailer_lines(commit_message, trailer).is_empty()
+}
+
+/// The lines of every trailer that repeats one that came before it
+fn duplicated_trailer_lines(commit_message: &CommitMessage, trailer: &str) -> Vec<usize> {
+    let mut seen = HashSet::new();
+
+    commit_message
+        .get_trailer(trailer)
+        .into_iter()
+        .filter(|trailer| !seen.insert(trailer.value()))
+        .map(Trailer::line)
+        .collect()
+}
+
+/// Remove each duplicate, along with any lines it continues onto
+fn remove_duplicates(commit_message: &CommitMessage, lines: &[usize]) -> Fix {
+    Fix::new(
+        if lines.len() > 1 {
+            FIX_PLURAL
+        } else {
+            FIX_SINGULAR
+        },
+        lines
+            .iter()
+            .flat_map(|line| {
+                (*line..).take_while(move |number| {
+                    number == line
+                        || matches!(
+                            commit_message.line(*number),
+                            Some(text) if text.starts_with(char::is_whitespace)
+                        )
+                })
+            })
+            .map(Edit::Remove)
+            .collect(),
+    )
+}
+
+pub(crate) fn options() -> Vec<LintOption> {
+    vec![LintOption::new(
+        OPTION_TRAILERS,
+        "The trailers to check, separated by spaces. Defaults to `Signed-off-by Co-authored-by`",
+        OptionKind::List {
+            pattern: REGEX_TRAILER_KEY,
+            expected: "a trailer key, like `Signed-off-by`",
+        },
+        DEFAULT_TRAILERS,
+    )]
+}
+
+pub(crate) fn documentation() -> Documentation {
+    Documentation::new(
+        "Checks that no `Signed-off-by` or `Co-authored-by` trailer appears twice with the same \
+         value. This can be fixed automatically by removing the duplicates.",
+        "Tools like `git commit --signoff` and commit templates can add a trailer that's already \
+         there. The copies don't add anything, and they make it look like someone took part twice.",
+    )
+    .with_passing_example(
+        "An example commit\n\nSigned-off-by: Billie <billie@example.com>\nSigned-off-by: Someone \
+         Else <someone@example.com>\n",
+    )
+    .with_failing_example(
+        "An example commit\n\nSigned-off-by: Billie <billie@example.com>\nSigned-off-by: Billie \
+         <billie@example.com>\n",
+    )
+    .with_failing_example(
+        "An example commit\n\nCo-authored-by: Billie <billie@example.com>\nCo-authored-by: Billie \
+         <billie@example.com>\n",
+    )
 }
 
-pub(crate) fn lint_duplicated_trailers(commit_message: &CommitMessage) -> Option<LintProblem> {
-    let duplicated_trailers = has_duplicated_trailers(commit_message);
+pub(crate) fn lint_duplicated_trailers(
+    commit_message: &CommitMessage,
+    trailers: &[String],
+) -> Option<LintProblem> {
+    let duplicated_trailers = has_duplicated_trailers(commit_message, trailers);
     if duplicated_trailers.is_empty() {
         None
     } else {
-        Some(LintProblem::new(
-            format!(
-                r#"Your commit cannot have the same name duplicated in the "{}" {}
+        let lines = duplicated_trailers
+            .iter()
+            .flat_map(|trailer| duplicated_trailer_lines(commit_message, trailer))
+            .collect::<Vec<_>>();
+
+        Some(
+            lines
+                .iter()
+                .map(|line| {
+                    Label::new(
+                        Span::whole_line(commit_message, *line),
+                        LabelKind::Problem,
+                        "duplicated here",
+                    )
+                })
+                .fold(
+                    LintProblem::new(
+                        format!(
+                            r#"Your commit cannot have the same name duplicated in the "{}" {}
 
 You can fix this by removing the duplicated field when you commit again
 "#,
-                duplicated_trailers.join("\", \""),
-                if duplicated_trailers.len() > 1 {
-                    FIELD_PLURAL
-                } else {
-                    FIELD_SINGULAR
-                }
-            ),
-            LintCode::DuplicatedTrailers,
-        ))
+                            duplicated_trailers.join("\", \""),
+                            if duplicated_trailers.len() > 1 {
+                                FIELD_PLURAL
+                            } else {
+                                FIELD_SINGULAR
+                            }
+                        ),
+                        LintCode::DuplicatedTrailers,
+                    ),
+                    LintProblem::with_label,
+                )
+                .with_fix(remove_duplicates(commit_message, &lines)),
+        )
     }
 }
 
@@ -81,13 +186,29 @@ Co-authored-by: Billie Thompson <email@example.com>
 Co-authored-by: Billie Thompson <email@example.com>
 "#
             .into(),
-            &Some(LintProblem::new(
-                "Your commit cannot have the same name duplicated in the \"Signed-off-by\", \
-                 \"Co-authored-by\" fields\n\nYou can fix this by removing the duplicated field \
-                 when you commit again\n"
-                    .into(),
-                LintCode::DuplicatedTrailers,
-            )),
+            &Some(
+                LintProblem::new(
+                    "Your commit cannot have the same name duplicated in the \"Signed-off-by\", \
+                     \"Co-authored-by\" fields\n\nYou can fix this by removing the duplicated \
+                     field when you commit again\n"
+                        .into(),
+                    LintCode::DuplicatedTrailers,
+                )
+                .with_label(Label::new(
+                    Span::new(7, 1, 51),
+                    LabelKind::Problem,
+                    "duplicated here",
+                ))
+                .with_label(Label::new(
+                    Span::new(9, 1, 52),
+                    LabelKind::Problem,
+                    "duplicated here",
+                ))
+                .with_fix(Fix::new(
+                    "Remove the duplicated trailers",
+                    vec![Edit::Remove(7), Edit::Remove(9)],
+                )),
+            ),
         );
         test_lint_duplicated_trailers(
             r#"
@@ -99,13 +220,24 @@ Signed-off-by: Billie Thompson <email@example.com>
 Signed-off-by: Billie Thompson <email@example.com>
 "#
             .into(),
-            &Some(LintProblem::new(
-                "Your commit cannot have the same name duplicated in the \"Signed-off-by\" \
-                 field\n\nYou can fix this by removing the duplicated field when you commit \
-                 again\n"
-                    .into(),
-                LintCode::DuplicatedTrailers,
-            )),
+            &Some(
+                LintProblem::new(
+                    "Your commit cannot have the same name duplicated in the \"Signed-off-by\" \
+                     field\n\nYou can fix this by removing the duplicated field when you commit \
+                     again\n"
+                        .into(),
+                    LintCode::DuplicatedTrailers,
+                )
+                .with_label(Label::new(
+                    Span::new(7, 1, 51),
+                    LabelKind::Problem,
+                    "duplicated here",
+                ))
+                .with_fix(Fix::new(
+                    "Remove the duplicated trailer",
+                    vec![Edit::Remove(7)],
+                )),
+            ),
         );
         test_lint_duplicated_trailers(
             r#"
@@ -117,18 +249,72 @@ Co-authored-by: Billie Thompson <email@example.com>
 Co-authored-by: Billie Thompson <email@example.com>
 "#
             .into(),
-            &Some(LintProblem::new(
-                "Your commit cannot have the same name duplicated in the \"Co-authored-by\" \
-                 field\n\nYou can fix this by removing the duplicated field when you commit \
-                 again\n"
-                    .into(),
-                LintCode::DuplicatedTrailers,
+            &Some(
+                LintProblem::new(
+                    "Your commit cannot have the same name duplicated in the \"Co-authored-by\" \
+                     field\n\nYou can fix this by removing the duplicated field when you commit \
+                     again\n"
+                        .into(),
+                    LintCode::DuplicatedTrailers,
+                )
+                .with_label(Label::new(
+                    Span::new(7, 1, 52),
+                    LabelKind::Problem,
+                    "duplicated here",
+                ))
+                .with_fix(Fix::new(
+                    "Remove the duplicated trailer",
+                    vec![Edit::Remove(7)],
+                )),
+            ),
+        );
+    }
+
+    #[test]
+    fn the_fix_removes_continuation_lines() {
+        let commit = CommitMessage::new(
+            "An example commit\n\nSigned-off-by: Billie\n  Thompson\nSigned-off-by: Billie\n  \
+             Thompson\nCo-authored-by: Someone Else\n"
+                .into(),
+        );
+
+        assert_eq!(
+            Some(&Fix::new(
+                "Remove the duplicated trailer",
+                vec![Edit::Remove(5), Edit::Remove(6)],
             )),
+            lint_duplicated_trailers(&commit, &default_trailers())
+                .as_ref()
+                .and_then(LintProblem::fix)
+        );
+    }
+
+    #[test]
+    fn the_trailers_to_check_can_be_changed() {
+        let commit = CommitMessage::new(
+            "An example commit\n\nSigned-off-by: Billie\nSigned-off-by: Billie\nReviewed-by: \
+             Someone Else\nReviewed-by: Someone Else\n"
+                .into(),
+        );
+
+        assert_eq!(
+            Some(vec![Edit::Remove(6)]),
+            lint_duplicated_trailers(&commit, &["Reviewed-by".into()])
+                .as_ref()
+                .and_then(LintProblem::fix)
+                .map(|fix| fix.edits().to_vec())
         );
     }
 
+    fn default_trailers() -> Vec<String> {
+        DEFAULT_TRAILERS
+            .split_whitespace()
+            .map(String::from)
+            .collect()
+    }
+
     fn test_lint_duplicated_trailers(message: String, expected: &Option<LintProblem>) {
-        let actual = &lint_duplicated_trailers(&CommitMessage::new(message));
+        let actual = &lint_duplicated_trailers(&CommitMessage::new(message), &default_trailers());
         assert_eq!(
             actual, expected,
             "Expected {:?}, found {:?}",
@@ -241,4 +427,36 @@ Anything: Billie Thompson <email@example.com>
             true,
         );
     }
+
+    #[test]
+    fn trailer_keys_with_different_cases() {
+        test_has_duplicated_trailer(
+            r#"
+An example commit
+
+This is an example commit with duplicate trailers
+
+Signed-off-by: Billie Thompson <email@example.com>
+signed-off-by: Billie Thompson <email@example.com>
+"#,
+            "Signed-off-by",
+            true,
+        );
+    }
+
+    #[test]
+    fn trailers_mentioned_in_the_body() {
+        test_has_duplicated_trailer(
+            r#"
+An example commit
+
+Signed-off-by: Billie Thompson <email@example.com>
+is the trailer that gets added when you use --signoff
+
+Signed-off-by: Billie Thompson <email@example.com>
+"#,
+            "Signed-off-by",
+            false,
+        );
+    }
 }
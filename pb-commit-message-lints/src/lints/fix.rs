@@ -0,0 +1,191 @@
+use crate::{errors::PbCommitMessageLintsError, external::vcs::Vcs, lints::CommitMessage};
+
+const CONFIG_FIX: &str = "pb.fix";
+
+/// A change to a line of the commit message, numbered from 1 like a span
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Edit {
+    /// Add a line before this one, or after the last line if there isn't one
+    Insert(usize, String),
+    Replace(usize, String),
+    Remove(usize),
+}
+
+impl Edit {
+    #[must_use]
+    pub fn line(&self) -> usize {
+        match self {
+            Edit::Insert(line, _) | Edit::Replace(line, _) | Edit::Remove(line) => *line,
+        }
+    }
+}
+
+/// A mechanical change to the commit message that resolves a problem
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Fix {
+    description: String,
+    edits: Vec<Edit>,
+}
+
+impl Fix {
+    #[must_use]
+    pub fn new(description: &str, edits: Vec<Edit>) -> Fix {
+        Fix {
+            description: description.into(),
+            edits,
+        }
+    }
+
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    #[must_use]
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+}
+
+/// Apply fixes to the original text of the commit message
+///
+/// Line numbers always refer to the message before any fix was applied, and
+/// if more than one fix changes the same line only the first change is made
+#[must_use]
+pub fn apply_fixes(commit_message: &CommitMessage, fixes: &[&Fix]) -> String {
+    let contents = commit_message.to_string();
+    let lines = contents.lines().collect::<Vec<_>>();
+    let edits = fixes.iter().flat_map(|fix| fix.edits()).collect::<Vec<_>>();
+    let mut fixed = vec![];
+
+    for (index, line) in lines.iter().enumerate() {
+        let number = index + 1;
+        fixed.extend(inserted_at(&edits, number));
+
+        match edits
+            .iter()
+            .find(|edit| !matches!(edit, Edit::Insert(..)) && edit.line() == number)
+        {
+            Some(Edit::Replace(_, text)) => fixed.push(text.clone()),
+            Some(Edit::Remove(_)) => {},
+            _ => fixed.push((*line).to_string()),
+        }
+    }
+
+    let mut after_end = edits
+        .iter()
+        .filter(|edit| matches!(edit, Edit::Insert(line, _) if *line > lines.len()))
+        .map(|edit| edit.line())
+        .collect::<Vec<_>>();
+    after_end.sort_unstable();
+    after_end.dedup();
+    after_end
+        .into_iter()
+        .for_each(|line| fixed.extend(inserted_at(&edits, line)));
+
+    let mut output = fixed.join("\n");
+    if contents.ends_with('\n') || (contents.is_empty() && !output.is_empty()) {
+        output.push('\n');
+    }
+
+    output
+}
+
+fn inserted_at(edits: &[&Edit], line: usize) -> Vec<String> {
+    edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Edit::Insert(insert_line, text) if *insert_line == line => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether fixes should be applied to commit messages automatically
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source
+pub fn get_fix_configuration(config: &dyn Vcs) -> Result<bool, PbCommitMessageLintsError> {
+    Ok(config.get_bool(CONFIG_FIX)?.unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests_apply_fixes {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{apply_fixes, CommitMessage, Edit, Fix};
+
+    #[test]
+    fn lines_can_be_removed_and_replaced() {
+        let commit = CommitMessage::new("Subject\n\nBody\nTrailer: 1\nTrailer: 1\n".into());
+        let fix = Fix::new(
+            "Tidy up",
+            vec![Edit::Replace(3, "New body".into()), Edit::Remove(5)],
+        );
+
+        assert_eq!(
+            "Subject\n\nNew body\nTrailer: 1\n",
+            apply_fixes(&commit, &[&fix])
+        );
+    }
+
+    #[test]
+    fn lines_can_be_inserted_before_others_or_at_the_end() {
+        let commit = CommitMessage::new("Subject\n\nBody\n".into());
+        let fix = Fix::new(
+            "Add things",
+            vec![
+                Edit::Insert(1, "First".into()),
+                Edit::Insert(9, "Last".into()),
+                Edit::Insert(4, "".into()),
+            ],
+        );
+
+        assert_eq!(
+            "First\nSubject\n\nBody\n\nLast\n",
+            apply_fixes(&commit, &[&fix])
+        );
+    }
+
+    #[test]
+    fn the_first_change_to_a_line_wins() {
+        let commit = CommitMessage::new("Subject\n".into());
+        let first = Fix::new("First", vec![Edit::Replace(1, "First".into())]);
+        let second = Fix::new("Second", vec![Edit::Remove(1)]);
+
+        assert_eq!("First\n", apply_fixes(&commit, &[&first, &second]));
+    }
+
+    #[test]
+    fn a_missing_trailing_newline_is_kept_missing() {
+        let commit = CommitMessage::new("Subject\nExtra".into());
+        let fix = Fix::new("Remove", vec![Edit::Remove(2)]);
+
+        assert_eq!("Subject", apply_fixes(&commit, &[&fix]));
+    }
+}
+
+#[cfg(test)]
+mod tests_get_fix_configuration {
+    use std::collections::HashMap;
+
+    use crate::{external::vcs::InMemory, lints::get_fix_configuration};
+
+    #[test]
+    fn fixes_are_off_by_default() {
+        let mut strings = HashMap::new();
+        let config = InMemory::new(&mut strings);
+
+        assert!(!get_fix_configuration(&config).unwrap());
+    }
+
+    #[test]
+    fn fixes_can_be_turned_on() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.fix".into(), "true".into());
+        let config = InMemory::new(&mut strings);
+
+        assert!(get_fix_configuration(&config).unwrap());
+    }
+}
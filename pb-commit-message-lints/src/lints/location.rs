@@ -0,0 +1,123 @@
+use crate::lints::CommitMessage;
+
+/// A range of characters on one line of the commit message
+///
+/// Lines and columns both start at 1, and the end column is exclusive
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Span {
+    line: usize,
+    start_column: usize,
+    end_column: usize,
+}
+
+impl Span {
+    #[must_use]
+    pub fn new(line: usize, start_column: usize, end_column: usize) -> Span {
+        Span {
+            line,
+            start_column,
+            end_column,
+        }
+    }
+
+    /// A span that covers all of a line of the commit message
+    #[must_use]
+    pub fn whole_line(commit_message: &CommitMessage, line: usize) -> Span {
+        let width = commit_message
+            .line(line)
+            .map_or(0, |text| text.chars().count());
+
+        Span::new(line, 1, width + 1)
+    }
+
+    #[must_use]
+    pub fn line(self) -> usize {
+        self.line
+    }
+
+    #[must_use]
+    pub fn start_column(self) -> usize {
+        self.start_column
+    }
+
+    #[must_use]
+    pub fn end_column(self) -> usize {
+        self.end_column
+    }
+}
+
+/// Whether a label marks the text that caused a problem, or suggests where
+/// something that is missing could go
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum LabelKind {
+    Problem,
+    Suggestion,
+}
+
+/// A span of the commit message, with a short note about it
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Label {
+    span: Span,
+    kind: LabelKind,
+    message: String,
+}
+
+impl Label {
+    #[must_use]
+    pub fn new(span: Span, kind: LabelKind, message: &str) -> Label {
+        Label {
+            span,
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Suggest the subject line as the place to add something that is missing
+    #[must_use]
+    pub fn subject_suggestion(commit_message: &CommitMessage, message: &str) -> Option<Label> {
+        commit_message.subject_line_number().map(|line| {
+            Label::new(
+                Span::whole_line(commit_message, line),
+                LabelKind::Suggestion,
+                message,
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> LabelKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(test)]
+mod tests_span {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{CommitMessage, Span};
+
+    #[test]
+    fn a_whole_line_is_measured_in_characters() {
+        let commit = CommitMessage::new("Some Commit Message\n\nBody with ünïcödé\n".into());
+
+        assert_eq!(Span::new(1, 1, 20), Span::whole_line(&commit, 1));
+        assert_eq!(Span::new(3, 1, 18), Span::whole_line(&commit, 3));
+    }
+
+    #[test]
+    fn missing_lines_are_empty() {
+        let commit = CommitMessage::new("Some Commit Message\n".into());
+
+        assert_eq!(Span::new(4, 1, 1), Span::whole_line(&commit, 4));
+    }
+}
@@ -1,6 +1,14 @@
 use regex::Regex;
 
-use crate::lints::{CommitMessage, LintCode, LintProblem};
+use crate::lints::{
+    CommitMessage,
+    Documentation,
+    Label,
+    LintCode,
+    LintOption,
+    LintProblem,
+    OptionKind,
+};
 
 const JIRA_HELP_MESSAGE: &str = r#"
 Your commit is missing a JIRA Issue Key
@@ -8,19 +16,76 @@ Your commit is missing a JIRA Issue Key
 You can fix this by adding a key like `JRA-123` to the commit message
 "#;
 
-const REGEX_JIRA_ISSUE_KEY: &str = r"(?m)(^| )[A-Z]{2,}-[0-9]+( |$)";
+const JIRA_SUGGESTION: &str = "consider adding a key like `JRA-123` here";
 
-fn has_missing_jira_issue_key(commit_message: &CommitMessage) -> bool {
-    let re = Regex::new(REGEX_JIRA_ISSUE_KEY).unwrap();
+pub(crate) const OPTION_PROJECTS: &str = "projects";
+const REGEX_ANY_PROJECT: &str = "[A-Z]{2,}";
+const REGEX_PROJECT_KEY: &str = r"^[A-Z][A-Z0-9_]+$";
+
+/// Any project's keys will do, unless the projects have been listed
+fn jira_issue_key_pattern(projects: &[String]) -> String {
+    let project = if projects.is_empty() {
+        REGEX_ANY_PROJECT.to_string()
+    } else {
+        format!(
+            "(?:{})",
+            projects
+                .iter()
+                .map(|project| regex::escape(project))
+                .collect::<Vec<_>>()
+                .join("|")
+        )
+    };
+
+    format!(r"(?m)(^| ){}-[0-9]+( |$)", project)
+}
+
+fn has_missing_jira_issue_key(commit_message: &CommitMessage, projects: &[String]) -> bool {
+    let re = Regex::new(&jira_issue_key_pattern(projects)).unwrap();
     !commit_message.matches_pattern(&re)
 }
 
-pub(crate) fn lint_missing_jira_issue_key(commit_message: &CommitMessage) -> Option<LintProblem> {
-    if has_missing_jira_issue_key(commit_message) {
-        Some(LintProblem::new(
-            JIRA_HELP_MESSAGE.into(),
-            LintCode::JiraIssueKeyMissing,
-        ))
+pub(crate) fn options() -> Vec<LintOption> {
+    vec![LintOption::new(
+        OPTION_PROJECTS,
+        "The keys of the Jira projects the issue can be in, separated by spaces. Any project will \
+         do if this isn't set",
+        OptionKind::List {
+            pattern: REGEX_PROJECT_KEY,
+            expected: "a Jira project key, like `JRA`",
+        },
+        "",
+    )]
+}
+
+pub(crate) fn documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the commit message mentions a Jira Issue Key somewhere. A key is two or more \
+         capital letters, a dash, and the number of the issue, like `JRA-123`. It needs to be a \
+         word on its own, so it has to be at the start or end of a line, or have spaces around it.",
+        "Jira links commits to issues using the key in the message. Without it the work doesn't \
+         show up on the issue, and it's hard to find out later why a change was made.",
+    )
+    .with_passing_example("JRA-123 An example commit\n\nThis is an example commit\n")
+    .with_passing_example("An example commit\n\nThis is an example commit\n\nJRA-123\n")
+    .with_failing_example("An example commit\n\nThis is an example commit\n")
+    .with_failing_example("An example commit\n\nThis is an example commit\n\nJRA123\n")
+    .with_failing_example("jra-123 An example commit\n")
+}
+
+pub(crate) fn lint_missing_jira_issue_key(
+    commit_message: &CommitMessage,
+    projects: &[String],
+) -> Option<LintProblem> {
+    if has_missing_jira_issue_key(commit_message, projects) {
+        Some(
+            Label::subject_suggestion(commit_message, JIRA_SUGGESTION)
+                .into_iter()
+                .fold(
+                    LintProblem::new(JIRA_HELP_MESSAGE.into(), LintCode::JiraIssueKeyMissing),
+                    LintProblem::with_label,
+                ),
+        )
     } else {
         None
     }
@@ -33,6 +98,7 @@ mod tests_has_missing_jira_issue_key {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::lints::{LabelKind, Span};
 
     #[test]
     fn id_present() {
@@ -89,12 +155,19 @@ An example commit
 
 This is an example commit
 "#,
-            &Some(LintProblem::new(
-                "\nYour commit is missing a JIRA Issue Key\n\nYou can fix this by adding a key \
-                 like `JRA-123` to the commit message\n"
-                    .into(),
-                LintCode::JiraIssueKeyMissing,
-            )),
+            &Some(
+                LintProblem::new(
+                    "\nYour commit is missing a JIRA Issue Key\n\nYou can fix this by adding a \
+                     key like `JRA-123` to the commit message\n"
+                        .into(),
+                    LintCode::JiraIssueKeyMissing,
+                )
+                .with_label(Label::new(
+                    Span::new(2, 1, 18),
+                    LabelKind::Suggestion,
+                    "consider adding a key like `JRA-123` here",
+                )),
+            ),
         );
         test_has_missing_jira_issue_key(
             r#"
@@ -104,12 +177,19 @@ This is an example commit
 
 A-123
 "#,
-            &Some(LintProblem::new(
-                "\nYour commit is missing a JIRA Issue Key\n\nYou can fix this by adding a key \
-                 like `JRA-123` to the commit message\n"
-                    .into(),
-                LintCode::JiraIssueKeyMissing,
-            )),
+            &Some(
+                LintProblem::new(
+                    "\nYour commit is missing a JIRA Issue Key\n\nYou can fix this by adding a \
+                     key like `JRA-123` to the commit message\n"
+                        .into(),
+                    LintCode::JiraIssueKeyMissing,
+                )
+                .with_label(Label::new(
+                    Span::new(2, 1, 18),
+                    LabelKind::Suggestion,
+                    "consider adding a key like `JRA-123` here",
+                )),
+            ),
         );
         test_has_missing_jira_issue_key(
             r#"
@@ -119,17 +199,42 @@ This is an example commit
 
 JRA-
 "#,
-            &Some(LintProblem::new(
-                "\nYour commit is missing a JIRA Issue Key\n\nYou can fix this by adding a key \
-                 like `JRA-123` to the commit message\n"
-                    .into(),
-                LintCode::JiraIssueKeyMissing,
-            )),
+            &Some(
+                LintProblem::new(
+                    "\nYour commit is missing a JIRA Issue Key\n\nYou can fix this by adding a \
+                     key like `JRA-123` to the commit message\n"
+                        .into(),
+                    LintCode::JiraIssueKeyMissing,
+                )
+                .with_label(Label::new(
+                    Span::new(2, 1, 18),
+                    LabelKind::Suggestion,
+                    "consider adding a key like `JRA-123` here",
+                )),
+            ),
+        );
+    }
+
+    #[test]
+    fn the_projects_can_be_limited() {
+        let projects = vec!["CORE".to_string(), "UI".to_string()];
+
+        assert_eq!(
+            None,
+            lint_missing_jira_issue_key(
+                &CommitMessage::new("UI-12 An example commit\n".into()),
+                &projects
+            )
         );
+        assert!(lint_missing_jira_issue_key(
+            &CommitMessage::new("JRA-123 An example commit\n".into()),
+            &projects
+        )
+        .is_some());
     }
 
     fn test_has_missing_jira_issue_key(message: &str, expected: &Option<LintProblem>) {
-        let actual = &lint_missing_jira_issue_key(&CommitMessage::new(message.into()));
+        let actual = &lint_missing_jira_issue_key(&CommitMessage::new(message.into()), &[]);
         assert_eq!(
             actual, expected,
             "Message {:?} should have returned {:?}, found {:?}",
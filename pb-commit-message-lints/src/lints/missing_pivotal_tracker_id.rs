@@ -1,6 +1,8 @@
 use regex::Regex;
 
-use crate::lints::{CommitMessage, LintCode, LintProblem};
+use crate::lints::{CommitMessage, Documentation, Label, LintCode, LintProblem};
+
+const PIVOTAL_TRACKER_SUGGESTION: &str = "consider adding an id like `[#12345678]` here";
 
 const REGEX_PIVOTAL_TRACKER_ID: &str =
     r"(?i)\[(((finish|fix)(ed|es)?|complete[ds]?|deliver(s|ed)?) )?#\d+([, ]#\d+)*]";
@@ -14,14 +16,39 @@ fn has_no_pivotal_tracker_id(text: &CommitMessage) -> bool {
     !text.matches_pattern(&re)
 }
 
+pub(crate) fn documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the commit message mentions a Pivotal Tracker story id in square brackets, \
+         like `[#12345678]`. Several ids can go in the same brackets, separated by spaces or \
+         commas, and the id can come after `fixes`, `finishes`, `completes` or `delivers` to \
+         change the state of the story.",
+        "Pivotal Tracker links commits to stories using the id in the message, and can move a \
+         story along when it sees one of the state changing words. Without it the work doesn't \
+         show up on the story.",
+    )
+    .with_passing_example("An example commit\n\nThis is an example commit\n\n[#12345678]\n")
+    .with_passing_example("An example commit\n\n[Delivers #12345678]\n")
+    .with_passing_example("An example commit\n\n[fixes #12345884 #12345678]\n")
+    .with_failing_example("An example commit\n\nThis is an example commit\n")
+    .with_failing_example("An example commit\n\n#12345678\n")
+    .with_failing_example("An example commit\n\n[Broke #12345678]\n")
+}
+
 pub(crate) fn lint_missing_pivotal_tracker_id(
     commit_message: &CommitMessage,
 ) -> Option<LintProblem> {
     if has_missing_pivotal_tracker_id(commit_message) {
-        Some(LintProblem::new(
-            PIVOTAL_TRACKER_HELP.into(),
-            LintCode::PivotalTrackerIdMissing,
-        ))
+        Some(
+            Label::subject_suggestion(commit_message, PIVOTAL_TRACKER_SUGGESTION)
+                .into_iter()
+                .fold(
+                    LintProblem::new(
+                        PIVOTAL_TRACKER_HELP.into(),
+                        LintCode::PivotalTrackerIdMissing,
+                    ),
+                    LintProblem::with_label,
+                ),
+        )
     } else {
         None
     }
@@ -46,7 +73,7 @@ mod tests_has_missing_pivotal_tracker_id {
 
     use pretty_assertions::assert_eq;
 
-    use crate::lints::CommitMessage;
+    use crate::lints::{CommitMessage, LabelKind, Span};
 
     use super::*;
 
@@ -316,15 +343,22 @@ This is an example commit
 
 [fake #12345678]
 "#,
-            &Some(LintProblem::new(
-                "\nYour commit is missing a Pivotal Tracker Id\n\nYou can fix this by adding the \
-                 Id in one of the styles below to the commit message\n[Delivers \
-                 #12345678]\n[fixes #12345678]\n[finishes #12345678]\n[#12345884 \
-                 #12345678]\n[#12345884,#12345678]\n[#12345678],[#12345884]\nThis will address \
-                 [#12345884]\n"
-                    .into(),
-                LintCode::PivotalTrackerIdMissing,
-            )),
+            &Some(
+                LintProblem::new(
+                    "\nYour commit is missing a Pivotal Tracker Id\n\nYou can fix this by adding \
+                     the Id in one of the styles below to the commit message\n[Delivers \
+                     #12345678]\n[fixes #12345678]\n[finishes #12345678]\n[#12345884 \
+                     #12345678]\n[#12345884,#12345678]\n[#12345678],[#12345884]\nThis will \
+                     address [#12345884]\n"
+                        .into(),
+                    LintCode::PivotalTrackerIdMissing,
+                )
+                .with_label(Label::new(
+                    Span::new(2, 1, 18),
+                    LabelKind::Suggestion,
+                    "consider adding an id like `[#12345678]` here",
+                )),
+            ),
         );
     }
 
@@ -336,15 +370,22 @@ An example commit
 
 This is an example commit
 "#,
-            &Some(LintProblem::new(
-                "\nYour commit is missing a Pivotal Tracker Id\n\nYou can fix this by adding the \
-                 Id in one of the styles below to the commit message\n[Delivers \
-                 #12345678]\n[fixes #12345678]\n[finishes #12345678]\n[#12345884 \
-                 #12345678]\n[#12345884,#12345678]\n[#12345678],[#12345884]\nThis will address \
-                 [#12345884]\n"
-                    .into(),
-                LintCode::PivotalTrackerIdMissing,
-            )),
+            &Some(
+                LintProblem::new(
+                    "\nYour commit is missing a Pivotal Tracker Id\n\nYou can fix this by adding \
+                     the Id in one of the styles below to the commit message\n[Delivers \
+                     #12345678]\n[fixes #12345678]\n[finishes #12345678]\n[#12345884 \
+                     #12345678]\n[#12345884,#12345678]\n[#12345678],[#12345884]\nThis will \
+                     address [#12345884]\n"
+                        .into(),
+                    LintCode::PivotalTrackerIdMissing,
+                )
+                .with_label(Label::new(
+                    Span::new(2, 1, 18),
+                    LabelKind::Suggestion,
+                    "consider adding an id like `[#12345678]` here",
+                )),
+            ),
         );
 
         test_has_missing_pivotal_tracker_id(
@@ -355,15 +396,22 @@ This is an example commit
 
 [#]
 "#,
-            &Some(LintProblem::new(
-                "\nYour commit is missing a Pivotal Tracker Id\n\nYou can fix this by adding the \
-                 Id in one of the styles below to the commit message\n[Delivers \
-                 #12345678]\n[fixes #12345678]\n[finishes #12345678]\n[#12345884 \
-                 #12345678]\n[#12345884,#12345678]\n[#12345678],[#12345884]\nThis will address \
-                 [#12345884]\n"
-                    .into(),
-                LintCode::PivotalTrackerIdMissing,
-            )),
+            &Some(
+                LintProblem::new(
+                    "\nYour commit is missing a Pivotal Tracker Id\n\nYou can fix this by adding \
+                     the Id in one of the styles below to the commit message\n[Delivers \
+                     #12345678]\n[fixes #12345678]\n[finishes #12345678]\n[#12345884 \
+                     #12345678]\n[#12345884,#12345678]\n[#12345678],[#12345884]\nThis will \
+                     address [#12345884]\n"
+                        .into(),
+                    LintCode::PivotalTrackerIdMissing,
+                )
+                .with_label(Label::new(
+                    Span::new(2, 1, 18),
+                    LabelKind::Suggestion,
+                    "consider adding an id like `[#12345678]` here",
+                )),
+            ),
         );
     }
 }
@@ -1,113 +1,63 @@
-use std::fmt::Display;
-
-use regex::Regex;
-
+use std::{collections::HashSet, convert::TryFrom, fmt::Display, sync::Arc};
+
+pub use crate::lints::{
+    commit_message::{
+        get_parse_configuration,
+        CleanupMode,
+        CommentChar,
+        CommitMessage,
+        ParseConfiguration,
+        Trailer,
+    },
+    conventional_commits::ConventionalHeader,
+    custom::{get_custom_lints, CustomLint, CustomLintMode, CustomLintTarget},
+    documentation::Documentation,
+    fix::{apply_fixes, get_fix_configuration, Edit, Fix},
+    location::{Label, LabelKind, Span},
+    options::{
+        get_lint_options,
+        set_lint_option,
+        LintOption,
+        LintOptions,
+        OptionKind,
+        OptionValue,
+    },
+    plugin::{get_plugins, run_plugins, Plugin, PluginContext},
+    profile::{apply_profile, get_profile, get_profiles, get_selected_profile, Profile},
+    ref_filter::{get_ref_filter, RefFilter},
+    registry::{Lint, LintRegistry},
+    script::{get_scripts, run_scripts, Script},
+    severity::Severity,
+    suppression::{get_suppressed_names, get_suppressions},
+};
 use crate::{
     errors::PbCommitMessageLintsError,
     external::vcs::Vcs,
     lints::{
-        duplicate_trailers::lint_duplicated_trailers,
-        missing_jira_issue_key::lint_missing_jira_issue_key,
+        conventional_commits::{lint_conventional_commits, OPTION_SCOPES, OPTION_TYPES},
+        duplicate_trailers::{lint_duplicated_trailers, OPTION_TRAILERS},
+        missing_jira_issue_key::{lint_missing_jira_issue_key, OPTION_PROJECTS},
         missing_pivotal_tracker_id::lint_missing_pivotal_tracker_id,
-        Lints::{DuplicatedTrailers, JiraIssueKeyMissing, PivotalTrackerIdMissing},
+        profile::check_profile,
+        subject::{
+            lint_subject_ends_with_period,
+            lint_subject_extra_whitespace,
+            lint_subject_not_capitalised,
+            lint_subject_too_long,
+            OPTION_MAX_LENGTH,
+        },
+        Lints::{
+            ConventionalCommits,
+            DuplicatedTrailers,
+            JiraIssueKeyMissing,
+            PivotalTrackerIdMissing,
+            SubjectEndsWithPeriod,
+            SubjectExtraWhitespace,
+            SubjectNotCapitalised,
+            SubjectTooLong,
+        },
     },
 };
-use std::{convert::TryFrom, fs::File, io::Read, path::PathBuf};
-
-pub struct CommitMessage {
-    contents: String,
-}
-
-impl CommitMessage {
-    #[must_use]
-    pub fn new(contents: String) -> CommitMessage {
-        CommitMessage { contents }
-    }
-
-    pub fn matches_pattern(&self, re: &Regex) -> bool {
-        re.is_match(&self.contents)
-    }
-
-    #[must_use]
-    pub fn get_trailer(&self, trailer: &str) -> Vec<&str> {
-        self.contents
-            .lines()
-            .filter(|line: &&str| CommitMessage::line_has_trailer(trailer, line))
-            .collect::<Vec<_>>()
-    }
-
-    fn line_has_trailer(trailer: &str, line: &str) -> bool {
-        line.starts_with(&format!("{}:", trailer))
-    }
-}
-
-impl TryFrom<PathBuf> for CommitMessage {
-    type Error = PbCommitMessageLintsError;
-
-    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
-        let mut file = File::open(value)?;
-        let mut buffer = String::new();
-
-        file.read_to_string(&mut buffer)
-            .map_err(PbCommitMessageLintsError::from)
-            .map(move |_| CommitMessage::new(buffer))
-    }
-}
-
-impl Display for CommitMessage {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.contents)
-    }
-}
-
-#[cfg(test)]
-mod test_commit_message {
-    use pretty_assertions::assert_eq;
-    use regex::Regex;
-
-    use crate::lints::CommitMessage;
-
-    #[test]
-    fn with_trailers() {
-        let commit = CommitMessage::new(
-            r#"Some Commit Message
-
-Anything: Some Trailer
-Anything: Some Trailer
-Another: Trailer
-"#
-            .into(),
-        );
-
-        assert_eq!(vec!["Another: Trailer"], commit.get_trailer("Another"));
-        assert_eq!(
-            vec!["Anything: Some Trailer", "Anything: Some Trailer"],
-            commit.get_trailer("Anything")
-        )
-    }
-
-    #[test]
-    fn regex_matching() {
-        let commit = CommitMessage::new(
-            r#"Some Commit Message
-
-Anything: Some Trailer
-Anything: Some Trailer
-Another: Trailer
-"#
-            .into(),
-        );
-
-        assert_eq!(
-            true,
-            commit.matches_pattern(&Regex::new("[AB]nything:").unwrap())
-        );
-        assert_eq!(
-            false,
-            commit.matches_pattern(&Regex::new("N[oO]thing:").unwrap())
-        );
-    }
-}
 
 /// The lints that are supported
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -115,33 +65,156 @@ pub enum Lints {
     DuplicatedTrailers,
     PivotalTrackerIdMissing,
     JiraIssueKeyMissing,
+    ConventionalCommits,
+    SubjectTooLong,
+    SubjectEndsWithPeriod,
+    SubjectNotCapitalised,
+    SubjectExtraWhitespace,
 }
 
 const CONFIG_DUPLICATED_TRAILERS: &str = "duplicated-trailers";
 const CONFIG_PIVOTAL_TRACKER_ID_MISSING: &str = "pivotal-tracker-id-missing";
 const CONFIG_JIRA_ISSUE_KEY_MISSING: &str = "jira-issue-key-missing";
+const CONFIG_CONVENTIONAL_COMMITS: &str = "conventional-commits";
+const CONFIG_SUBJECT_TOO_LONG: &str = "subject-too-long";
+const CONFIG_SUBJECT_ENDS_WITH_PERIOD: &str = "subject-ends-with-period";
+const CONFIG_SUBJECT_NOT_CAPITALISED: &str = "subject-not-capitalised";
+const CONFIG_SUBJECT_EXTRA_WHITESPACE: &str = "subject-extra-whitespace";
+const CONFIG_ALLOW_SUPPRESSIONS: &str = "pb.allow-lint-skip";
 
 impl Lints {
     pub fn iterator() -> impl Iterator<Item = Lints> {
-        static LINTS: [Lints; 3] = [
+        static LINTS: [Lints; 8] = [
             DuplicatedTrailers,
             PivotalTrackerIdMissing,
             JiraIssueKeyMissing,
+            ConventionalCommits,
+            SubjectTooLong,
+            SubjectEndsWithPeriod,
+            SubjectNotCapitalised,
+            SubjectExtraWhitespace,
         ];
         LINTS.iter().copied()
     }
 
     #[must_use]
     pub fn config_key(self) -> String {
-        format!("pb.lint.{}", self)
+        lint_config_key(self.name())
     }
 
     #[must_use]
-    pub fn lint(self, commit_message: &CommitMessage) -> Option<LintProblem> {
+    pub fn lint(
+        self,
+        commit_message: &CommitMessage,
+        options: &LintOptions,
+    ) -> Option<LintProblem> {
         match self {
-            Lints::DuplicatedTrailers => lint_duplicated_trailers(commit_message),
+            Lints::DuplicatedTrailers => lint_duplicated_trailers(
+                commit_message,
+                options.get(&self, OPTION_TRAILERS).as_list(),
+            ),
             Lints::PivotalTrackerIdMissing => lint_missing_pivotal_tracker_id(commit_message),
-            Lints::JiraIssueKeyMissing => lint_missing_jira_issue_key(commit_message),
+            Lints::JiraIssueKeyMissing => lint_missing_jira_issue_key(
+                commit_message,
+                options.get(&self, OPTION_PROJECTS).as_list(),
+            ),
+            Lints::ConventionalCommits => lint_conventional_commits(
+                commit_message,
+                options.get(&self, OPTION_TYPES).as_list(),
+                options.get(&self, OPTION_SCOPES).as_list(),
+            ),
+            Lints::SubjectTooLong => lint_subject_too_long(
+                commit_message,
+                options.get(&self, OPTION_MAX_LENGTH).as_number(),
+            ),
+            Lints::SubjectEndsWithPeriod => lint_subject_ends_with_period(commit_message),
+            Lints::SubjectNotCapitalised => lint_subject_not_capitalised(commit_message),
+            Lints::SubjectExtraWhitespace => lint_subject_extra_whitespace(commit_message),
+        }
+    }
+
+    /// The settings that change how the lint behaves
+    #[must_use]
+    pub fn options(self) -> Vec<LintOption> {
+        match self {
+            Lints::DuplicatedTrailers => duplicate_trailers::options(),
+            Lints::SubjectTooLong => subject::max_length_options(),
+            Lints::PivotalTrackerIdMissing
+            | Lints::SubjectEndsWithPeriod
+            | Lints::SubjectNotCapitalised
+            | Lints::SubjectExtraWhitespace => vec![],
+            Lints::JiraIssueKeyMissing => missing_jira_issue_key::options(),
+            Lints::ConventionalCommits => conventional_commits::options(),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// If the lint has no option with this name
+    pub fn option(self, name: &str) -> Result<LintOption, PbCommitMessageLintsError> {
+        self.options()
+            .into_iter()
+            .find(|option| option.name() == name)
+            .ok_or_else(|| {
+                PbCommitMessageLintsError::LintOptionNotFoundError(self.name().into(), name.into())
+            })
+    }
+
+    #[must_use]
+    pub fn option_key(self, name: &str) -> String {
+        format!("{}.{}", self.config_key(), name)
+    }
+
+    /// What the lint checks for
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Lints::DuplicatedTrailers => {
+                "Detect duplicated `Signed-off-by` and `Co-authored-by` trailers"
+            },
+            Lints::PivotalTrackerIdMissing => "Detect a missing Pivotal Tracker Id",
+            Lints::JiraIssueKeyMissing => "Detect a missing Jira Issue Key",
+            Lints::ConventionalCommits => "Check the subject follows Conventional Commits",
+            Lints::SubjectTooLong => "Detect a subject that is too long",
+            Lints::SubjectEndsWithPeriod => "Detect a subject ending with a period",
+            Lints::SubjectNotCapitalised => "Detect a subject that doesn't start with a capital",
+            Lints::SubjectExtraWhitespace => "Detect whitespace at the start or end of the subject",
+        }
+    }
+
+    /// The long-form documentation for the lint
+    #[must_use]
+    pub fn documentation(self) -> Documentation {
+        let documentation = match self {
+            Lints::DuplicatedTrailers => duplicate_trailers::documentation(),
+            Lints::PivotalTrackerIdMissing => missing_pivotal_tracker_id::documentation(),
+            Lints::JiraIssueKeyMissing => missing_jira_issue_key::documentation(),
+            Lints::ConventionalCommits => conventional_commits::documentation(),
+            Lints::SubjectTooLong => subject::too_long_documentation(),
+            Lints::SubjectEndsWithPeriod => subject::ends_with_period_documentation(),
+            Lints::SubjectNotCapitalised => subject::not_capitalised_documentation(),
+            Lints::SubjectExtraWhitespace => subject::extra_whitespace_documentation(),
+        };
+
+        self.options()
+            .iter()
+            .fold(documentation, |documentation, option| {
+                documentation.with_option(&self.option_key(option.name()), option.description())
+            })
+    }
+
+    /// The severity the lint has when it hasn't been configured
+    #[must_use]
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Lints::DuplicatedTrailers => Severity::Error,
+            Lints::PivotalTrackerIdMissing
+            | Lints::JiraIssueKeyMissing
+            | Lints::ConventionalCommits
+            | Lints::SubjectTooLong
+            | Lints::SubjectEndsWithPeriod
+            | Lints::SubjectNotCapitalised
+            | Lints::SubjectExtraWhitespace => Severity::Off,
         }
     }
 }
@@ -174,38 +247,388 @@ impl std::convert::TryFrom<&str> for Lints {
     }
 }
 
+/// Only the built-in lints have a code of their own
+impl std::convert::TryFrom<LintCode> for Lints {
+    type Error = PbCommitMessageLintsError;
+
+    fn try_from(from: LintCode) -> Result<Self, Self::Error> {
+        match from {
+            LintCode::DuplicatedTrailers => Ok(DuplicatedTrailers),
+            LintCode::PivotalTrackerIdMissing => Ok(PivotalTrackerIdMissing),
+            LintCode::JiraIssueKeyMissing => Ok(JiraIssueKeyMissing),
+            LintCode::ConventionalCommits => Ok(ConventionalCommits),
+            LintCode::SubjectTooLong => Ok(SubjectTooLong),
+            LintCode::SubjectEndsWithPeriod => Ok(SubjectEndsWithPeriod),
+            LintCode::SubjectNotCapitalised => Ok(SubjectNotCapitalised),
+            LintCode::SubjectExtraWhitespace => Ok(SubjectExtraWhitespace),
+            LintCode::CustomLint | LintCode::Plugin | LintCode::Script => Err(
+                PbCommitMessageLintsError::LintNotFoundError(format!("{:?}", from)),
+            ),
+        }
+    }
+}
+
 impl std::convert::From<Lints> for String {
     fn from(from: Lints) -> Self {
         format!("{}", from)
     }
 }
 
-/// Get the lints that are currently enabled
+/// The severity each lint has been given, and the options they've been
+/// configured with
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LintConfiguration {
+    severities: Vec<(Arc<dyn Lint>, Severity)>,
+    custom: Vec<(CustomLint, Severity)>,
+    plugins: Vec<(Plugin, Severity)>,
+    scripts: Vec<(Script, Severity)>,
+    options: LintOptions,
+    profile: Option<Profile>,
+    suppressions_allowed: bool,
+}
+
+impl LintConfiguration {
+    #[must_use]
+    pub fn new(severities: Vec<(Lints, Severity)>) -> LintConfiguration {
+        LintConfiguration {
+            severities: severities
+                .into_iter()
+                .map(|(lint, severity)| (Arc::new(lint) as Arc<dyn Lint>, severity))
+                .collect(),
+            custom: vec![],
+            plugins: vec![],
+            scripts: vec![],
+            options: LintOptions::default(),
+            profile: None,
+            suppressions_allowed: true,
+        }
+    }
+
+    /// Lints from a [`LintRegistry`], in place of the built-in ones given to
+    /// [`LintConfiguration::new`]
+    #[must_use]
+    pub fn with_registered_lints(
+        mut self,
+        severities: Vec<(Arc<dyn Lint>, Severity)>,
+    ) -> LintConfiguration {
+        self.severities = severities;
+        self
+    }
+
+    #[must_use]
+    pub fn registered_lints(&self) -> &[(Arc<dyn Lint>, Severity)] {
+        &self.severities
+    }
+
+    /// Lints defined in config, which run after the registered ones
+    #[must_use]
+    pub fn with_custom_lints(mut self, custom: Vec<(CustomLint, Severity)>) -> LintConfiguration {
+        self.custom = custom;
+        self
+    }
+
+    #[must_use]
+    pub fn custom_lints(&self) -> &[(CustomLint, Severity)] {
+        &self.custom
+    }
+
+    /// Lints that run a command, which run after all the others
+    #[must_use]
+    pub fn with_plugins(mut self, plugins: Vec<(Plugin, Severity)>) -> LintConfiguration {
+        self.plugins = plugins;
+        self
+    }
+
+    #[must_use]
+    pub fn plugins(&self) -> &[(Plugin, Severity)] {
+        &self.plugins
+    }
+
+    /// Lints written as scripts, which run after the custom lints
+    #[must_use]
+    pub fn with_scripts(mut self, scripts: Vec<(Script, Severity)>) -> LintConfiguration {
+        self.scripts = scripts;
+        self
+    }
+
+    #[must_use]
+    pub fn scripts(&self) -> &[(Script, Severity)] {
+        &self.scripts
+    }
+
+    #[must_use]
+    pub fn with_options(mut self, options: LintOptions) -> LintConfiguration {
+        self.options = options;
+        self
+    }
+
+    #[must_use]
+    pub fn options(&self) -> &LintOptions {
+        &self.options
+    }
+
+    /// The profile the severities and options started from
+    #[must_use]
+    pub fn with_profile(mut self, profile: Option<Profile>) -> LintConfiguration {
+        self.profile = profile;
+        self
+    }
+
+    #[must_use]
+    pub fn profile(&self) -> Option<&Profile> {
+        self.profile.as_ref()
+    }
+
+    /// Whether commit messages may skip lints with a `Lint-Skip` trailer or
+    /// a `pb-lint-disable` comment
+    #[must_use]
+    pub fn with_suppressions_allowed(mut self, allowed: bool) -> LintConfiguration {
+        self.suppressions_allowed = allowed;
+        self
+    }
+
+    #[must_use]
+    pub fn suppressions_allowed(&self) -> bool {
+        self.suppressions_allowed
+    }
+
+    /// The names of the lints that would have run, but this commit message
+    /// skips
+    #[must_use]
+    pub fn suppressed(&self, commit_message: &CommitMessage) -> Vec<String> {
+        if !self.suppressions_allowed {
+            return vec![];
+        }
+
+        let named = get_suppressed_names(commit_message);
+
+        self.names()
+            .into_iter()
+            .filter(|name| named.contains(name))
+            .collect()
+    }
+
+    /// The names of every lint that isn't turned off, custom lints, scripts
+    /// and plugins included
+    #[must_use]
+    pub fn names(&self) -> Vec<String> {
+        let custom = self
+            .custom
+            .iter()
+            .filter(|(_, severity)| severity != &Severity::Off)
+            .map(|(lint, _)| lint.name().to_string());
+        let scripts = self
+            .scripts
+            .iter()
+            .filter(|(_, severity)| severity != &Severity::Off)
+            .map(|(script, _)| script.name().to_string());
+        let plugins = self
+            .plugins
+            .iter()
+            .filter(|(_, severity)| severity != &Severity::Off)
+            .map(|(plugin, _)| plugin.name().to_string());
+
+        self.severities
+            .iter()
+            .filter(|(_, severity)| severity != &Severity::Off)
+            .map(|(lint, _)| lint.name().to_string())
+            .chain(custom)
+            .chain(scripts)
+            .chain(plugins)
+            .collect()
+    }
+
+    /// The built-in lints that aren't turned off
+    #[must_use]
+    pub fn lints(&self) -> Vec<Lints> {
+        self.severities
+            .iter()
+            .filter(|(_, severity)| severity != &Severity::Off)
+            .filter_map(|(lint, _)| Lints::try_from(lint.name()).ok())
+            .collect()
+    }
+
+    /// Registered lints that haven't been given a severity are off
+    #[must_use]
+    pub fn severity<T: Into<String>>(&self, lint: T) -> Severity {
+        let name = lint.into();
+
+        self.severities
+            .iter()
+            .find(|(configured, _)| configured.name() == name)
+            .map_or(Severity::Off, |(_, severity)| *severity)
+    }
+}
+
+impl From<Vec<Lints>> for LintConfiguration {
+    fn from(lints: Vec<Lints>) -> Self {
+        LintConfiguration::new(
+            lints
+                .into_iter()
+                .map(|lint| (lint, Severity::Error))
+                .collect(),
+        )
+    }
+}
+
+/// Get the severity and options of every built-in lint, and whether commit
+/// messages may skip them
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or a severity
+/// or option isn't valid
+pub fn get_lint_configuration(
+    config: &dyn Vcs,
+) -> Result<LintConfiguration, PbCommitMessageLintsError> {
+    get_registered_lint_configuration(config, &LintRegistry::new())
+}
+
+/// Get the severity and options of every lint in the registry, and whether
+/// commit messages may skip them
 ///
 /// # Errors
 ///
-/// If there's an error reading from the configuration source
-pub fn get_lint_configuration(config: &dyn Vcs) -> Result<Vec<Lints>, PbCommitMessageLintsError> {
-    Ok(vec![
-        get_config_or_default(config, Lints::DuplicatedTrailers, true)?,
-        get_config_or_default(config, Lints::PivotalTrackerIdMissing, false)?,
-        get_config_or_default(config, Lints::JiraIssueKeyMissing, false)?,
-    ]
-    .into_iter()
-    .flatten()
-    .collect())
+/// If there's an error reading from the configuration source, a severity or
+/// option isn't valid, or two lints have the same name
+pub fn get_registered_lint_configuration(
+    config: &dyn Vcs,
+    registry: &LintRegistry,
+) -> Result<LintConfiguration, PbCommitMessageLintsError> {
+    let suppressions_allowed = config.get_bool(CONFIG_ALLOW_SUPPRESSIONS)?.unwrap_or(true);
+    let profile = get_selected_profile(config)?;
+    let custom_lints = get_custom_lints(config)?;
+    let scripts = get_scripts(config)?;
+    let plugins = get_plugins(config)?;
+
+    check_names_are_unique(
+        registry,
+        custom_lints
+            .iter()
+            .map(CustomLint::name)
+            .chain(scripts.iter().map(Script::name))
+            .chain(plugins.iter().map(Plugin::name)),
+    )?;
+
+    if let Some(profile) = &profile {
+        check_profile(profile, config, registry)?;
+    }
+
+    let default = |name: &str, default: Severity| {
+        profile
+            .as_ref()
+            .and_then(|profile| profile.severity(name))
+            .unwrap_or(default)
+    };
+
+    Ok(LintConfiguration::new(vec![])
+        .with_registered_lints(
+            registry
+                .lints()
+                .iter()
+                .map(|lint| {
+                    get_config_or_default(
+                        config,
+                        &lint.config_key(),
+                        default(lint.name(), lint.default_severity()),
+                    )
+                    .map(|severity| (Arc::clone(lint), severity))
+                })
+                .collect::<Result<_, _>>()?,
+        )
+        .with_custom_lints(
+            custom_lints
+                .into_iter()
+                .map(|lint| {
+                    get_config_or_default(
+                        config,
+                        &lint.config_key(),
+                        default(lint.name(), Severity::Error),
+                    )
+                    .map(|severity| (lint, severity))
+                })
+                .collect::<Result<_, _>>()?,
+        )
+        .with_scripts(
+            scripts
+                .into_iter()
+                .map(|script| {
+                    get_config_or_default(
+                        config,
+                        &script.config_key(),
+                        default(script.name(), Severity::Error),
+                    )
+                    .map(|severity| (script, severity))
+                })
+                .collect::<Result<_, _>>()?,
+        )
+        .with_plugins(
+            plugins
+                .into_iter()
+                .map(|plugin| {
+                    get_config_or_default(
+                        config,
+                        &plugin.config_key(),
+                        default(plugin.name(), Severity::Error),
+                    )
+                    .map(|severity| (plugin, severity))
+                })
+                .collect::<Result<_, _>>()?,
+        )
+        .with_options(get_lint_options(config, registry, profile.as_ref())?)
+        .with_profile(profile)
+        .with_suppressions_allowed(suppressions_allowed))
 }
 
+/// Every kind of lint keeps its severity under `pb.lint.<name>`, so lints that
+/// shared a name would share a severity and a `Lint-Skip` entry too
+fn check_names_are_unique<'a>(
+    registry: &LintRegistry,
+    names: impl Iterator<Item = &'a str>,
+) -> Result<(), PbCommitMessageLintsError> {
+    let mut seen = HashSet::new();
+
+    for name in names {
+        if registry.get(name).is_some() || !seen.insert(name) {
+            return Err(PbCommitMessageLintsError::LintAlreadyRegisteredError(
+                name.into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Where the severity of the lint with this name is kept, for built-in and
+/// custom lints alike
+#[must_use]
+pub fn lint_config_key(name: &str) -> String {
+    format!("pb.lint.{}", name)
+}
+
+/// Severities can also be given as booleans, which is how they used to be
+/// configured, where `true` is an error and `false` is off
 fn get_config_or_default(
     config: &dyn Vcs,
-    lint: Lints,
-    default: bool,
-) -> Result<Option<Lints>, PbCommitMessageLintsError> {
-    Ok(config
-        .get_bool(&lint.config_key())?
-        .or(Some(default))
-        .filter(|lint_value| lint_value == &true)
-        .map(|_| lint))
+    key: &str,
+    default: Severity,
+) -> Result<Severity, PbCommitMessageLintsError> {
+    let key = key.to_string();
+
+    match config.get_str(&key)? {
+        None => Ok(default),
+        Some(value) => Severity::try_from(value).or_else(|_| {
+            config
+                .get_bool(&key)
+                .map(|enabled| match enabled {
+                    Some(true) => Severity::Error,
+                    Some(false) | None => Severity::Off,
+                })
+                .map_err(|_| {
+                    PbCommitMessageLintsError::InvalidConfigurationValue(key, value.into())
+                })
+        }),
+    }
 }
 
 #[cfg(test)]
@@ -214,7 +637,8 @@ mod tests_lints {
 
     use pretty_assertions::assert_eq;
 
-    use crate::lints::{Lints, Lints::PivotalTrackerIdMissing};
+    use crate::lints::{LintCode, Lints, Lints::PivotalTrackerIdMissing, Severity};
+    use std::convert::TryFrom;
 
     #[test]
     fn it_is_convertible_to_string() {
@@ -228,6 +652,38 @@ mod tests_lints {
         assert_eq!(PivotalTrackerIdMissing, lint)
     }
 
+    #[test]
+    fn it_can_be_found_from_its_code() {
+        assert_eq!(
+            Ok(PivotalTrackerIdMissing),
+            Lints::try_from(LintCode::PivotalTrackerIdMissing)
+        )
+    }
+
+    #[test]
+    fn custom_lints_share_a_code() {
+        assert!(Lints::try_from(LintCode::CustomLint).is_err())
+    }
+
+    #[test]
+    fn only_duplicated_trailers_is_on_by_default() {
+        assert_eq!(
+            vec![
+                Severity::Error,
+                Severity::Off,
+                Severity::Off,
+                Severity::Off,
+                Severity::Off,
+                Severity::Off,
+                Severity::Off,
+                Severity::Off
+            ],
+            Lints::iterator()
+                .map(Lints::default_severity)
+                .collect::<Vec<_>>()
+        )
+    }
+
     #[test]
     fn it_is_printable() {
         assert_eq!(
@@ -237,6 +693,36 @@ mod tests_lints {
     }
 }
 
+mod commit_message;
+
+mod conventional_commits;
+
+mod custom;
+
+mod plugin;
+
+mod profile;
+
+mod script;
+
+mod documentation;
+
+mod fix;
+
+mod location;
+
+mod options;
+
+mod ref_filter;
+
+mod subject;
+
+mod registry;
+
+mod severity;
+
+mod suppression;
+
 mod missing_pivotal_tracker_id;
 
 mod duplicate_trailers;
@@ -256,6 +742,7 @@ mod tests_get_lint_configuration {
             get_lint_configuration,
             Lints,
             Lints::{DuplicatedTrailers, JiraIssueKeyMissing, PivotalTrackerIdMissing},
+            Severity,
         },
     };
 
@@ -264,7 +751,7 @@ mod tests_get_lint_configuration {
         let mut strings = HashMap::new();
         let config = InMemory::new(&mut strings);
 
-        let actual = get_lint_configuration(&config);
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
         let expected = Ok(vec![DuplicatedTrailers]);
 
         assert_eq!(
@@ -280,7 +767,7 @@ mod tests_get_lint_configuration {
         strings.insert("pb.lint.duplicated-trailers".into(), "false".into());
         let config = InMemory::new(&mut strings);
 
-        let actual = get_lint_configuration(&config);
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
         let expected: Result<Vec<Lints>, PbCommitMessageLintsError> = Ok(vec![]);
 
         assert_eq!(
@@ -296,7 +783,7 @@ mod tests_get_lint_configuration {
         strings.insert("pb.lint.duplicated-trailers".into(), "true".into());
         let config = InMemory::new(&mut strings);
 
-        let actual = get_lint_configuration(&config);
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
         let expected: Result<Vec<Lints>, PbCommitMessageLintsError> = Ok(vec![DuplicatedTrailers]);
 
         assert_eq!(
@@ -312,7 +799,7 @@ mod tests_get_lint_configuration {
         strings.insert("pb.lint.pivotal-tracker-id-missing".into(), "true".into());
         let config = InMemory::new(&mut strings);
 
-        let actual = get_lint_configuration(&config);
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
         let expected: Result<Vec<Lints>, PbCommitMessageLintsError> =
             Ok(vec![DuplicatedTrailers, PivotalTrackerIdMissing]);
 
@@ -329,7 +816,7 @@ mod tests_get_lint_configuration {
         strings.insert("pb.lint.jira-issue-key-missing".into(), "true".into());
         let config = InMemory::new(&mut strings);
 
-        let actual = get_lint_configuration(&config);
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
         let expected: Result<Vec<Lints>, PbCommitMessageLintsError> =
             Ok(vec![DuplicatedTrailers, JiraIssueKeyMissing]);
 
@@ -340,13 +827,60 @@ mod tests_get_lint_configuration {
         )
     }
 
+    #[test]
+    fn lints_can_be_given_a_severity() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.duplicated-trailers".into(), "warn".into());
+        strings.insert("pb.lint.jira-issue-key-missing".into(), "error".into());
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_lint_configuration(&config).unwrap();
+
+        assert_eq!(Severity::Warning, actual.severity(DuplicatedTrailers));
+        assert_eq!(Severity::Error, actual.severity(JiraIssueKeyMissing));
+        assert_eq!(Severity::Off, actual.severity(PivotalTrackerIdMissing));
+        assert_eq!(
+            vec![DuplicatedTrailers, JiraIssueKeyMissing],
+            actual.lints()
+        );
+    }
+
+    #[test]
+    fn lints_can_be_turned_off() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.duplicated-trailers".into(), "off".into());
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
+        let expected: Result<Vec<Lints>, PbCommitMessageLintsError> = Ok(vec![]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn unknown_severities_are_an_error() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.duplicated-trailers".into(), "banana".into());
+        let config = InMemory::new(&mut strings);
+
+        let actual = get_lint_configuration(&config);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::InvalidConfigurationValue(
+                "pb.lint.duplicated-trailers".into(),
+                "banana".into()
+            )),
+            actual
+        );
+    }
+
     #[test]
     fn disabled_jira_issue_key_missing() {
         let mut strings = HashMap::new();
         strings.insert("pb.lint.jira-issue-key-missing".into(), "false".into());
         let config = InMemory::new(&mut strings);
 
-        let actual = get_lint_configuration(&config);
+        let actual = get_lint_configuration(&config).map(|configuration| configuration.lints());
         let expected = Ok(vec![DuplicatedTrailers]);
 
         assert_eq!(
@@ -357,6 +891,60 @@ mod tests_get_lint_configuration {
     }
 }
 
+#[cfg(test)]
+mod tests_lint_suppressions {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        external::vcs::InMemory,
+        lints::{get_lint_configuration, lint, CommitMessage, Lints::JiraIssueKeyMissing},
+    };
+
+    const SKIPPED: &str = "Release 1.0.0\n\nLint-Skip: jira-issue-key-missing\n";
+
+    #[test]
+    fn skipped_lints_do_not_run() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.jira-issue-key-missing".into(), "true".into());
+        let config = InMemory::new(&mut strings);
+        let configuration = get_lint_configuration(&config).unwrap();
+        let commit = CommitMessage::new(SKIPPED.into());
+
+        assert_eq!(
+            vec![JiraIssueKeyMissing.name().to_string()],
+            configuration.suppressed(&commit)
+        );
+        assert!(lint(&commit, &configuration).is_empty());
+    }
+
+    #[test]
+    fn lints_that_are_off_are_not_reported_as_skipped() {
+        let mut strings = HashMap::new();
+        let config = InMemory::new(&mut strings);
+        let configuration = get_lint_configuration(&config).unwrap();
+
+        assert!(configuration
+            .suppressed(&CommitMessage::new(SKIPPED.into()))
+            .is_empty());
+    }
+
+    #[test]
+    fn skipping_lints_can_be_forbidden() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.jira-issue-key-missing".into(), "true".into());
+        strings.insert("pb.allow-lint-skip".into(), "false".into());
+        let config = InMemory::new(&mut strings);
+        let configuration = get_lint_configuration(&config).unwrap();
+        let commit = CommitMessage::new(SKIPPED.into());
+
+        assert!(!configuration.suppressions_allowed());
+        assert!(configuration.suppressed(&commit).is_empty());
+        assert_eq!(1, lint(&commit, &configuration).len());
+    }
+}
+
 #[cfg(test)]
 mod tests_can_enable_lints_via_a_command {
     use std::collections::HashMap;
@@ -365,9 +953,24 @@ mod tests_can_enable_lints_via_a_command {
 
     use crate::{
         external::vcs::InMemory,
-        lints::{set_lint_status, Lints::PivotalTrackerIdMissing},
+        lints::{set_lint_severity, set_lint_status, Lints::PivotalTrackerIdMissing, Severity},
     };
 
+    #[test]
+    fn we_can_set_the_severity_of_lints() {
+        let mut strings = HashMap::new();
+        let mut config = InMemory::new(&mut strings);
+
+        set_lint_severity(&[PivotalTrackerIdMissing], &mut config, Severity::Warning).unwrap();
+
+        let expected = "warn".to_string();
+        let actual = strings
+            .get("pb.lint.pivotal-tracker-id-missing")
+            .unwrap()
+            .clone();
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn we_can_enable_lints() {
         let mut strings = HashMap::new();
@@ -401,25 +1004,73 @@ mod tests_can_enable_lints_via_a_command {
     }
 }
 
+/// The lints can be built-in ones, or the names of custom lints
+///
 /// # Errors
 ///
 /// Errors if writing to the VCS config fails
-pub fn set_lint_status(
-    lints: &[Lints],
+pub fn set_lint_status<T: Clone + Into<String>>(
+    lints: &[T],
     vcs: &mut dyn Vcs,
     status: bool,
 ) -> Result<(), PbCommitMessageLintsError> {
-    lints
-        .iter()
-        .try_for_each(|lint| vcs.set_str(&lint.config_key(), &status.to_string()))?;
+    lints.iter().try_for_each(|lint| {
+        vcs.set_str(&lint_config_key(&lint.clone().into()), &status.to_string())
+    })?;
     Ok(())
 }
 
+/// The lints can be built-in ones, or the names of custom lints
+///
+/// # Errors
+///
+/// Errors if writing to the VCS config fails
+pub fn set_lint_severity<T: Clone + Into<String>>(
+    lints: &[T],
+    vcs: &mut dyn Vcs,
+    severity: Severity,
+) -> Result<(), PbCommitMessageLintsError> {
+    lints.iter().try_for_each(|lint| {
+        vcs.set_str(&lint_config_key(&lint.clone().into()), severity.name())
+    })?;
+    Ok(())
+}
+
+/// Run every lint that isn't off or skipped by the commit message, and give
+/// any problems the severity of the lint that found them
 #[must_use]
-pub fn lint(commit_message: &CommitMessage, lints: Vec<Lints>) -> Vec<LintProblem> {
-    lints
-        .into_iter()
-        .flat_map(|lint| lint.lint(commit_message))
+pub fn lint(commit_message: &CommitMessage, configuration: &LintConfiguration) -> Vec<LintProblem> {
+    let suppressed = configuration.suppressed(commit_message);
+
+    let custom = configuration
+        .custom_lints()
+        .iter()
+        .filter(|(_, severity)| severity != &Severity::Off)
+        .filter(|(lint, _)| !suppressed.iter().any(|name| name == lint.name()))
+        .flat_map(|(lint, severity)| {
+            lint.lint(commit_message)
+                .map(|problem| problem.with_severity(*severity))
+        });
+
+    configuration
+        .registered_lints()
+        .iter()
+        .filter(|(_, severity)| severity != &Severity::Off)
+        .filter(|(lint, _)| !suppressed.iter().any(|name| name == lint.name()))
+        .flat_map(|(lint, severity)| {
+            lint.check(commit_message, configuration.options())
+                .into_iter()
+                .map(move |problem| {
+                    let problem = problem.with_severity(*severity);
+
+                    if problem.lint_name() == lint.name() {
+                        problem
+                    } else {
+                        problem.with_lint_name(lint.name())
+                    }
+                })
+        })
+        .chain(custom)
         .collect::<Vec<LintProblem>>()
 }
 
@@ -427,18 +1078,87 @@ pub fn lint(commit_message: &CommitMessage, lints: Vec<Lints>) -> Vec<LintProble
 pub struct LintProblem {
     help: String,
     code: LintCode,
+    lint: Option<String>,
+    labels: Vec<Label>,
+    severity: Severity,
+    fix: Option<Fix>,
 }
 
 impl LintProblem {
     #[must_use]
     pub fn new(help: String, code: LintCode) -> LintProblem {
-        LintProblem { help, code }
+        LintProblem {
+            help,
+            code,
+            lint: None,
+            labels: vec![],
+            severity: Severity::Error,
+            fix: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> LintProblem {
+        self.severity = severity;
+        self
     }
 
     #[must_use]
-    pub fn code(self) -> LintCode {
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Point at a part of the commit message this problem is about
+    #[must_use]
+    pub fn with_label(mut self, label: Label) -> LintProblem {
+        self.labels.push(label);
+        self
+    }
+
+    #[must_use]
+    pub fn code(&self) -> LintCode {
         self.code
     }
+
+    /// Name the lint that found this problem, for lints that share a code
+    #[must_use]
+    pub fn with_lint_name(mut self, name: &str) -> LintProblem {
+        self.lint = Some(name.into());
+        self
+    }
+
+    /// The name of the lint that found this problem
+    #[must_use]
+    pub fn lint_name(&self) -> &str {
+        match &self.lint {
+            Some(name) => name,
+            None => Lints::try_from(self.code)
+                .map(Lints::name)
+                .unwrap_or_default(),
+        }
+    }
+
+    #[must_use]
+    pub fn help(&self) -> &str {
+        &self.help
+    }
+
+    #[must_use]
+    pub fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    /// Suggest a change to the commit message that would resolve this problem
+    #[must_use]
+    pub fn with_fix(mut self, fix: Fix) -> LintProblem {
+        self.fix = Some(fix);
+        self
+    }
+
+    #[must_use]
+    pub fn fix(&self) -> Option<&Fix> {
+        self.fix.as_ref()
+    }
 }
 
 impl Display for LintProblem {
@@ -453,6 +1173,14 @@ pub enum LintCode {
     DuplicatedTrailers = 3,
     PivotalTrackerIdMissing,
     JiraIssueKeyMissing,
+    CustomLint,
+    Plugin,
+    Script,
+    ConventionalCommits,
+    SubjectTooLong,
+    SubjectEndsWithPeriod,
+    SubjectNotCapitalised,
+    SubjectExtraWhitespace,
 }
 
 impl Lints {
@@ -462,6 +1190,11 @@ impl Lints {
             DuplicatedTrailers => CONFIG_DUPLICATED_TRAILERS,
             PivotalTrackerIdMissing => CONFIG_PIVOTAL_TRACKER_ID_MISSING,
             JiraIssueKeyMissing => CONFIG_JIRA_ISSUE_KEY_MISSING,
+            ConventionalCommits => CONFIG_CONVENTIONAL_COMMITS,
+            SubjectTooLong => CONFIG_SUBJECT_TOO_LONG,
+            SubjectEndsWithPeriod => CONFIG_SUBJECT_ENDS_WITH_PERIOD,
+            SubjectNotCapitalised => CONFIG_SUBJECT_NOT_CAPITALISED,
+            SubjectExtraWhitespace => CONFIG_SUBJECT_EXTRA_WHITESPACE,
         }
     }
 }
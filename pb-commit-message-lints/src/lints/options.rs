@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::vcs::Vcs,
+    lints::{Lint, LintRegistry, Profile},
+};
+
+/// The type of value an option takes, and what makes it valid
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum OptionKind {
+    /// Words separated by spaces, each of which has to match the pattern
+    List {
+        pattern: &'static str,
+        expected: &'static str,
+    },
+    /// A whole number, from the minimum up to the maximum
+    Number { min: usize, max: usize },
+}
+
+/// A parsed and validated option value
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum OptionValue {
+    List(Vec<String>),
+    Number(usize),
+}
+
+impl OptionValue {
+    /// # Panics
+    ///
+    /// If the option isn't a list
+    #[must_use]
+    pub fn as_list(&self) -> &[String] {
+        match self {
+            OptionValue::List(items) => items,
+            OptionValue::Number(_) => panic!("Options should be read as the kind they are"),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// If the option isn't a number
+    #[must_use]
+    pub fn as_number(&self) -> usize {
+        match self {
+            OptionValue::Number(number) => *number,
+            OptionValue::List(_) => panic!("Options should be read as the kind they are"),
+        }
+    }
+}
+
+/// A setting that changes how a lint behaves, kept in
+/// `pb.lint.<lint>.<option>`
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct LintOption {
+    name: &'static str,
+    description: &'static str,
+    kind: OptionKind,
+    default: &'static str,
+}
+
+impl LintOption {
+    #[must_use]
+    pub fn new(
+        name: &'static str,
+        description: &'static str,
+        kind: OptionKind,
+        default: &'static str,
+    ) -> LintOption {
+        LintOption {
+            name,
+            description,
+            kind,
+            default,
+        }
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[must_use]
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> OptionKind {
+        self.kind
+    }
+
+    /// The value as it would be written in config
+    #[must_use]
+    pub fn default(&self) -> &'static str {
+        self.default
+    }
+
+    /// The value the lint uses when the option isn't configured
+    ///
+    /// # Panics
+    ///
+    /// If the default isn't a valid value for the option
+    #[must_use]
+    pub fn default_value(&self) -> OptionValue {
+        self.parse(self.default)
+            .expect("The default value of an option should be valid")
+    }
+
+    /// Check a value as it would be written in config
+    ///
+    /// # Errors
+    ///
+    /// A description of what the value should have looked like
+    ///
+    /// # Panics
+    ///
+    /// If the pattern for a list option isn't a valid regular expression
+    pub fn parse(&self, value: &str) -> Result<OptionValue, String> {
+        match self.kind {
+            OptionKind::List { pattern, expected } => {
+                let re = Regex::new(pattern).unwrap();
+                let items = value
+                    .split_whitespace()
+                    .map(String::from)
+                    .collect::<Vec<_>>();
+
+                match items.iter().find(|item| !re.is_match(item)) {
+                    Some(item) => Err(format!("\"{}\" is not {}", item, expected)),
+                    None => Ok(OptionValue::List(items)),
+                }
+            },
+            OptionKind::Number { min, max } => value
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|number| (min..=max).contains(number))
+                .map(OptionValue::Number)
+                .ok_or_else(|| format!("\"{}\" is not a number from {} to {}", value, min, max)),
+        }
+    }
+}
+
+/// The options that have been configured for any lint
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct LintOptions {
+    values: HashMap<String, OptionValue>,
+}
+
+impl LintOptions {
+    #[must_use]
+    pub fn new(values: HashMap<String, OptionValue>) -> LintOptions {
+        LintOptions { values }
+    }
+
+    /// The value of an option, or its default if it hasn't been configured
+    ///
+    /// # Panics
+    ///
+    /// If the lint has no option with this name
+    #[must_use]
+    pub fn get(&self, lint: &dyn Lint, name: &str) -> OptionValue {
+        let key = lint.option_key(name);
+
+        self.values.get(&key).cloned().unwrap_or_else(|| {
+            lint.option(name)
+                .expect("Lints should only read their own options")
+                .default_value()
+        })
+    }
+}
+
+/// Read the options of every registered lint, checking each one is valid
+///
+/// Options the profile sets are used when they aren't configured
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, a value isn't
+/// valid, or the profile sets an option a lint doesn't have
+pub fn get_lint_options(
+    config: &dyn Vcs,
+    registry: &LintRegistry,
+    profile: Option<&Profile>,
+) -> Result<LintOptions, PbCommitMessageLintsError> {
+    let mut values = HashMap::new();
+
+    if let Some(profile) = profile {
+        for (lint, name, value) in profile.options() {
+            let lint = registry.get(lint).ok_or_else(|| {
+                PbCommitMessageLintsError::LintOptionNotFoundError(lint.clone(), name.clone())
+            })?;
+            let key = lint.option_key(name);
+            let parsed = lint.option(name)?.parse(value).map_err(|reason| {
+                PbCommitMessageLintsError::InvalidLintOption(key.clone(), value.clone(), reason)
+            })?;
+
+            values.insert(key, parsed);
+        }
+    }
+
+    for lint in registry.lints() {
+        for option in lint.options() {
+            let key = lint.option_key(option.name());
+
+            if let Some(value) = config.get_str(&key)? {
+                let parsed = option.parse(value).map_err(|reason| {
+                    PbCommitMessageLintsError::InvalidLintOption(key.clone(), value.into(), reason)
+                })?;
+
+                values.insert(key, parsed);
+            }
+        }
+    }
+
+    Ok(LintOptions::new(values))
+}
+
+/// Check the value is valid for the option, then write it to config
+///
+/// # Errors
+///
+/// If the lint has no option with this name, the value isn't valid, or
+/// writing to the VCS config fails
+pub fn set_lint_option(
+    lint: &dyn Lint,
+    name: &str,
+    value: &str,
+    vcs: &mut dyn Vcs,
+) -> Result<(), PbCommitMessageLintsError> {
+    let key = lint.option_key(name);
+    let option = lint.option(name)?;
+
+    option.parse(value).map_err(|reason| {
+        PbCommitMessageLintsError::InvalidLintOption(key.clone(), value.into(), reason)
+    })?;
+
+    vcs.set_str(&key, value)
+}
+
+#[cfg(test)]
+mod tests_lint_option {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{LintOption, OptionKind, OptionValue};
+
+    const OPTION: LintOption = LintOption {
+        name: "trailers",
+        description: "The trailers to check",
+        kind: OptionKind::List {
+            pattern: r"^[A-Za-z0-9-]+$",
+            expected: "a trailer key",
+        },
+        default: "Signed-off-by Co-authored-by",
+    };
+
+    #[test]
+    fn lists_are_separated_by_spaces() {
+        assert_eq!(
+            Ok(OptionValue::List(vec![
+                "Reviewed-by".into(),
+                "Acked-by".into()
+            ])),
+            OPTION.parse(" Reviewed-by  Acked-by ")
+        );
+    }
+
+    #[test]
+    fn every_item_in_a_list_is_checked() {
+        assert_eq!(
+            Err("\"Acked:by\" is not a trailer key".to_string()),
+            OPTION.parse("Reviewed-by Acked:by")
+        );
+    }
+
+    #[test]
+    fn numbers_have_to_be_in_range() {
+        let option = LintOption::new(
+            "max-length",
+            "The longest the subject can be",
+            OptionKind::Number { min: 1, max: 72 },
+            "50",
+        );
+
+        assert_eq!(Ok(OptionValue::Number(60)), option.parse(" 60 "));
+        assert_eq!(
+            Err("\"80\" is not a number from 1 to 72".to_string()),
+            option.parse("80")
+        );
+        assert_eq!(
+            Err("\"long\" is not a number from 1 to 72".to_string()),
+            option.parse("long")
+        );
+    }
+
+    #[test]
+    fn the_default_is_used_when_it_is_not_configured() {
+        assert_eq!(
+            OptionValue::List(vec!["Signed-off-by".into(), "Co-authored-by".into()]),
+            OPTION.default_value()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_get_lint_options {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{get_lint_options, set_lint_option, LintRegistry, Lints, OptionValue},
+    };
+
+    #[test]
+    fn unconfigured_options_have_their_default() {
+        let mut strings = HashMap::new();
+        let config = InMemory::new(&mut strings);
+
+        let options = get_lint_options(&config, &LintRegistry::new(), None).unwrap();
+
+        assert_eq!(
+            OptionValue::List(vec!["Signed-off-by".into(), "Co-authored-by".into()]),
+            options.get(&Lints::DuplicatedTrailers, "trailers")
+        );
+        assert_eq!(
+            OptionValue::List(vec![]),
+            options.get(&Lints::JiraIssueKeyMissing, "projects")
+        );
+    }
+
+    #[test]
+    fn options_are_read_from_config() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.lint.jira-issue-key-missing.projects".into(),
+            "JRA CORE".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        let options = get_lint_options(&config, &LintRegistry::new(), None).unwrap();
+
+        assert_eq!(
+            OptionValue::List(vec!["JRA".into(), "CORE".into()]),
+            options.get(&Lints::JiraIssueKeyMissing, "projects")
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_an_error() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.lint.jira-issue-key-missing.projects".into(),
+            "JRA core".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::InvalidLintOption(
+                "pb.lint.jira-issue-key-missing.projects".into(),
+                "JRA core".into(),
+                "\"core\" is not a Jira project key, like `JRA`".into()
+            )),
+            get_lint_options(&config, &LintRegistry::new(), None)
+        );
+    }
+
+    #[test]
+    fn options_are_checked_before_they_are_set() {
+        let mut strings = HashMap::new();
+        let mut config = InMemory::new(&mut strings);
+
+        assert!(set_lint_option(
+            &Lints::DuplicatedTrailers,
+            "trailers",
+            "Signed-off-by: Someone",
+            &mut config
+        )
+        .is_err());
+        assert_eq!(
+            Err(PbCommitMessageLintsError::LintOptionNotFoundError(
+                "duplicated-trailers".into(),
+                "projects".into()
+            )),
+            set_lint_option(&Lints::DuplicatedTrailers, "projects", "JRA", &mut config)
+        );
+        set_lint_option(
+            &Lints::DuplicatedTrailers,
+            "trailers",
+            "Reviewed-by",
+            &mut config,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(&"Reviewed-by".to_string()),
+            strings.get("pb.lint.duplicated-trailers.trailers")
+        );
+    }
+}
@@ -0,0 +1,703 @@
+use std::{
+    convert::TryFrom,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use git2::Repository;
+use serde_json::{json, Value};
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::vcs::{ConfigSource, Vcs},
+    lints::{
+        custom::{check_lint_name, configured_names},
+        CommitMessage,
+        Label,
+        LabelKind,
+        LintCode,
+        LintConfiguration,
+        LintProblem,
+        Severity,
+        Span,
+    },
+};
+
+const CONFIG_PLUGIN_PREFIX: &str = "pb.plugin.";
+const CONFIG_COMMAND: &str = "command";
+const CONFIG_TIMEOUT: &str = "timeout";
+const DEFAULT_TIMEOUT_SECONDS: u64 = 10;
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Where the commit is being made, which plugins are told about
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct PluginContext {
+    branch: Option<String>,
+    repository: Option<PathBuf>,
+}
+
+impl PluginContext {
+    /// The branch and working directory of the repository the directory is
+    /// in, if it's in one
+    #[must_use]
+    pub fn discover(dir: &Path) -> PluginContext {
+        let repository = match Repository::discover(dir) {
+            Ok(repository) => repository,
+            Err(_) => return PluginContext::default(),
+        };
+        let branch = repository.find_reference("HEAD").ok().and_then(|head| {
+            head.symbolic_target()
+                .map(|target| target.trim_start_matches("refs/heads/").to_string())
+        });
+
+        PluginContext {
+            branch,
+            repository: Some(
+                repository
+                    .workdir()
+                    .unwrap_or_else(|| repository.path())
+                    .to_path_buf(),
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn with_branch(mut self, branch: &str) -> PluginContext {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_repository(mut self, repository: &Path) -> PluginContext {
+        self.repository = Some(repository.into());
+        self
+    }
+
+    /// There's no branch when `HEAD` is detached
+    #[must_use]
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    #[must_use]
+    pub fn repository(&self) -> Option<&Path> {
+        self.repository.as_deref()
+    }
+}
+
+/// A lint defined in config, under `pb.plugin.<name>`, that runs a command to
+/// check the commit message
+///
+/// The command is given the commit message as JSON on stdin, and writes the
+/// problems it finds as a JSON list on stdout
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Plugin {
+    name: String,
+    command: String,
+    timeout: Duration,
+}
+
+impl Plugin {
+    /// The command is run by the shell, so it can have arguments
+    ///
+    /// # Errors
+    ///
+    /// If the name can't be used for a lint, or there's no command
+    pub fn new(name: &str, command: &str) -> Result<Plugin, PbCommitMessageLintsError> {
+        let invalid = |reason: String| PbCommitMessageLintsError::PluginError(name.into(), reason);
+
+        check_lint_name(name).map_err(invalid)?;
+        if command.trim().is_empty() {
+            return Err(invalid("The command is empty".into()));
+        }
+
+        Ok(Plugin {
+            name: name.into(),
+            command: command.into(),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+        })
+    }
+
+    /// How long the command has to finish before it's stopped
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Plugin {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    #[must_use]
+    pub fn config_key(&self) -> String {
+        format!("pb.lint.{}", self.name)
+    }
+
+    /// The JSON document the command is given on stdin
+    #[must_use]
+    pub fn input(&self, commit_message: &CommitMessage, context: &PluginContext) -> Value {
+        let mut input = message_document(commit_message, context);
+        input["plugin"] = json!(self.name);
+
+        input
+    }
+
+    /// Problems are errors unless the plugin says otherwise, and only
+    /// warnings if the plugin has been enabled as a warning
+    ///
+    /// # Errors
+    ///
+    /// If the command can't be started, doesn't finish in time, fails, or
+    /// doesn't write out a list of problems
+    pub fn lint(
+        &self,
+        commit_message: &CommitMessage,
+        context: &PluginContext,
+        severity: Severity,
+    ) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+        let output = self.run(&self.input(commit_message, context).to_string())?;
+
+        let problems = serde_json::from_str::<Value>(&output)
+            .map_err(|error| format!("{}", error))
+            .and_then(|problems| {
+                parse_problems(
+                    &self.name,
+                    LintCode::Plugin,
+                    commit_message,
+                    &problems,
+                    severity,
+                )
+            })
+            .map_err(|reason| {
+                PbCommitMessageLintsError::PluginOutputError(self.name.clone(), reason)
+            })?;
+
+        Ok(problems)
+    }
+
+    fn run(&self, input: &str) -> Result<String, PbCommitMessageLintsError> {
+        let mut child = shell_command(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| {
+                PbCommitMessageLintsError::PluginStartError(self.name.clone(), format!("{}", error))
+            })?;
+
+        // The plugin may not read all of its input, so that can't be an error
+        let stdin = child.stdin.take();
+        let input = input.to_string();
+        let writer = thread::spawn(move || {
+            if let Some(mut stdin) = stdin {
+                let _ = stdin.write_all(input.as_bytes());
+            }
+        });
+        let stdout = read_in_background(child.stdout.take());
+        let stderr = read_in_background(child.stderr.take());
+
+        let status = self.wait(&mut child)?;
+        let _ = writer.join();
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        if status.success() {
+            Ok(stdout)
+        } else {
+            Err(PbCommitMessageLintsError::PluginCrashError(
+                self.name.clone(),
+                status.code(),
+                stderr,
+            ))
+        }
+    }
+
+    fn wait(
+        &self,
+        child: &mut std::process::Child,
+    ) -> Result<ExitStatus, PbCommitMessageLintsError> {
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            let finished = child.try_wait().map_err(|error| {
+                PbCommitMessageLintsError::PluginCrashError(
+                    self.name.clone(),
+                    None,
+                    format!("{}", error),
+                )
+            })?;
+
+            if let Some(status) = finished {
+                return Ok(status);
+            }
+
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+
+                return Err(PbCommitMessageLintsError::PluginTimeoutError(
+                    self.name.clone(),
+                    self.timeout.as_secs(),
+                ));
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// The commit message, and where it's being committed, as plugins and
+/// scripts see it
+pub(crate) fn message_document(commit_message: &CommitMessage, context: &PluginContext) -> Value {
+    json!({
+        "message": commit_message.text(),
+        "subject": commit_message.subject(),
+        "body": commit_message
+            .body_lines()
+            .iter()
+            .map(|(_, line)| *line)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "trailers": commit_message
+            .trailers()
+            .iter()
+            .map(|trailer| json!({ "key": trailer.key(), "value": trailer.value() }))
+            .collect::<Vec<_>>(),
+        "branch": context.branch(),
+        "repository": context.repository(),
+    })
+}
+
+/// Problems are errors unless they say otherwise, and only warnings if the
+/// lint has been enabled as a warning
+pub(crate) fn parse_problems(
+    lint: &str,
+    code: LintCode,
+    commit_message: &CommitMessage,
+    problems: &Value,
+    severity: Severity,
+) -> Result<Vec<LintProblem>, String> {
+    problems
+        .as_array()
+        .ok_or("Expected a list of problems")?
+        .iter()
+        .enumerate()
+        .filter_map(|(index, problem)| {
+            parse_problem(lint, code, commit_message, problem, severity)
+                .map_err(|reason| format!("Problem {} {}", index + 1, reason))
+                .transpose()
+        })
+        .collect()
+}
+
+fn parse_problem(
+    lint: &str,
+    code: LintCode,
+    commit_message: &CommitMessage,
+    problem: &Value,
+    severity: Severity,
+) -> Result<Option<LintProblem>, String> {
+    let help = problem
+        .get("help")
+        .and_then(Value::as_str)
+        .ok_or("has no help")?;
+    let reported = match problem.get("severity") {
+        None | Some(Value::Null) => Severity::Error,
+        Some(reported) => reported
+            .as_str()
+            .and_then(|name| Severity::try_from(name).ok())
+            .ok_or("has a severity that isn't error, warn or off")?,
+    };
+    let severity = match (severity, reported) {
+        (_, Severity::Off) => return Ok(None),
+        (Severity::Warning, _) => Severity::Warning,
+        (_, reported) => reported,
+    };
+    let lint_problem = LintProblem::new(help.into(), code)
+        .with_lint_name(lint)
+        .with_severity(severity);
+
+    Ok(Some(match problem.get("location") {
+        None | Some(Value::Null) => lint_problem,
+        Some(location) => lint_problem.with_label(parse_location(commit_message, location)?),
+    }))
+}
+
+/// Locations have a line, and optionally the columns and a message, with the
+/// columns defaulting to the rest of the line
+fn parse_location(commit_message: &CommitMessage, location: &Value) -> Result<Label, String> {
+    let number = |field: &str| {
+        location
+            .get(field)
+            .and_then(Value::as_u64)
+            .and_then(|number| usize::try_from(number).ok())
+            .filter(|number| *number > 0)
+    };
+    let line = number("line").ok_or("has a location without a line")?;
+    let whole_line = Span::whole_line(commit_message, line);
+    let start_column = number("start_column").unwrap_or_else(|| whole_line.start_column());
+    let end_column = number("end_column").unwrap_or_else(|| whole_line.end_column());
+
+    Ok(Label::new(
+        Span::new(line, start_column, end_column),
+        LabelKind::Problem,
+        location
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or_default(),
+    ))
+}
+
+fn shell_command(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+
+    shell
+}
+
+fn read_in_background<T: Read + Send + 'static>(pipe: Option<T>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+
+        output
+    })
+}
+
+/// Read every plugin in the config, in order of their names
+///
+/// Plugins run commands, so unlike other settings they're only read from git
+/// config, and never from the repository's config file, which anyone who can
+/// commit can change
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or one of the
+/// plugins isn't valid, which names the plugin
+pub fn get_plugins(config: &dyn Vcs) -> Result<Vec<Plugin>, PbCommitMessageLintsError> {
+    configured_names(config, CONFIG_PLUGIN_PREFIX)?
+        .iter()
+        .filter_map(|name| get_plugin(config, name).transpose())
+        .collect()
+}
+
+/// Nothing if none of the plugin's settings are in git config
+fn get_plugin(config: &dyn Vcs, name: &str) -> Result<Option<Plugin>, PbCommitMessageLintsError> {
+    let key = |setting: &str| format!("{}{}.{}", CONFIG_PLUGIN_PREFIX, name, setting);
+    let invalid = |reason: String| PbCommitMessageLintsError::PluginError(name.into(), reason);
+    let in_git_config = |setting: &str| -> Result<bool, PbCommitMessageLintsError> {
+        Ok(config
+            .get_source(&key(setting))?
+            .is_some_and(|source| source != ConfigSource::RepoFile))
+    };
+
+    let has_command = in_git_config(CONFIG_COMMAND)?;
+    let has_timeout = in_git_config(CONFIG_TIMEOUT)?;
+
+    if !has_command && !has_timeout {
+        return Ok(None);
+    }
+
+    let command = if has_command {
+        config.get_str(&key(CONFIG_COMMAND))?
+    } else {
+        None
+    };
+    let timeout = if has_timeout {
+        config.get_i64(&key(CONFIG_TIMEOUT))?
+    } else {
+        None
+    };
+
+    let mut plugin = Plugin::new(
+        name,
+        command.ok_or_else(|| {
+            invalid(format!(
+                "There's no command, set one in {}",
+                key(CONFIG_COMMAND)
+            ))
+        })?,
+    )?;
+
+    if let Some(timeout) = timeout {
+        let seconds = u64::try_from(timeout)
+            .ok()
+            .filter(|seconds| *seconds > 0)
+            .ok_or_else(|| invalid("The timeout has to be a number of seconds above 0".into()))?;
+
+        plugin = plugin.with_timeout(Duration::from_secs(seconds));
+    }
+
+    Ok(Some(plugin))
+}
+
+/// Run every plugin that isn't off or skipped by the commit message
+///
+/// # Errors
+///
+/// If any of the plugins fail, or don't write out a list of problems
+pub fn run_plugins(
+    commit_message: &CommitMessage,
+    configuration: &LintConfiguration,
+    context: &PluginContext,
+) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+    let suppressed = configuration.suppressed(commit_message);
+    let mut problems = vec![];
+
+    for (plugin, severity) in configuration.plugins() {
+        if severity == &Severity::Off || suppressed.iter().any(|name| name == plugin.name()) {
+            continue;
+        }
+
+        problems.extend(plugin.lint(commit_message, context, *severity)?);
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests_plugin {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::lints::{CommitMessage, Plugin, PluginContext};
+
+    #[test]
+    fn names_have_to_be_usable_for_lints() {
+        assert!(Plugin::new("jira-issue-key-missing", "true").is_err());
+        assert!(Plugin::new("check service", "true").is_err());
+        assert!(Plugin::new("check-service", "").is_err());
+    }
+
+    #[test]
+    fn the_input_has_the_message_trailers_and_repository() {
+        let plugin = Plugin::new("check-service", "true").unwrap();
+        let context = PluginContext::default()
+            .with_branch("main")
+            .with_repository(Path::new("/tmp/repo"));
+
+        assert_eq!(
+            json!({
+                "plugin": "check-service",
+                "message": "An example commit\n\nSigned-off-by: Billie <billie@example.com>",
+                "subject": "An example commit",
+                "body": "",
+                "trailers": [{ "key": "Signed-off-by", "value": "Billie <billie@example.com>" }],
+                "branch": "main",
+                "repository": "/tmp/repo",
+            }),
+            plugin.input(
+                &CommitMessage::new(
+                    "An example commit\n\nSigned-off-by: Billie <billie@example.com>\n".into()
+                ),
+                &context
+            )
+        );
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests_plugin_lint {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        lints::{
+            CommitMessage,
+            Label,
+            LabelKind,
+            LintCode,
+            LintProblem,
+            Plugin,
+            PluginContext,
+            Severity,
+            Span,
+        },
+    };
+
+    fn lint(
+        command: &str,
+        severity: Severity,
+    ) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+        Plugin::new("check-service", command).unwrap().lint(
+            &CommitMessage::new("An example commit\n".into()),
+            &PluginContext::default(),
+            severity,
+        )
+    }
+
+    #[test]
+    fn problems_are_read_from_stdout() {
+        let output = r#"[
+            {"help": "Mention a service"},
+            {"help": "Not a real word", "severity": "warn", "location": {"line": 1, "start_column": 4, "end_column": 11, "message": "here"}},
+            {"help": "Ignored", "severity": "off"}
+        ]"#;
+
+        assert_eq!(
+            Ok(vec![
+                LintProblem::new("Mention a service".into(), LintCode::Plugin)
+                    .with_lint_name("check-service"),
+                LintProblem::new("Not a real word".into(), LintCode::Plugin)
+                    .with_lint_name("check-service")
+                    .with_severity(Severity::Warning)
+                    .with_label(Label::new(Span::new(1, 4, 11), LabelKind::Problem, "here")),
+            ]),
+            lint(
+                &format!("cat > /dev/null; echo '{}'", output),
+                Severity::Error
+            )
+        );
+    }
+
+    #[test]
+    fn plugins_enabled_as_warnings_only_warn() {
+        assert_eq!(
+            Ok(vec![LintProblem::new(
+                "Mention a service".into(),
+                LintCode::Plugin
+            )
+            .with_lint_name("check-service")
+            .with_severity(Severity::Warning)]),
+            lint(
+                r#"echo '[{"help": "Mention a service", "severity": "error"}]'"#,
+                Severity::Warning
+            )
+        );
+    }
+
+    #[test]
+    fn the_message_is_given_on_stdin() {
+        assert_eq!(
+            Ok(vec![]),
+            lint(
+                r#"grep -q '"message":"An example commit"' && echo '[]'"#,
+                Severity::Error
+            )
+        );
+    }
+
+    #[test]
+    fn failing_plugins_are_an_error() {
+        assert_eq!(
+            Err(PbCommitMessageLintsError::PluginCrashError(
+                "check-service".into(),
+                Some(3),
+                "Something went wrong\n".into()
+            )),
+            lint("echo 'Something went wrong' >&2; exit 3", Severity::Error)
+        );
+    }
+
+    #[test]
+    fn malformed_output_is_an_error() {
+        assert!(matches!(
+            lint("echo 'Looks fine to me'", Severity::Error),
+            Err(PbCommitMessageLintsError::PluginOutputError(_, _))
+        ));
+        assert_eq!(
+            Err(PbCommitMessageLintsError::PluginOutputError(
+                "check-service".into(),
+                "Problem 1 has no help".into()
+            )),
+            lint(r#"echo '[{"severity": "warn"}]'"#, Severity::Error)
+        );
+    }
+
+    #[test]
+    fn slow_plugins_are_stopped() {
+        let plugin = Plugin::new("check-service", "exec sleep 5")
+            .unwrap()
+            .with_timeout(Duration::from_millis(100));
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::PluginTimeoutError(
+                "check-service".into(),
+                0
+            )),
+            plugin.lint(
+                &CommitMessage::new("An example commit\n".into()),
+                &PluginContext::default(),
+                Severity::Error
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_get_plugins {
+    use std::{collections::HashMap, time::Duration};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{get_plugins, Plugin},
+    };
+
+    #[test]
+    fn plugins_are_read_from_config() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.plugin.check-service.command".into(),
+            "scripts/check-service --strict".into(),
+        );
+        strings.insert("pb.plugin.check-service.timeout".into(), "30".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(vec![Plugin::new(
+                "check-service",
+                "scripts/check-service --strict"
+            )
+            .unwrap()
+            .with_timeout(Duration::from_secs(30))]),
+            get_plugins(&config)
+        );
+    }
+
+    #[test]
+    fn plugins_need_a_command() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.plugin.check-service.timeout".into(), "30".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::PluginError(
+                "check-service".into(),
+                "There's no command, set one in pb.plugin.check-service.command".into()
+            )),
+            get_plugins(&config)
+        );
+    }
+}
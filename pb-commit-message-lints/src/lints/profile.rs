@@ -0,0 +1,537 @@
+use std::convert::TryFrom;
+
+use regex::Regex;
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::vcs::Vcs,
+    lints::{
+        duplicate_trailers::OPTION_TRAILERS,
+        get_custom_lints,
+        get_plugins,
+        get_scripts,
+        lint_config_key,
+        set_lint_option,
+        LintRegistry,
+        Lints,
+        Severity,
+    },
+};
+
+const CONFIG_PROFILE: &str = "pb.lint.profile";
+const CONFIG_PROFILE_PREFIX: &str = "pb.profile.";
+const REGEX_PROFILE_NAME: &str = r"^[A-Za-z0-9-]+$";
+const PROFILE_DESCRIPTION_CONFIGURED: &str = "Defined in config";
+
+/// A named set of lint severities and options, which a repository can start
+/// from rather than enabling and configuring lints one by one
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Profile {
+    name: String,
+    description: String,
+    severities: Vec<(String, Severity)>,
+    options: Vec<(String, String, String)>,
+}
+
+impl Profile {
+    /// # Errors
+    ///
+    /// If the name has anything other than letters, numbers and dashes in it
+    ///
+    /// # Panics
+    ///
+    /// If the pattern for profile names isn't a valid regular expression
+    pub fn new(name: &str, description: &str) -> Result<Profile, PbCommitMessageLintsError> {
+        if !Regex::new(REGEX_PROFILE_NAME).unwrap().is_match(name) {
+            return Err(PbCommitMessageLintsError::ProfileError(
+                name.into(),
+                "The name can only have letters, numbers and dashes in it".into(),
+            ));
+        }
+
+        Ok(Profile {
+            name: name.into(),
+            description: description.into(),
+            severities: vec![],
+            options: vec![],
+        })
+    }
+
+    /// Give a lint a severity, replacing any it already had
+    #[must_use]
+    pub fn with_severity<T: Into<String>>(mut self, lint: T, severity: Severity) -> Profile {
+        let lint = lint.into();
+        self.severities
+            .retain(|(configured, _)| configured != &lint);
+        self.severities.push((lint, severity));
+        self
+    }
+
+    /// Give one of a lint's options a value, as it would be written in config
+    #[must_use]
+    pub fn with_option<T: Into<String>>(mut self, lint: T, option: &str, value: &str) -> Profile {
+        let lint = lint.into();
+        self.options
+            .retain(|(configured, name, _)| configured != &lint || name != option);
+        self.options.push((lint, option.into(), value.into()));
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The lints the profile sets the severity of
+    #[must_use]
+    pub fn severities(&self) -> &[(String, Severity)] {
+        &self.severities
+    }
+
+    /// The lint, option and value of each option the profile sets
+    #[must_use]
+    pub fn options(&self) -> &[(String, String, String)] {
+        &self.options
+    }
+
+    /// The severity the profile gives the lint, if it mentions it
+    #[must_use]
+    pub fn severity(&self, lint: &str) -> Option<Severity> {
+        self.severities
+            .iter()
+            .find(|(configured, _)| configured == lint)
+            .map(|(_, severity)| *severity)
+    }
+
+    /// The value the profile gives one of a lint's options, if it sets it
+    #[must_use]
+    pub fn option(&self, lint: &str, option: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(configured, name, _)| configured == lint && name == option)
+            .map(|(_, _, value)| value.as_str())
+    }
+
+    /// The profiles that come with the hooks
+    ///
+    /// # Panics
+    ///
+    /// If one of their names isn't a valid profile name
+    #[must_use]
+    pub fn built_in() -> Vec<Profile> {
+        vec![
+            Profile::new(
+                "jira-team",
+                "Require a Jira Issue Key, and check for duplicated trailers",
+            )
+            .unwrap()
+            .with_severity(Lints::DuplicatedTrailers.name(), Severity::Error)
+            .with_severity(Lints::JiraIssueKeyMissing.name(), Severity::Error)
+            .with_severity(Lints::PivotalTrackerIdMissing.name(), Severity::Off),
+            Profile::new(
+                "pivotal-team",
+                "Require a Pivotal Tracker Id, and check for duplicated trailers",
+            )
+            .unwrap()
+            .with_severity(Lints::DuplicatedTrailers.name(), Severity::Error)
+            .with_severity(Lints::PivotalTrackerIdMissing.name(), Severity::Error)
+            .with_severity(Lints::JiraIssueKeyMissing.name(), Severity::Off),
+            Profile::new(
+                "oss-dco",
+                "Check sign-offs and review trailers aren't duplicated, without an issue tracker",
+            )
+            .unwrap()
+            .with_severity(Lints::DuplicatedTrailers.name(), Severity::Error)
+            .with_option(
+                Lints::DuplicatedTrailers.name(),
+                OPTION_TRAILERS,
+                "Signed-off-by Co-authored-by Reviewed-by Acked-by",
+            )
+            .with_severity(Lints::PivotalTrackerIdMissing.name(), Severity::Off)
+            .with_severity(Lints::JiraIssueKeyMissing.name(), Severity::Off),
+            Profile::new(
+                "conventional",
+                "Require Conventional Commits, and check for duplicated trailers",
+            )
+            .unwrap()
+            .with_severity(Lints::DuplicatedTrailers.name(), Severity::Error)
+            .with_severity(Lints::ConventionalCommits.name(), Severity::Error),
+        ]
+    }
+}
+
+/// The built-in profiles, and any defined in config, where a profile in
+/// config replaces a built-in one with the same name
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or one of the
+/// profiles isn't valid, which names the profile
+pub fn get_profiles(config: &dyn Vcs) -> Result<Vec<Profile>, PbCommitMessageLintsError> {
+    let configured = get_configured_profiles(config)?;
+    let mut profiles = Profile::built_in()
+        .into_iter()
+        .filter(|profile| {
+            !configured
+                .iter()
+                .any(|configured| configured.name() == profile.name())
+        })
+        .collect::<Vec<_>>();
+    profiles.extend(configured);
+
+    Ok(profiles)
+}
+
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or there's no
+/// profile with this name
+pub fn get_profile(config: &dyn Vcs, name: &str) -> Result<Profile, PbCommitMessageLintsError> {
+    get_profiles(config)?
+        .into_iter()
+        .find(|profile| profile.name() == name)
+        .ok_or_else(|| PbCommitMessageLintsError::ProfileNotFoundError(name.into()))
+}
+
+/// The profile chosen with `pb.lint.profile`, if there is one
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or there's no
+/// profile with that name
+pub fn get_selected_profile(
+    config: &dyn Vcs,
+) -> Result<Option<Profile>, PbCommitMessageLintsError> {
+    config
+        .get_str(CONFIG_PROFILE)?
+        .map(|name| get_profile(config, name))
+        .transpose()
+}
+
+/// Profiles are kept in `pb.profile.<name>.<lint>` for severities, and
+/// `pb.profile.<name>.<lint>.<option>` for options
+fn get_configured_profiles(config: &dyn Vcs) -> Result<Vec<Profile>, PbCommitMessageLintsError> {
+    let mut keys = config.get_keys(CONFIG_PROFILE_PREFIX)?;
+    keys.sort();
+
+    let mut profiles: Vec<Profile> = vec![];
+
+    for key in keys {
+        let parts = key[CONFIG_PROFILE_PREFIX.len()..]
+            .split('.')
+            .collect::<Vec<_>>();
+        let (name, lint) = match parts.as_slice() {
+            [name, lint] | [name, lint, _] => (*name, *lint),
+            _ => {
+                return Err(PbCommitMessageLintsError::InvalidConfigurationValue(
+                    key.clone(),
+                    config.get_str(&key)?.unwrap_or_default().into(),
+                ))
+            },
+        };
+        let value = config.get_str(&key)?.unwrap_or_default();
+
+        let profile = match profiles.iter().position(|profile| profile.name() == name) {
+            Some(index) => profiles.remove(index),
+            None => Profile::new(name, PROFILE_DESCRIPTION_CONFIGURED)?,
+        };
+
+        profiles.push(match parts.get(2) {
+            Some(option) => profile.with_option(lint, option, value),
+            None => {
+                let severity = Severity::try_from(value).map_err(|_| {
+                    PbCommitMessageLintsError::InvalidConfigurationValue(key.clone(), value.into())
+                })?;
+
+                profile.with_severity(lint, severity)
+            },
+        });
+    }
+
+    profiles.sort_by(|a, b| a.name().cmp(b.name()));
+
+    Ok(profiles)
+}
+
+/// Check every lint the profile mentions exists, either in the registry or
+/// defined in config
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or the profile
+/// mentions a lint that doesn't exist
+pub(crate) fn check_profile(
+    profile: &Profile,
+    config: &dyn Vcs,
+    registry: &LintRegistry,
+) -> Result<(), PbCommitMessageLintsError> {
+    let custom = get_custom_lints(config)?;
+    let scripts = get_scripts(config)?;
+    let plugins = get_plugins(config)?;
+
+    let missing = profile
+        .severities()
+        .iter()
+        .map(|(lint, _)| lint)
+        .chain(profile.options().iter().map(|(lint, ..)| lint))
+        .find(|name| {
+            registry.get(name).is_none()
+                && !custom.iter().any(|lint| lint.name() == name.as_str())
+                && !scripts.iter().any(|script| script.name() == name.as_str())
+                && !plugins.iter().any(|plugin| plugin.name() == name.as_str())
+        });
+
+    match missing {
+        Some(name) => Err(PbCommitMessageLintsError::ProfileError(
+            profile.name().into(),
+            format!("There's no lint called \"{}\"", name),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Write every severity and option in the profile to config, so they can be
+/// changed one by one afterwards
+///
+/// # Errors
+///
+/// If the profile mentions a lint or option that doesn't exist, an option
+/// isn't valid, or writing to the VCS config fails
+pub fn apply_profile(
+    profile: &Profile,
+    registry: &LintRegistry,
+    vcs: &mut dyn Vcs,
+) -> Result<(), PbCommitMessageLintsError> {
+    check_profile(profile, vcs, registry)?;
+
+    for (lint, option, value) in profile.options() {
+        let registered = registry.get(lint).ok_or_else(|| {
+            PbCommitMessageLintsError::LintOptionNotFoundError(lint.clone(), option.clone())
+        })?;
+
+        registered.option(option)?.parse(value).map_err(|reason| {
+            PbCommitMessageLintsError::InvalidLintOption(
+                registered.option_key(option),
+                value.clone(),
+                reason,
+            )
+        })?;
+    }
+
+    for (lint, severity) in profile.severities() {
+        vcs.set_str(&lint_config_key(lint), severity.name())?;
+    }
+
+    for (lint, option, value) in profile.options() {
+        if let Some(registered) = registry.get(lint) {
+            set_lint_option(registered.as_ref(), option, value, vcs)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests_profile {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        lints::{Profile, Severity},
+    };
+
+    #[test]
+    fn later_settings_replace_earlier_ones() {
+        let profile = Profile::new("team", "")
+            .unwrap()
+            .with_severity("duplicated-trailers", Severity::Error)
+            .with_severity("duplicated-trailers", Severity::Warning)
+            .with_option("duplicated-trailers", "trailers", "Signed-off-by")
+            .with_option("duplicated-trailers", "trailers", "Reviewed-by");
+
+        assert_eq!(
+            Some(Severity::Warning),
+            profile.severity("duplicated-trailers")
+        );
+        assert_eq!(
+            Some("Reviewed-by"),
+            profile.option("duplicated-trailers", "trailers")
+        );
+        assert_eq!(1, profile.severities().len());
+        assert_eq!(1, profile.options().len());
+    }
+
+    #[test]
+    fn lints_it_does_not_mention_are_left_alone() {
+        let profile = Profile::new("team", "").unwrap();
+
+        assert_eq!(None, profile.severity("duplicated-trailers"));
+        assert_eq!(None, profile.option("duplicated-trailers", "trailers"));
+    }
+
+    #[test]
+    fn names_are_checked() {
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ProfileError(
+                "my.team".into(),
+                "The name can only have letters, numbers and dashes in it".into()
+            )),
+            Profile::new("my.team", "")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_get_profiles {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{
+            apply_profile,
+            get_profile,
+            get_profiles,
+            get_selected_profile,
+            LintRegistry,
+            Profile,
+            Severity,
+        },
+    };
+
+    #[test]
+    fn the_built_in_profiles_are_always_there() {
+        let mut strings = HashMap::new();
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            vec!["jira-team", "pivotal-team", "oss-dco", "conventional"],
+            get_profiles(&config)
+                .unwrap()
+                .iter()
+                .map(Profile::name)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn profiles_are_read_from_config() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.profile.platform.jira-issue-key-missing".into(),
+            "warn".into(),
+        );
+        strings.insert(
+            "pb.profile.platform.jira-issue-key-missing.projects".into(),
+            "PLAT OPS".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(Profile::new("platform", "Defined in config")
+                .unwrap()
+                .with_severity("jira-issue-key-missing", Severity::Warning)
+                .with_option("jira-issue-key-missing", "projects", "PLAT OPS")),
+            get_profile(&config, "platform")
+        );
+    }
+
+    #[test]
+    fn profiles_in_config_replace_built_in_ones() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.profile.jira-team.jira-issue-key-missing".into(),
+            "warn".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        let profiles = get_profiles(&config).unwrap();
+
+        assert_eq!(4, profiles.len());
+        assert_eq!(
+            Ok(Profile::new("jira-team", "Defined in config")
+                .unwrap()
+                .with_severity("jira-issue-key-missing", Severity::Warning)),
+            get_profile(&config, "jira-team")
+        );
+    }
+
+    #[test]
+    fn invalid_severities_are_an_error() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.profile.platform.jira-issue-key-missing".into(),
+            "loud".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::InvalidConfigurationValue(
+                "pb.profile.platform.jira-issue-key-missing".into(),
+                "loud".into()
+            )),
+            get_profiles(&config)
+        );
+    }
+
+    #[test]
+    fn the_selected_profile_has_to_exist() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.profile".into(), "platform".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ProfileNotFoundError(
+                "platform".into()
+            )),
+            get_selected_profile(&config)
+        );
+    }
+
+    #[test]
+    fn applying_a_profile_writes_it_to_config() {
+        let mut strings = HashMap::new();
+        let mut config = InMemory::new(&mut strings);
+        let profile = get_profile(&config, "oss-dco").unwrap();
+
+        apply_profile(&profile, &LintRegistry::new(), &mut config).unwrap();
+
+        assert_eq!(
+            Some(&"error".to_string()),
+            strings.get("pb.lint.duplicated-trailers")
+        );
+        assert_eq!(
+            Some(&"off".to_string()),
+            strings.get("pb.lint.jira-issue-key-missing")
+        );
+        assert_eq!(
+            Some(&"Signed-off-by Co-authored-by Reviewed-by Acked-by".to_string()),
+            strings.get("pb.lint.duplicated-trailers.trailers")
+        );
+    }
+
+    #[test]
+    fn profiles_can_only_mention_lints_that_exist() {
+        let mut strings = HashMap::new();
+        let mut config = InMemory::new(&mut strings);
+        let profile = Profile::new("platform", "")
+            .unwrap()
+            .with_severity("no-wip", Severity::Error);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ProfileError(
+                "platform".into(),
+                "There's no lint called \"no-wip\"".into()
+            )),
+            apply_profile(&profile, &LintRegistry::new(), &mut config)
+        );
+        assert!(strings.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use regex::Regex;
+
+use crate::{errors::PbCommitMessageLintsError, external::vcs::Vcs};
+
+const CONFIG_INCLUDE_REFS: &str = "pb.receive.include-refs";
+const CONFIG_EXCLUDE_REFS: &str = "pb.receive.exclude-refs";
+
+/// Which refs to lint the commits of, from globs like `refs/heads/*`
+///
+/// A `*` matches anything but a `/`, and a `**` matches anything at all
+#[derive(Debug, Clone, Default)]
+pub struct RefFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl RefFilter {
+    /// # Errors
+    ///
+    /// If one of the globs can't be turned into a pattern
+    pub fn new(include: &[&str], exclude: &[&str]) -> Result<RefFilter, PbCommitMessageLintsError> {
+        Ok(RefFilter {
+            include: include
+                .iter()
+                .map(|glob| glob_to_regex(CONFIG_INCLUDE_REFS, glob))
+                .collect::<Result<_, _>>()?,
+            exclude: exclude
+                .iter()
+                .map(|glob| glob_to_regex(CONFIG_EXCLUDE_REFS, glob))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Refs are linted if they match an include pattern, or there aren't
+    /// any, and don't match an exclude pattern
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|regex| regex.is_match(name)))
+            && !self.exclude.iter().any(|regex| regex.is_match(name))
+    }
+}
+
+/// Get which refs should be linted when they're pushed to this repository
+///
+/// Both settings are lists of globs separated by whitespace
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or a glob
+/// isn't valid
+pub fn get_ref_filter(config: &dyn Vcs) -> Result<RefFilter, PbCommitMessageLintsError> {
+    let include = config.get_str(CONFIG_INCLUDE_REFS)?.unwrap_or_default();
+    let exclude = config.get_str(CONFIG_EXCLUDE_REFS)?.unwrap_or_default();
+
+    RefFilter::new(
+        &include.split_whitespace().collect::<Vec<_>>(),
+        &exclude.split_whitespace().collect::<Vec<_>>(),
+    )
+}
+
+fn glob_to_regex(key: &str, glob: &str) -> Result<Regex, PbCommitMessageLintsError> {
+    let pattern = glob
+        .split("**")
+        .map(|part| {
+            part.split('*')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join("[^/]*")
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+
+    Regex::new(&format!("^{}$", pattern))
+        .map_err(|_| PbCommitMessageLintsError::InvalidConfigurationValue(key.into(), glob.into()))
+}
+
+#[cfg(test)]
+mod tests_ref_filter {
+    use crate::lints::RefFilter;
+
+    #[test]
+    fn everything_matches_by_default() {
+        assert!(RefFilter::default().matches("refs/heads/main"));
+    }
+
+    #[test]
+    fn only_included_refs_match() {
+        let filter = RefFilter::new(&["refs/heads/*"], &[]).unwrap();
+
+        assert!(filter.matches("refs/heads/main"));
+        assert!(!filter.matches("refs/heads/feature/example"));
+        assert!(!filter.matches("refs/tags/v1.0.0"));
+    }
+
+    #[test]
+    fn double_stars_match_across_slashes() {
+        let filter = RefFilter::new(&["refs/heads/**"], &[]).unwrap();
+
+        assert!(filter.matches("refs/heads/feature/example"));
+    }
+
+    #[test]
+    fn excluded_refs_never_match() {
+        let filter = RefFilter::new(&["refs/heads/**"], &["refs/heads/wip/*"]).unwrap();
+
+        assert!(filter.matches("refs/heads/main"));
+        assert!(!filter.matches("refs/heads/wip/example"));
+    }
+
+    #[test]
+    fn other_characters_are_not_patterns() {
+        let filter = RefFilter::new(&["refs/heads/v1.0"], &[]).unwrap();
+
+        assert!(filter.matches("refs/heads/v1.0"));
+        assert!(!filter.matches("refs/heads/v1x0"));
+    }
+}
+
+#[cfg(test)]
+mod tests_get_ref_filter {
+    use std::collections::HashMap;
+
+    use crate::{external::vcs::InMemory, lints::get_ref_filter};
+
+    #[test]
+    fn reads_the_globs_from_config() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.receive.include-refs".into(),
+            "refs/heads/main refs/heads/release/*".into(),
+        );
+        strings.insert(
+            "pb.receive.exclude-refs".into(),
+            "refs/heads/release/old".into(),
+        );
+        let config = InMemory::new(&mut strings);
+
+        let filter = get_ref_filter(&config).unwrap();
+
+        assert!(filter.matches("refs/heads/main"));
+        assert!(filter.matches("refs/heads/release/new"));
+        assert!(!filter.matches("refs/heads/release/old"));
+        assert!(!filter.matches("refs/heads/feature"));
+    }
+}
@@ -0,0 +1,306 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    lints::{
+        lint_config_key,
+        CommitMessage,
+        LintOption,
+        LintOptions,
+        LintProblem,
+        Lints,
+        Severity,
+    },
+};
+
+/// A check a commit message can be put through, which can be written outside
+/// of this crate and added to a [`LintRegistry`]
+///
+/// Problems found by lints from other crates should use
+/// [`LintCode::CustomLint`](crate::lints::LintCode::CustomLint); they're
+/// given the name of the lint that found them when they're run
+pub trait Lint: Debug {
+    /// What the lint is enabled, disabled and skipped by, like
+    /// `duplicated-trailers`
+    fn name(&self) -> &str;
+
+    /// What the lint checks for
+    fn description(&self) -> &str;
+
+    /// The severity the lint has when it hasn't been configured
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// The settings that change how the lint behaves
+    fn options(&self) -> Vec<LintOption> {
+        vec![]
+    }
+
+    /// Look for problems in the commit message, using the configured options
+    fn check(&self, commit_message: &CommitMessage, options: &LintOptions) -> Vec<LintProblem>;
+
+    /// Where the severity of the lint is kept
+    fn config_key(&self) -> String {
+        lint_config_key(self.name())
+    }
+
+    /// Where one of the lint's options is kept
+    fn option_key(&self, name: &str) -> String {
+        format!("{}.{}", self.config_key(), name)
+    }
+
+    /// # Errors
+    ///
+    /// If the lint has no option with this name
+    fn option(&self, name: &str) -> Result<LintOption, PbCommitMessageLintsError> {
+        self.options()
+            .into_iter()
+            .find(|option| option.name() == name)
+            .ok_or_else(|| {
+                PbCommitMessageLintsError::LintOptionNotFoundError(self.name().into(), name.into())
+            })
+    }
+}
+
+/// Lints are the same lint if they have the same name
+impl PartialEq for dyn Lint {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for dyn Lint {}
+
+impl Lint for Lints {
+    fn name(&self) -> &str {
+        Lints::name(*self)
+    }
+
+    fn description(&self) -> &str {
+        Lints::description(*self)
+    }
+
+    fn default_severity(&self) -> Severity {
+        Lints::default_severity(*self)
+    }
+
+    fn options(&self) -> Vec<LintOption> {
+        Lints::options(*self)
+    }
+
+    fn check(&self, commit_message: &CommitMessage, options: &LintOptions) -> Vec<LintProblem> {
+        self.lint(commit_message, options).into_iter().collect()
+    }
+}
+
+/// Every lint that can be configured and run, in the order they run
+#[derive(Debug, Clone)]
+pub struct LintRegistry {
+    lints: Vec<Arc<dyn Lint>>,
+}
+
+impl LintRegistry {
+    /// A registry with the built-in lints already in it
+    #[must_use]
+    pub fn new() -> LintRegistry {
+        LintRegistry {
+            lints: Lints::iterator()
+                .map(|lint| Arc::new(lint) as Arc<dyn Lint>)
+                .collect(),
+        }
+    }
+
+    /// Add a lint, which runs after the ones already registered
+    ///
+    /// # Errors
+    ///
+    /// If there's already a lint with the same name
+    pub fn register<T: Lint + 'static>(
+        &mut self,
+        lint: T,
+    ) -> Result<(), PbCommitMessageLintsError> {
+        if self.get(lint.name()).is_some() {
+            return Err(PbCommitMessageLintsError::LintAlreadyRegisteredError(
+                lint.name().into(),
+            ));
+        }
+
+        self.lints.push(Arc::new(lint));
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn Lint>> {
+        self.lints.iter().find(|lint| lint.name() == name)
+    }
+
+    #[must_use]
+    pub fn lints(&self) -> &[Arc<dyn Lint>] {
+        &self.lints
+    }
+}
+
+impl Default for LintRegistry {
+    fn default() -> Self {
+        LintRegistry::new()
+    }
+}
+
+#[cfg(test)]
+mod tests_lint_registry {
+    use std::collections::HashMap;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{
+            get_registered_lint_configuration,
+            lint,
+            CommitMessage,
+            Lint,
+            LintCode,
+            LintOptions,
+            LintProblem,
+            LintRegistry,
+            Lints,
+            Severity,
+        },
+    };
+
+    #[derive(Debug)]
+    struct NoWip;
+
+    impl Lint for NoWip {
+        fn name(&self) -> &str {
+            "no-wip"
+        }
+
+        fn description(&self) -> &str {
+            "Detect work in progress commits"
+        }
+
+        fn check(&self, commit_message: &CommitMessage, _: &LintOptions) -> Vec<LintProblem> {
+            if commit_message
+                .subject()
+                .unwrap_or_default()
+                .starts_with("WIP")
+            {
+                vec![LintProblem::new(
+                    "Finish the work before committing it".into(),
+                    LintCode::CustomLint,
+                )]
+            } else {
+                vec![]
+            }
+        }
+    }
+
+    #[test]
+    fn the_built_in_lints_are_registered() {
+        assert_eq!(
+            Lints::iterator().map(Lints::name).collect::<Vec<_>>(),
+            LintRegistry::new()
+                .lints()
+                .iter()
+                .map(|lint| lint.name())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn other_lints_run_after_the_built_in_ones() {
+        let mut registry = LintRegistry::new();
+        registry.register(NoWip).unwrap();
+
+        let lint = registry.get("no-wip").unwrap();
+
+        assert_eq!(
+            Some("no-wip"),
+            registry.lints().last().map(|lint| lint.name())
+        );
+        assert_eq!(Severity::Error, lint.default_severity());
+        assert_eq!("pb.lint.no-wip", lint.config_key());
+        assert_eq!(
+            1,
+            lint.check(
+                &CommitMessage::new("WIP An example commit\n".into()),
+                &LintOptions::default()
+            )
+            .len()
+        );
+    }
+
+    #[test]
+    fn names_can_only_be_registered_once() {
+        let mut registry = LintRegistry::new();
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::LintAlreadyRegisteredError(
+                "duplicated-trailers".into()
+            )),
+            registry.register(Lints::DuplicatedTrailers)
+        );
+    }
+
+    #[test]
+    fn registered_lints_are_configured_and_run_like_the_built_in_ones() {
+        let mut registry = LintRegistry::new();
+        registry.register(NoWip).unwrap();
+        let mut strings = HashMap::new();
+        strings.insert("pb.lint.no-wip".into(), "warn".into());
+        let config = InMemory::new(&mut strings);
+
+        let configuration = get_registered_lint_configuration(&config, &registry).unwrap();
+
+        assert_eq!(
+            vec!["duplicated-trailers".to_string(), "no-wip".into()],
+            configuration.names()
+        );
+        assert_eq!(
+            vec![LintProblem::new(
+                "Finish the work before committing it".into(),
+                LintCode::CustomLint
+            )
+            .with_severity(Severity::Warning)
+            .with_lint_name("no-wip")],
+            lint(
+                &CommitMessage::new("WIP An example commit\n".into()),
+                &configuration
+            )
+        );
+    }
+
+    #[test]
+    fn lints_from_config_cannot_take_a_registered_name() {
+        let mut registry = LintRegistry::new();
+        registry.register(NoWip).unwrap();
+        let mut strings = HashMap::new();
+        strings.insert("pb.customlint.no-wip.pattern".into(), "^WIP".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Some(PbCommitMessageLintsError::LintAlreadyRegisteredError(
+                "no-wip".into()
+            )),
+            get_registered_lint_configuration(&config, &registry).err()
+        );
+    }
+
+    #[test]
+    fn lints_of_different_kinds_cannot_share_a_name() {
+        let mut strings = HashMap::new();
+        strings.insert("pb.customlint.no-wip.pattern".into(), "^WIP".into());
+        strings.insert("pb.script.no-wip.path".into(), "lints/no-wip.rhai".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Some(PbCommitMessageLintsError::LintAlreadyRegisteredError(
+                "no-wip".into()
+            )),
+            get_registered_lint_configuration(&config, &LintRegistry::new()).err()
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::{
+    convert::TryFrom,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+use serde_json::{json, Value};
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::vcs::Vcs,
+    lints::{
+        custom::{check_lint_name, configured_names},
+        plugin::{message_document, parse_problems},
+        CommitMessage,
+        LintCode,
+        LintConfiguration,
+        LintProblem,
+        PluginContext,
+        Severity,
+    },
+};
+
+const CONFIG_SCRIPT_PREFIX: &str = "pb.script.";
+const CONFIG_PATH: &str = "path";
+const CONFIG_BUDGET: &str = "budget";
+const DEFAULT_BUDGET: u64 = 100_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_SIZE: usize = 10_000;
+
+/// A lint defined in config, under `pb.script.<name>`, that runs a Rhai
+/// script to check the commit message
+///
+/// The script is given the commit message as a `commit` map, the same
+/// document plugins get, and its last expression is the list of problems
+/// it found. Scripts can't reach the filesystem or network, and are stopped
+/// once they've run more operations than their budget
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Script {
+    name: String,
+    path: PathBuf,
+    budget: u64,
+}
+
+impl Script {
+    /// A relative path is from the top of the repository
+    ///
+    /// # Errors
+    ///
+    /// If the name can't be used for a lint
+    pub fn new(name: &str, path: &Path) -> Result<Script, PbCommitMessageLintsError> {
+        check_lint_name(name)
+            .map_err(|reason| PbCommitMessageLintsError::ScriptError(name.into(), reason))?;
+
+        Ok(Script {
+            name: name.into(),
+            path: path.into(),
+            budget: DEFAULT_BUDGET,
+        })
+    }
+
+    /// How many operations the script can run before it's stopped
+    #[must_use]
+    pub fn with_budget(mut self, budget: u64) -> Script {
+        self.budget = budget;
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    #[must_use]
+    pub fn config_key(&self) -> String {
+        format!("pb.lint.{}", self.name)
+    }
+
+    /// Read the script from the repository and run it
+    ///
+    /// # Errors
+    ///
+    /// If the script can't be read, isn't valid, fails, runs out of budget,
+    /// or doesn't return a list of problems
+    pub fn lint(
+        &self,
+        commit_message: &CommitMessage,
+        context: &PluginContext,
+        severity: Severity,
+    ) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+        let path = context.repository().map_or_else(
+            || self.path.clone(),
+            |repository| repository.join(&self.path),
+        );
+        let source = fs::read_to_string(&path)
+            .map_err(|error| self.error(format!("{}: {}", path.display(), error)))?;
+
+        self.lint_source(&source, commit_message, context, severity)
+    }
+
+    /// Run the script's source, without reading it from a file
+    ///
+    /// # Errors
+    ///
+    /// If the script isn't valid, fails, runs out of budget, or doesn't
+    /// return a list of problems
+    pub fn lint_source(
+        &self,
+        source: &str,
+        commit_message: &CommitMessage,
+        context: &PluginContext,
+        severity: Severity,
+    ) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+        let engine = self.engine();
+        let ast = engine
+            .compile(source)
+            .map_err(|error| self.error(format!("{}", error)))?;
+        let mut scope = Scope::new();
+        scope.push_constant(
+            "commit",
+            to_dynamic(&message_document(commit_message, context)),
+        );
+
+        let result = engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &ast)
+            .map_err(|error| match *error {
+                EvalAltResult::ErrorTooManyOperations(_) => {
+                    PbCommitMessageLintsError::ScriptBudgetError(self.name.clone(), self.budget)
+                },
+                error => self.error(format!("{}", error)),
+            })?;
+        let problems = if result.is_unit() {
+            json!([])
+        } else {
+            from_dynamic(result)
+        };
+
+        parse_problems(
+            &self.name,
+            LintCode::Script,
+            commit_message,
+            &problems,
+            severity,
+        )
+        .map_err(|reason| self.error(reason))
+    }
+
+    /// An engine with nothing but the standard library, that can't import
+    /// modules or evaluate code it builds itself
+    fn engine(&self) -> Engine {
+        let mut engine = Engine::new();
+        engine
+            .disable_symbol("eval")
+            .set_max_operations(self.budget)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+            .set_max_string_size(MAX_SIZE)
+            .set_max_array_size(MAX_SIZE)
+            .set_max_map_size(MAX_SIZE)
+            .on_print(|text| eprintln!("{}", text))
+            .on_debug(|text, _, _| eprintln!("{}", text));
+
+        engine
+    }
+
+    fn error(&self, reason: String) -> PbCommitMessageLintsError {
+        PbCommitMessageLintsError::ScriptError(self.name.clone(), reason)
+    }
+}
+
+fn to_dynamic(value: &Value) -> Dynamic {
+    match value {
+        Value::Null => Dynamic::UNIT,
+        Value::Bool(value) => (*value).into(),
+        Value::Number(number) => number.as_i64().map_or(Dynamic::UNIT, Dynamic::from),
+        Value::String(text) => text.clone().into(),
+        Value::Array(items) => items.iter().map(to_dynamic).collect::<Array>().into(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| (key.as_str().into(), to_dynamic(value)))
+            .collect::<Map>()
+            .into(),
+    }
+}
+
+fn from_dynamic(value: Dynamic) -> Value {
+    if value.is_unit() {
+        Value::Null
+    } else if let Ok(value) = value.as_bool() {
+        json!(value)
+    } else if let Ok(number) = value.as_int() {
+        json!(number)
+    } else if value.is_array() {
+        Value::Array(
+            value
+                .into_array()
+                .unwrap_or_default()
+                .into_iter()
+                .map(from_dynamic)
+                .collect(),
+        )
+    } else if value.is_map() {
+        Value::Object(
+            value
+                .try_cast::<Map>()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), from_dynamic(value)))
+                .collect(),
+        )
+    } else {
+        json!(value.to_string())
+    }
+}
+
+/// Read every script in the config, in order of their names
+///
+/// # Errors
+///
+/// If there's an error reading from the configuration source, or one of the
+/// scripts isn't valid, which names the script
+pub fn get_scripts(config: &dyn Vcs) -> Result<Vec<Script>, PbCommitMessageLintsError> {
+    configured_names(config, CONFIG_SCRIPT_PREFIX)?
+        .iter()
+        .map(|name| get_script(config, name))
+        .collect()
+}
+
+fn get_script(config: &dyn Vcs, name: &str) -> Result<Script, PbCommitMessageLintsError> {
+    let key = |setting: &str| format!("{}{}.{}", CONFIG_SCRIPT_PREFIX, name, setting);
+    let invalid = |reason: String| PbCommitMessageLintsError::ScriptError(name.into(), reason);
+
+    let path = config.get_str(&key(CONFIG_PATH))?.ok_or_else(|| {
+        invalid(format!(
+            "There's no path to the script, set one in {}",
+            key(CONFIG_PATH)
+        ))
+    })?;
+    let mut script = Script::new(name, Path::new(path))?;
+
+    if let Some(budget) = config.get_i64(&key(CONFIG_BUDGET))? {
+        let budget = u64::try_from(budget)
+            .ok()
+            .filter(|budget| *budget > 0)
+            .ok_or_else(|| invalid("The budget has to be a number of operations above 0".into()))?;
+
+        script = script.with_budget(budget);
+    }
+
+    Ok(script)
+}
+
+/// Run every script that isn't off or skipped by the commit message
+///
+/// # Errors
+///
+/// If any of the scripts fail, or don't return a list of problems
+pub fn run_scripts(
+    commit_message: &CommitMessage,
+    configuration: &LintConfiguration,
+    context: &PluginContext,
+) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+    let suppressed = configuration.suppressed(commit_message);
+    let mut problems = vec![];
+
+    for (script, severity) in configuration.scripts() {
+        if severity == &Severity::Off || suppressed.iter().any(|name| name == script.name()) {
+            continue;
+        }
+
+        problems.extend(script.lint(commit_message, context, *severity)?);
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests_script {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        lints::{
+            CommitMessage,
+            Label,
+            LabelKind,
+            LintCode,
+            LintProblem,
+            PluginContext,
+            Script,
+            Severity,
+            Span,
+        },
+    };
+
+    const REVERT_NEEDS_A_REASON: &str = r#"
+        if commit.subject.starts_with("Revert") && !commit.body.contains("because") {
+            [#{
+                help: "Say why the commit is being reverted",
+                location: #{ line: 1, message: "this is a revert" }
+            }]
+        }
+    "#;
+
+    fn lint(source: &str, message: &str) -> Result<Vec<LintProblem>, PbCommitMessageLintsError> {
+        Script::new("revert-reason", Path::new("lints/revert.rhai"))
+            .unwrap()
+            .with_budget(10_000)
+            .lint_source(
+                source,
+                &CommitMessage::new(message.into()),
+                &PluginContext::default(),
+                Severity::Error,
+            )
+    }
+
+    #[test]
+    fn problems_are_what_the_script_returns() {
+        assert_eq!(
+            Ok(vec![LintProblem::new(
+                "Say why the commit is being reverted".into(),
+                LintCode::Script
+            )
+            .with_lint_name("revert-reason")
+            .with_label(Label::new(
+                Span::new(1, 1, 22),
+                LabelKind::Problem,
+                "this is a revert"
+            ))]),
+            lint(
+                REVERT_NEEDS_A_REASON,
+                "Revert the login page\n\nIt broke things\n"
+            )
+        );
+    }
+
+    #[test]
+    fn nothing_returned_is_no_problems() {
+        assert_eq!(
+            Ok(vec![]),
+            lint(
+                REVERT_NEEDS_A_REASON,
+                "Revert the login page\n\nThis is because it broke things\n"
+            )
+        );
+        assert_eq!(Ok(vec![]), lint("[]", "An example commit\n"));
+    }
+
+    #[test]
+    fn scripts_that_run_too_long_are_stopped() {
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ScriptBudgetError(
+                "revert-reason".into(),
+                10_000
+            )),
+            lint("loop {}", "An example commit\n")
+        );
+    }
+
+    #[test]
+    fn scripts_can_not_import_or_evaluate_code() {
+        assert!(matches!(
+            lint(r#"import "std" as std; []"#, "An example commit\n"),
+            Err(PbCommitMessageLintsError::ScriptError(_, _))
+        ));
+        assert!(matches!(
+            lint(r#"eval("[]")"#, "An example commit\n"),
+            Err(PbCommitMessageLintsError::ScriptError(_, _))
+        ));
+    }
+
+    #[test]
+    fn invalid_scripts_are_an_error() {
+        assert!(matches!(
+            lint("if {", "An example commit\n"),
+            Err(PbCommitMessageLintsError::ScriptError(_, _))
+        ));
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ScriptError(
+                "revert-reason".into(),
+                "Expected a list of problems".into()
+            )),
+            lint(r#""Not a list""#, "An example commit\n")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_get_scripts {
+    use std::{collections::HashMap, path::Path};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        errors::PbCommitMessageLintsError,
+        external::vcs::InMemory,
+        lints::{get_scripts, Script},
+    };
+
+    #[test]
+    fn scripts_are_read_from_config() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.script.revert-reason.path".into(),
+            "lints/revert.rhai".into(),
+        );
+        strings.insert("pb.script.revert-reason.budget".into(), "5000".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Ok(vec![Script::new(
+                "revert-reason",
+                Path::new("lints/revert.rhai")
+            )
+            .unwrap()
+            .with_budget(5000)]),
+            get_scripts(&config)
+        );
+    }
+
+    #[test]
+    fn budgets_have_to_be_above_zero() {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "pb.script.revert-reason.path".into(),
+            "lints/revert.rhai".into(),
+        );
+        strings.insert("pb.script.revert-reason.budget".into(), "0".into());
+        let config = InMemory::new(&mut strings);
+
+        assert_eq!(
+            Err(PbCommitMessageLintsError::ScriptError(
+                "revert-reason".into(),
+                "The budget has to be a number of operations above 0".into()
+            )),
+            get_scripts(&config)
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::convert::TryFrom;
+
+use crate::errors::PbCommitMessageLintsError;
+
+/// How much a problem found by a lint matters
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Severity {
+    Error,
+    Warning,
+    Off,
+}
+
+const SEVERITY_ERROR: &str = "error";
+const SEVERITY_WARNING: &str = "warn";
+const SEVERITY_OFF: &str = "off";
+
+impl Severity {
+    pub fn iterator() -> impl Iterator<Item = Severity> {
+        static SEVERITIES: [Severity; 3] = [Severity::Error, Severity::Warning, Severity::Off];
+        SEVERITIES.iter().copied()
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Error => SEVERITY_ERROR,
+            Severity::Warning => SEVERITY_WARNING,
+            Severity::Off => SEVERITY_OFF,
+        }
+    }
+
+    /// What to call a problem with this severity when it's shown to someone
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Off => "off",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl TryFrom<&str> for Severity {
+    type Error = PbCommitMessageLintsError;
+
+    fn try_from(from: &str) -> Result<Self, PbCommitMessageLintsError> {
+        Severity::iterator()
+            .find(|severity| severity.name() == from)
+            .ok_or_else(|| PbCommitMessageLintsError::SeverityNotFoundError(from.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests_severity {
+    use std::convert::TryFrom;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{errors::PbCommitMessageLintsError, lints::Severity};
+
+    #[test]
+    fn it_can_be_created_from_its_name() {
+        assert_eq!(Ok(Severity::Warning), Severity::try_from("warn"));
+    }
+
+    #[test]
+    fn unknown_severities_are_an_error() {
+        assert_eq!(
+            Err(PbCommitMessageLintsError::SeverityNotFoundError(
+                "banana".into()
+            )),
+            Severity::try_from("banana")
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use crate::lints::{
+    CommitMessage,
+    Documentation,
+    Edit,
+    Fix,
+    Label,
+    LabelKind,
+    LintCode,
+    LintOption,
+    LintProblem,
+    OptionKind,
+    Span,
+};
+
+const PERIOD_HELP: &str = r#"
+Your commit's subject ends with a period
+
+You can fix this by removing it, as the subject is a title rather than a sentence
+"#;
+
+const CAPITALISED_HELP: &str = r#"
+Your commit's subject doesn't start with a capital letter
+
+You can fix this by capitalising the first word
+"#;
+
+const WHITESPACE_HELP: &str = r#"
+Your commit's subject has whitespace at the start or end
+
+You can fix this by removing it
+"#;
+
+pub(crate) const OPTION_MAX_LENGTH: &str = "max-length";
+const DEFAULT_MAX_LENGTH: &str = "50";
+const HARD_MAX_LENGTH: usize = 72;
+
+/// The subject's line, any `fixup! ` and `squash! ` prefixes it starts
+/// with, and the rest of it
+fn subject_parts(commit_message: &CommitMessage) -> Option<(usize, &str, &str)> {
+    let line = commit_message.subject_line_number()?;
+    let subject = commit_message.subject()?;
+    let unprefixed = commit_message.subject_without_autosquash()?;
+
+    Some((
+        line,
+        &subject[..subject.len() - unprefixed.len()],
+        unprefixed,
+    ))
+}
+
+/// Replace the subject, keeping any prefixes it had
+fn replace_subject(line: usize, prefix: &str, unprefixed: &str, description: &str) -> Fix {
+    Fix::new(
+        description,
+        vec![Edit::Replace(line, format!("{}{}", prefix, unprefixed))],
+    )
+}
+
+pub(crate) fn max_length_options() -> Vec<LintOption> {
+    vec![LintOption::new(
+        OPTION_MAX_LENGTH,
+        "The most characters the subject can have, up to 72. Defaults to 50",
+        OptionKind::Number {
+            min: 1,
+            max: HARD_MAX_LENGTH,
+        },
+        DEFAULT_MAX_LENGTH,
+    )]
+}
+
+pub(crate) fn too_long_documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the subject is no longer than 50 characters, or the length it's been \
+         configured with, which can't be more than 72. Any `fixup! ` or `squash! ` prefixes \
+         aren't counted.",
+        "The subject is all that's shown by `git log --oneline`, in the list of commits in most \
+         tools, and as the title of patches sent by email. A short subject fits in all of them \
+         without being cut off or wrapped, and leaves the detail to the body.",
+    )
+    .with_passing_example("Add a search box\n\nSearches the titles of every page\n")
+    .with_passing_example("fixup! Add a search box that searches every page title\n")
+    .with_failing_example("Add a search box that searches the title of every page\n")
+}
+
+pub(crate) fn ends_with_period_documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the subject doesn't end with a period. An ellipsis is fine. This can be \
+         fixed automatically by removing the period.",
+        "The subject is the title of the change, and titles don't end with a period. Leaving it \
+         off also saves a character of a short line.",
+    )
+    .with_passing_example("Add a search box\n")
+    .with_passing_example("Try out a search box...\n")
+    .with_failing_example("Add a search box.\n")
+}
+
+pub(crate) fn not_capitalised_documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the first word of the subject starts with a capital letter, after any \
+         `fixup! ` or `squash! ` prefixes. Subjects that start with something other than a letter \
+         pass. This can be fixed automatically by capitalising the first letter.",
+        "The subject is the title of the change, and reads best in the log when every title \
+         starts the same way.",
+    )
+    .with_passing_example("Add a search box\n")
+    .with_passing_example("squash! Add a search box\n")
+    .with_passing_example("1 more test for the search box\n")
+    .with_failing_example("add a search box\n")
+}
+
+pub(crate) fn extra_whitespace_documentation() -> Documentation {
+    Documentation::new(
+        "Checks that the subject doesn't start or end with spaces or tabs. This can be fixed \
+         automatically by removing them.",
+        "Whitespace at the start of the subject makes it look indented in the log, and whitespace \
+         at the end can't be seen but still takes up room in a short line.",
+    )
+    .with_passing_example("Add a search box\n")
+    .with_failing_example("  Add a search box\n")
+    .with_failing_example("Add a search box \n")
+}
+
+pub(crate) fn lint_subject_too_long(
+    commit_message: &CommitMessage,
+    max_length: usize,
+) -> Option<LintProblem> {
+    let (line, prefix, subject) = subject_parts(commit_message)?;
+    let prefix_length = prefix.chars().count();
+    let length = subject.chars().count();
+
+    if length <= max_length {
+        return None;
+    }
+
+    Some(
+        LintProblem::new(
+            format!(
+                "\nYour commit's subject is longer than {} characters\n\nYou can fix this by \
+                 moving some of the detail into the body\n",
+                max_length
+            ),
+            LintCode::SubjectTooLong,
+        )
+        .with_label(Label::new(
+            Span::new(
+                line,
+                prefix_length + max_length + 1,
+                prefix_length + length + 1,
+            ),
+            LabelKind::Problem,
+            &format!("{} characters too long", length - max_length),
+        )),
+    )
+}
+
+pub(crate) fn lint_subject_ends_with_period(commit_message: &CommitMessage) -> Option<LintProblem> {
+    let (line, prefix, subject) = subject_parts(commit_message)?;
+    let trimmed = subject.trim_end();
+
+    if !trimmed.ends_with('.') || trimmed.ends_with("...") {
+        return None;
+    }
+
+    let column = prefix.chars().count() + trimmed.chars().count();
+    let without_period = format!(
+        "{}{}",
+        &trimmed[..trimmed.len() - 1],
+        &subject[trimmed.len()..]
+    );
+
+    Some(
+        LintProblem::new(PERIOD_HELP.into(), LintCode::SubjectEndsWithPeriod)
+            .with_label(Label::new(
+                Span::new(line, column, column + 1),
+                LabelKind::Problem,
+                "remove this period",
+            ))
+            .with_fix(replace_subject(
+                line,
+                prefix,
+                &without_period,
+                "Remove the period",
+            )),
+    )
+}
+
+pub(crate) fn lint_subject_not_capitalised(commit_message: &CommitMessage) -> Option<LintProblem> {
+    let (line, prefix, subject) = subject_parts(commit_message)?;
+    let trimmed = subject.trim_start();
+    let first = trimmed.chars().next()?;
+
+    if !first.is_lowercase() {
+        return None;
+    }
+
+    let start = prefix.chars().count() + subject.chars().count() - trimmed.chars().count() + 1;
+    let word = trimmed
+        .split_whitespace()
+        .next()
+        .map_or(1, |word| word.chars().count());
+    let capitalised = format!(
+        "{}{}{}",
+        &subject[..subject.len() - trimmed.len()],
+        first.to_uppercase(),
+        &trimmed[first.len_utf8()..]
+    );
+
+    Some(
+        LintProblem::new(CAPITALISED_HELP.into(), LintCode::SubjectNotCapitalised)
+            .with_label(Label::new(
+                Span::new(line, start, start + word),
+                LabelKind::Problem,
+                "capitalise this word",
+            ))
+            .with_fix(replace_subject(
+                line,
+                prefix,
+                &capitalised,
+                "Capitalise the first word",
+            )),
+    )
+}
+
+pub(crate) fn lint_subject_extra_whitespace(commit_message: &CommitMessage) -> Option<LintProblem> {
+    let (line, prefix, subject) = subject_parts(commit_message)?;
+    let trimmed = subject.trim();
+
+    if trimmed.len() == subject.len() {
+        return None;
+    }
+
+    let start = prefix.chars().count() + 1;
+    let end = start + subject.chars().count();
+    let leading = subject.chars().count() - subject.trim_start().chars().count();
+    let trailing = subject.chars().count() - subject.trim_end().chars().count();
+    let labels = vec![
+        (leading > 0).then(|| Span::new(line, start, start + leading)),
+        (trailing > 0).then(|| Span::new(line, end - trailing, end)),
+    ];
+
+    Some(
+        labels
+            .into_iter()
+            .flatten()
+            .map(|span| Label::new(span, LabelKind::Problem, "remove this whitespace"))
+            .fold(
+                LintProblem::new(WHITESPACE_HELP.into(), LintCode::SubjectExtraWhitespace),
+                LintProblem::with_label,
+            )
+            .with_fix(replace_subject(
+                line,
+                prefix,
+                trimmed,
+                "Remove the whitespace",
+            )),
+    )
+}
+
+#[cfg(test)]
+mod tests_subject_too_long {
+    #![allow(clippy::wildcard_imports)]
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn subjects_up_to_the_limit_pass() {
+        assert_eq!(
+            None,
+            lint_subject_too_long(&CommitMessage::new("Add a search box\n".into()), 16)
+        );
+    }
+
+    #[test]
+    fn the_characters_over_the_limit_are_marked() {
+        assert_eq!(
+            Some(
+                LintProblem::new(
+                    "\nYour commit's subject is longer than 10 characters\n\nYou can fix this by \
+                     moving some of the detail into the body\n"
+                        .into(),
+                    LintCode::SubjectTooLong
+                )
+                .with_label(Label::new(
+                    Span::new(1, 11, 17),
+                    LabelKind::Problem,
+                    "6 characters too long"
+                ))
+            ),
+            lint_subject_too_long(&CommitMessage::new("Add a search box\n".into()), 10)
+        );
+    }
+
+    #[test]
+    fn characters_are_counted_rather_than_bytes() {
+        assert_eq!(
+            None,
+            lint_subject_too_long(&CommitMessage::new("Añadir búsqueda\n".into()), 15)
+        );
+    }
+
+    #[test]
+    fn autosquash_prefixes_are_not_counted() {
+        assert_eq!(
+            Some(Span::new(1, 26, 32)),
+            lint_subject_too_long(
+                &CommitMessage::new("fixup! squash! Add a search box\n".into()),
+                10
+            )
+            .map(|problem| problem.labels()[0].span())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_subject_ends_with_period {
+    #![allow(clippy::wildcard_imports)]
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn the_period_is_marked_and_can_be_removed() {
+        assert_eq!(
+            Some(
+                LintProblem::new(PERIOD_HELP.into(), LintCode::SubjectEndsWithPeriod)
+                    .with_label(Label::new(
+                        Span::new(1, 24, 25),
+                        LabelKind::Problem,
+                        "remove this period"
+                    ))
+                    .with_fix(Fix::new(
+                        "Remove the period",
+                        vec![Edit::Replace(1, "fixup! Add a search box".into())]
+                    ))
+            ),
+            lint_subject_ends_with_period(&CommitMessage::new("fixup! Add a search box.\n".into()))
+        );
+    }
+
+    #[test]
+    fn other_endings_pass() {
+        for subject in &["Add a search box\n", "Try a search box...\n"] {
+            assert_eq!(
+                None,
+                lint_subject_ends_with_period(&CommitMessage::new((*subject).into())),
+                "{}",
+                subject
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_subject_not_capitalised {
+    #![allow(clippy::wildcard_imports)]
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn the_first_word_is_marked_and_can_be_capitalised() {
+        assert_eq!(
+            Some(
+                LintProblem::new(CAPITALISED_HELP.into(), LintCode::SubjectNotCapitalised)
+                    .with_label(Label::new(
+                        Span::new(1, 9, 12),
+                        LabelKind::Problem,
+                        "capitalise this word"
+                    ))
+                    .with_fix(Fix::new(
+                        "Capitalise the first word",
+                        vec![Edit::Replace(1, "squash! Add a search box".into())]
+                    ))
+            ),
+            lint_subject_not_capitalised(&CommitMessage::new("squash! add a search box\n".into()))
+        );
+    }
+
+    #[test]
+    fn subjects_that_do_not_start_with_a_letter_pass() {
+        assert_eq!(
+            None,
+            lint_subject_not_capitalised(&CommitMessage::new("1 more test\n".into()))
+        );
+        assert_eq!(
+            None,
+            lint_subject_not_capitalised(&CommitMessage::new("Érase una vez\n".into()))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_subject_extra_whitespace {
+    #![allow(clippy::wildcard_imports)]
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn whitespace_at_either_end_is_marked_and_can_be_removed() {
+        assert_eq!(
+            Some(
+                LintProblem::new(WHITESPACE_HELP.into(), LintCode::SubjectExtraWhitespace)
+                    .with_label(Label::new(
+                        Span::new(1, 1, 3),
+                        LabelKind::Problem,
+                        "remove this whitespace"
+                    ))
+                    .with_label(Label::new(
+                        Span::new(1, 19, 20),
+                        LabelKind::Problem,
+                        "remove this whitespace"
+                    ))
+                    .with_fix(Fix::new(
+                        "Remove the whitespace",
+                        vec![Edit::Replace(1, "Add a search box".into())]
+                    ))
+            ),
+            lint_subject_extra_whitespace(&CommitMessage::new("  Add a search box\t\n".into()))
+        );
+    }
+
+    #[test]
+    fn trimmed_subjects_pass() {
+        assert_eq!(
+            None,
+            lint_subject_extra_whitespace(&CommitMessage::new("Add a search box\n".into()))
+        );
+    }
+}
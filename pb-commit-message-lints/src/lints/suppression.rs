@@ -0,0 +1,105 @@
+use crate::lints::{CommitMessage, Lints};
+
+const SUPPRESSION_TRAILER: &str = "Lint-Skip";
+const SUPPRESSION_DIRECTIVE: &str = "pb-lint-disable";
+
+/// The built-in lints a commit message asks to skip
+///
+/// Names that aren't built-in lints are ignored
+#[must_use]
+pub fn get_suppressions(commit_message: &CommitMessage) -> Vec<Lints> {
+    let named = get_suppressed_names(commit_message);
+
+    Lints::iterator()
+        .filter(|lint| named.iter().any(|name| name == lint.name()))
+        .collect()
+}
+
+/// The names of every lint a commit message asks to skip
+///
+/// They can be named in a `Lint-Skip: <lint>` trailer, or a
+/// `pb-lint-disable <lint>` comment, separated by spaces or commas
+#[must_use]
+pub fn get_suppressed_names(commit_message: &CommitMessage) -> Vec<String> {
+    let trailers = commit_message
+        .get_trailer(SUPPRESSION_TRAILER)
+        .into_iter()
+        .map(|trailer| trailer.value().to_string())
+        .collect::<Vec<_>>();
+    let directives = commit_message
+        .comments()
+        .into_iter()
+        .filter_map(|comment| {
+            comment
+                .chars()
+                .skip(1)
+                .collect::<String>()
+                .trim()
+                .strip_prefix(SUPPRESSION_DIRECTIVE)
+                .map(String::from)
+        })
+        .collect::<Vec<_>>();
+    let named = trailers
+        .iter()
+        .chain(directives.iter())
+        .flat_map(|names| {
+            names.split(|character: char| character == ',' || character.is_whitespace())
+        })
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    named
+        .iter()
+        .enumerate()
+        .filter(|(index, name)| !named[..*index].contains(name))
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_get_suppressions {
+    use pretty_assertions::assert_eq;
+
+    use crate::lints::{
+        get_suppressions,
+        CommitMessage,
+        Lints::{DuplicatedTrailers, JiraIssueKeyMissing, PivotalTrackerIdMissing},
+    };
+
+    #[test]
+    fn lints_can_be_skipped_with_a_trailer() {
+        let commit = CommitMessage::new(
+            "Release 1.0.0\n\nLint-Skip: jira-issue-key-missing, pivotal-tracker-id-missing\n"
+                .into(),
+        );
+
+        assert_eq!(
+            vec![PivotalTrackerIdMissing, JiraIssueKeyMissing],
+            get_suppressions(&commit)
+        );
+    }
+
+    #[test]
+    fn lints_can_be_skipped_with_a_comment() {
+        let commit =
+            CommitMessage::new("Release 1.0.0\n\n# pb-lint-disable duplicated-trailers\n".into());
+
+        assert_eq!(vec![DuplicatedTrailers], get_suppressions(&commit));
+    }
+
+    #[test]
+    fn unknown_lints_are_ignored() {
+        let commit = CommitMessage::new("Release 1.0.0\n\nLint-Skip: banana\n".into());
+
+        assert_eq!(Vec::<crate::lints::Lints>::new(), get_suppressions(&commit));
+    }
+
+    #[test]
+    fn the_trailer_has_to_be_a_trailer() {
+        let commit =
+            CommitMessage::new("Lint-Skip: jira-issue-key-missing\n\nA body paragraph\n".into());
+
+        assert_eq!(Vec::<crate::lints::Lints>::new(), get_suppressions(&commit));
+    }
+}
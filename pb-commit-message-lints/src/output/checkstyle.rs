@@ -0,0 +1,81 @@
+use crate::{
+    lints::LintProblem,
+    output::{escape_xml, Report},
+};
+
+const CHECKSTYLE_VERSION: &str = "4.3";
+const SOURCE_PREFIX: &str = "pb-git-hooks";
+
+pub(crate) fn format_checkstyle(reports: &[Report]) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"{}\">\n{}</checkstyle>",
+        CHECKSTYLE_VERSION,
+        reports.iter().map(format_file).collect::<String>()
+    )
+}
+
+fn format_file(report: &Report) -> String {
+    format!(
+        "  <file name=\"{}\">\n{}  </file>\n",
+        escape_xml(report.origin()),
+        report
+            .problems()
+            .iter()
+            .map(format_error)
+            .collect::<String>()
+    )
+}
+
+/// Checkstyle needs a line for every error, so problems that aren't about
+/// any part of the message go on the first line
+fn format_error(problem: &LintProblem) -> String {
+    let (line, column) = problem.labels().first().map_or((1, 1), |label| {
+        (label.span().line(), label.span().start_column())
+    });
+
+    format!(
+        "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}.{}\"/>\n",
+        line,
+        column,
+        problem.severity().label(),
+        escape_xml(problem.help().trim()),
+        SOURCE_PREFIX,
+        problem.lint_name()
+    )
+}
+
+#[cfg(test)]
+mod tests_format_checkstyle {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        lints::{CommitMessage, Label, LabelKind, LintCode, LintProblem, Lints, Span},
+        output::{checkstyle::format_checkstyle, Report},
+    };
+
+    #[test]
+    fn problems_are_errors_on_their_first_label() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n".into()),
+            vec![Lints::DuplicatedTrailers, Lints::JiraIssueKeyMissing],
+            vec![
+                LintProblem::new("Duplicated\n".into(), LintCode::DuplicatedTrailers).with_label(
+                    Label::new(Span::new(7, 1, 51), LabelKind::Problem, "duplicated here"),
+                ),
+                LintProblem::new("Missing\n".into(), LintCode::JiraIssueKeyMissing),
+            ],
+        );
+
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="4.3">
+  <file name="COMMIT_EDITMSG">
+    <error line="7" column="1" severity="error" message="Duplicated" source="pb-git-hooks.duplicated-trailers"/>
+    <error line="1" column="1" severity="error" message="Missing" source="pb-git-hooks.jira-issue-key-missing"/>
+  </file>
+</checkstyle>"#,
+            format_checkstyle(&[report])
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{
+    lints::{CommitMessage, Label, LabelKind, LintProblem},
+    output::split_help,
+};
+
+const PROBLEM_MARKER: &str = "^";
+const SUGGESTION_MARKER: &str = "-";
+
+/// Render a problem like a compiler diagnostic, showing the lines of the
+/// commit message that it is about
+///
+/// The first line of the help becomes the headline, and the rest of it
+/// follows the snippet of the commit message
+#[must_use]
+pub fn format_diagnostic(
+    origin: &str,
+    commit_message: &CommitMessage,
+    problem: &LintProblem,
+) -> String {
+    let (summary, details) = split_help(problem.help());
+
+    let mut output = format!("{}: {}\n", problem.severity().label(), summary);
+    output.push_str(&format_snippet(origin, commit_message, problem.labels()));
+
+    if !details.is_empty() {
+        let _ = writeln!(output, "\n{}", details);
+    }
+
+    output
+}
+
+fn format_snippet(origin: &str, commit_message: &CommitMessage, labels: &[Label]) -> String {
+    let first = match labels.first() {
+        Some(label) => label.span(),
+        None => return String::new(),
+    };
+    let lines = labels
+        .iter()
+        .map(|label| label.span().line())
+        .collect::<BTreeSet<_>>();
+    let gutter = lines.iter().max().map_or(1, |line| line.to_string().len());
+    let padding = " ".repeat(gutter);
+
+    let mut output = format!(
+        "{}--> {}:{}:{}\n{} |\n",
+        padding,
+        origin,
+        first.line(),
+        first.start_column(),
+        padding
+    );
+    let mut previous: Option<usize> = None;
+
+    for line in lines {
+        if matches!(previous, Some(previous) if line > previous + 1) {
+            output.push_str("...\n");
+        }
+
+        output.push_str(&format_line(
+            &format!("{:>width$}", line, width = gutter),
+            commit_message.line(line).unwrap_or_default(),
+        ));
+
+        labels
+            .iter()
+            .filter(|label| label.span().line() == line)
+            .for_each(|label| output.push_str(&format_line(&padding, &format_underline(label))));
+
+        previous = Some(line);
+    }
+
+    let _ = writeln!(output, "{} |", padding);
+    output
+}
+
+fn format_underline(label: &Label) -> String {
+    let span = label.span();
+    let marker = match label.kind() {
+        LabelKind::Problem => PROBLEM_MARKER,
+        LabelKind::Suggestion => SUGGESTION_MARKER,
+    };
+
+    format!(
+        "{}{} {}",
+        " ".repeat(span.start_column().saturating_sub(1)),
+        marker.repeat(span.end_column().saturating_sub(span.start_column()).max(1)),
+        label.message()
+    )
+}
+
+fn format_line(gutter: &str, text: &str) -> String {
+    format!("{} | {}", gutter, text).trim_end().to_string() + "\n"
+}
+
+#[cfg(test)]
+mod tests_format_diagnostic {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        lints::{CommitMessage, Label, LabelKind, LintCode, LintProblem, Span},
+        output::format_diagnostic,
+    };
+
+    #[test]
+    fn without_labels_it_is_just_the_help() {
+        let commit = CommitMessage::new("An example commit\n".into());
+        let problem = LintProblem::new(
+            "\nSomething is wrong\n\nYou can fix it\n".into(),
+            LintCode::JiraIssueKeyMissing,
+        );
+
+        assert_eq!(
+            "error: Something is wrong\n\nYou can fix it\n",
+            format_diagnostic("COMMIT_EDITMSG", &commit, &problem)
+        );
+    }
+
+    #[test]
+    fn labels_underline_the_lines_they_are_about() {
+        let commit = CommitMessage::new(
+            r#"An example commit
+
+This is an example commit
+
+Signed-off-by: Billie Thompson <email@example.com>
+Signed-off-by: Billie Thompson <email@example.com>
+"#
+            .into(),
+        );
+        let problem = LintProblem::new(
+            "Duplicated\n\nRemove it\n".into(),
+            LintCode::DuplicatedTrailers,
+        )
+        .with_label(Label::new(
+            Span::new(6, 1, 51),
+            LabelKind::Problem,
+            "duplicated here",
+        ))
+        .with_label(Label::new(Span::new(1, 4, 11), LabelKind::Suggestion, ""));
+
+        assert_eq!(
+            r#"error: Duplicated
+ --> COMMIT_EDITMSG:6:1
+  |
+1 | An example commit
+  |    -------
+...
+6 | Signed-off-by: Billie Thompson <email@example.com>
+  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ duplicated here
+  |
+
+Remove it
+"#,
+            format_diagnostic("COMMIT_EDITMSG", &commit, &problem)
+        );
+    }
+
+    #[test]
+    fn the_gutter_fits_the_widest_line_number() {
+        let commit = CommitMessage::new(format!("An example commit{}", "\n".repeat(10)));
+        let problem = LintProblem::new("Something is wrong".into(), LintCode::DuplicatedTrailers)
+            .with_label(Label::new(Span::new(10, 1, 1), LabelKind::Problem, "here"))
+            .with_label(Label::new(
+                Span::new(9, 1, 1),
+                LabelKind::Problem,
+                "and here",
+            ));
+
+        assert_eq!(
+            r#"error: Something is wrong
+  --> COMMIT_EDITMSG:10:1
+   |
+ 9 |
+   | ^ and here
+10 |
+   | ^ here
+   |
+"#,
+            format_diagnostic("COMMIT_EDITMSG", &commit, &problem)
+        );
+    }
+}
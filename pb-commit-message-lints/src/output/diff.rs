@@ -0,0 +1,120 @@
+use std::fmt::Write;
+
+use crate::lints::{CommitMessage, Edit, Fix};
+
+const REMOVED_MARKER: &str = "-";
+const ADDED_MARKER: &str = "+";
+
+/// Render a fix as a small diff of the lines of the commit message it
+/// changes, so the person committing can see what was done for them
+#[must_use]
+pub fn format_fix(origin: &str, commit_message: &CommitMessage, fix: &Fix) -> String {
+    let mut edits = fix.edits().iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| edit.line());
+
+    let gutter = edits
+        .iter()
+        .map(|edit| edit.line())
+        .max()
+        .map_or(1, |line| line.to_string().len());
+    let padding = " ".repeat(gutter);
+
+    let mut output = format!("fixed: {}\n", fix.description());
+
+    if let Some(first) = edits.first() {
+        let _ = writeln!(
+            output,
+            "{}--> {}:{}\n{} |",
+            padding,
+            origin,
+            first.line(),
+            padding
+        );
+    }
+
+    for edit in &edits {
+        let original = commit_message.line(edit.line()).unwrap_or_default();
+
+        match edit {
+            Edit::Insert(line, text) => {
+                output.push_str(&diff_line(gutter, *line, ADDED_MARKER, text));
+            },
+            Edit::Replace(line, text) => {
+                output.push_str(&diff_line(gutter, *line, REMOVED_MARKER, original));
+                output.push_str(&diff_line(gutter, *line, ADDED_MARKER, text));
+            },
+            Edit::Remove(line) => {
+                output.push_str(&diff_line(gutter, *line, REMOVED_MARKER, original));
+            },
+        }
+    }
+
+    if !edits.is_empty() {
+        let _ = writeln!(output, "{} |", padding);
+    }
+
+    output
+}
+
+fn diff_line(gutter: usize, line: usize, marker: &str, text: &str) -> String {
+    if text.is_empty() {
+        format!("{:>gutter$} {}\n", line, marker, gutter = gutter)
+    } else {
+        format!("{:>gutter$} {} {}\n", line, marker, text, gutter = gutter)
+    }
+}
+
+#[cfg(test)]
+mod tests_format_fix {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        lints::{CommitMessage, Edit, Fix},
+        output::format_fix,
+    };
+
+    #[test]
+    fn removed_lines_are_shown() {
+        let commit = CommitMessage::new(
+            "An example commit\n\nSigned-off-by: Billie <billie@example.com>\nSigned-off-by: \
+             Billie <billie@example.com>\n"
+                .into(),
+        );
+        let fix = Fix::new("Remove the duplicated trailer", vec![Edit::Remove(4)]);
+
+        assert_eq!(
+            r#"fixed: Remove the duplicated trailer
+ --> COMMIT_EDITMSG:4
+  |
+4 - Signed-off-by: Billie <billie@example.com>
+  |
+"#,
+            format_fix("COMMIT_EDITMSG", &commit, &fix)
+        );
+    }
+
+    #[test]
+    fn replaced_and_inserted_lines_are_shown() {
+        let commit =
+            CommitMessage::new("an example commit\n\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n".into());
+        let fix = Fix::new(
+            "Tidy up",
+            vec![
+                Edit::Insert(13, "11".into()),
+                Edit::Replace(1, "An example commit".into()),
+            ],
+        );
+
+        assert_eq!(
+            r#"fixed: Tidy up
+  --> COMMIT_EDITMSG:1
+   |
+ 1 - an example commit
+ 1 + An example commit
+13 + 11
+   |
+"#,
+            format_fix("COMMIT_EDITMSG", &commit, &fix)
+        );
+    }
+}
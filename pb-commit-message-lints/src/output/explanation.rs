@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use crate::lints::Lints;
+
+const EXAMPLE_INDENT: &str = "    ";
+
+/// Render the long-form documentation for a lint as text
+#[must_use]
+pub fn format_explanation(lint: Lints) -> String {
+    let documentation = lint.documentation();
+    let mut output = format!(
+        "{}: {}\nDefault: {}\n\n{}\n\nWhy?\n\n{}\n\nConfiguration\n\n",
+        lint,
+        lint.description(),
+        lint.default_severity(),
+        documentation.description(),
+        documentation.rationale()
+    );
+
+    let _ = writeln!(
+        output,
+        "{}{}\n{}{}error, warn or off",
+        EXAMPLE_INDENT,
+        lint.config_key(),
+        EXAMPLE_INDENT,
+        EXAMPLE_INDENT
+    );
+    for (key, description) in documentation.options() {
+        let _ = writeln!(
+            output,
+            "{}{}\n{}{}{}",
+            EXAMPLE_INDENT, key, EXAMPLE_INDENT, EXAMPLE_INDENT, description
+        );
+    }
+
+    let _ = writeln!(
+        output,
+        "\nIt can be skipped for one commit with a `Lint-Skip: {}` trailer",
+        lint
+    );
+
+    for (heading, examples) in [
+        ("Passes", documentation.passing()),
+        ("Fails", documentation.failing()),
+    ] {
+        for example in examples {
+            let _ = write!(output, "\n{}\n\n{}", heading, indent(example));
+        }
+    }
+
+    output
+}
+
+fn indent(example: &str) -> String {
+    example
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("{}{}\n", EXAMPLE_INDENT, line)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests_format_explanation {
+    use pretty_assertions::assert_eq;
+
+    use crate::{lints::Lints, output::format_explanation};
+
+    #[test]
+    fn it_has_the_documentation_and_examples() {
+        let output = format_explanation(Lints::JiraIssueKeyMissing);
+
+        assert!(output.starts_with(
+            "jira-issue-key-missing: Detect a missing Jira Issue Key\nDefault: off\n\nChecks"
+        ));
+        assert!(output.contains(
+            "\nConfiguration\n\n    pb.lint.jira-issue-key-missing\n        error, warn or off\n"
+        ));
+        assert!(output.contains("\nPasses\n\n    JRA-123 An example commit\n\n    This is"));
+        assert!(output.contains("\nFails\n\n    An example commit\n"));
+    }
+
+    #[test]
+    fn every_example_is_shown() {
+        let output = format_explanation(Lints::PivotalTrackerIdMissing);
+        let documentation = Lints::PivotalTrackerIdMissing.documentation();
+
+        assert_eq!(
+            documentation.passing().len() + documentation.failing().len(),
+            output.matches("\n    An example commit\n").count()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    lints::{Label, LabelKind, LintProblem},
+    output::Report,
+};
+
+/// Bump this whenever a field is removed or changes meaning, adding fields is
+/// not a breaking change
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+// Serialize is implemented by hand, as the derive in the version of serde we
+// use generates impls that newer compilers warn about
+
+struct JsonOutput<'a> {
+    schema_version: u32,
+    results: Vec<JsonResult<'a>>,
+}
+
+impl Serialize for JsonOutput<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut output = serializer.serialize_struct("JsonOutput", 2)?;
+        output.serialize_field("schema_version", &self.schema_version)?;
+        output.serialize_field("results", &self.results)?;
+        output.end()
+    }
+}
+
+struct JsonResult<'a> {
+    origin: &'a str,
+    lints: Vec<&'a str>,
+    problems: Vec<JsonProblem<'a>>,
+    suppressed: Vec<&'a str>,
+}
+
+impl Serialize for JsonResult<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut result = serializer.serialize_struct("JsonResult", 4)?;
+        result.serialize_field("origin", self.origin)?;
+        result.serialize_field("lints", &self.lints)?;
+        result.serialize_field("problems", &self.problems)?;
+        result.serialize_field("suppressed", &self.suppressed)?;
+        result.end()
+    }
+}
+
+struct JsonProblem<'a> {
+    lint: &'a str,
+    code: i32,
+    severity: &'static str,
+    help: &'a str,
+    labels: Vec<JsonLabel<'a>>,
+}
+
+impl Serialize for JsonProblem<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut problem = serializer.serialize_struct("JsonProblem", 5)?;
+        problem.serialize_field("lint", self.lint)?;
+        problem.serialize_field("code", &self.code)?;
+        problem.serialize_field("severity", self.severity)?;
+        problem.serialize_field("help", self.help)?;
+        problem.serialize_field("labels", &self.labels)?;
+        problem.end()
+    }
+}
+
+struct JsonLabel<'a> {
+    kind: &'static str,
+    line: usize,
+    start_column: usize,
+    end_column: usize,
+    message: &'a str,
+}
+
+impl Serialize for JsonLabel<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut label = serializer.serialize_struct("JsonLabel", 5)?;
+        label.serialize_field("kind", self.kind)?;
+        label.serialize_field("line", &self.line)?;
+        label.serialize_field("start_column", &self.start_column)?;
+        label.serialize_field("end_column", &self.end_column)?;
+        label.serialize_field("message", self.message)?;
+        label.end()
+    }
+}
+
+impl<'a> From<&'a Report> for JsonResult<'a> {
+    fn from(report: &'a Report) -> Self {
+        JsonResult {
+            origin: report.origin(),
+            lints: report.lints().iter().map(String::as_str).collect(),
+            problems: report.problems().iter().map(JsonProblem::from).collect(),
+            suppressed: report.suppressed().iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a LintProblem> for JsonProblem<'a> {
+    fn from(problem: &'a LintProblem) -> Self {
+        JsonProblem {
+            lint: problem.lint_name(),
+            code: problem.code() as i32,
+            severity: problem.severity().name(),
+            help: problem.help(),
+            labels: problem.labels().iter().map(JsonLabel::from).collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a Label> for JsonLabel<'a> {
+    fn from(label: &'a Label) -> Self {
+        JsonLabel {
+            kind: match label.kind() {
+                LabelKind::Problem => "problem",
+                LabelKind::Suggestion => "suggestion",
+            },
+            line: label.span().line(),
+            start_column: label.span().start_column(),
+            end_column: label.span().end_column(),
+            message: label.message(),
+        }
+    }
+}
+
+pub(crate) fn format_json(reports: &[Report]) -> Result<String, PbCommitMessageLintsError> {
+    serde_json::to_string_pretty(&JsonOutput {
+        schema_version: JSON_SCHEMA_VERSION,
+        results: reports.iter().map(JsonResult::from).collect(),
+    })
+    .map_err(PbCommitMessageLintsError::from)
+}
+
+#[cfg(test)]
+mod tests_format_json {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        lints::{CommitMessage, Label, LabelKind, LintCode, LintProblem, Lints, Span},
+        output::{json::format_json, Report},
+    };
+
+    #[test]
+    fn no_problems_is_an_empty_list() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n".into()),
+            vec![Lints::DuplicatedTrailers],
+            vec![],
+        );
+
+        assert_eq!(
+            Ok(r#"{
+  "schema_version": 1,
+  "results": [
+    {
+      "origin": "COMMIT_EDITMSG",
+      "lints": [
+        "duplicated-trailers"
+      ],
+      "problems": [],
+      "suppressed": []
+    }
+  ]
+}"#
+            .into()),
+            format_json(&[report])
+        );
+    }
+
+    #[test]
+    fn problems_include_their_lint_and_location() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n".into()),
+            vec![Lints::JiraIssueKeyMissing],
+            vec![
+                LintProblem::new("Missing\n".into(), LintCode::JiraIssueKeyMissing).with_label(
+                    Label::new(Span::new(1, 1, 18), LabelKind::Suggestion, "add it here"),
+                ),
+            ],
+        );
+
+        assert_eq!(
+            Ok(r#"{
+  "schema_version": 1,
+  "results": [
+    {
+      "origin": "COMMIT_EDITMSG",
+      "lints": [
+        "jira-issue-key-missing"
+      ],
+      "problems": [
+        {
+          "lint": "jira-issue-key-missing",
+          "code": 5,
+          "severity": "error",
+          "help": "Missing\n",
+          "labels": [
+            {
+              "kind": "suggestion",
+              "line": 1,
+              "start_column": 1,
+              "end_column": 18,
+              "message": "add it here"
+            }
+          ]
+        }
+      ],
+      "suppressed": []
+    }
+  ]
+}"#
+            .into()),
+            format_json(&[report])
+        );
+    }
+
+    #[test]
+    fn skipped_lints_are_listed() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n\nLint-Skip: jira-issue-key-missing\n".into()),
+            vec![Lints::JiraIssueKeyMissing],
+            vec![],
+        )
+        .with_suppressed(vec![Lints::JiraIssueKeyMissing]);
+
+        assert_eq!(
+            Ok(r#"{
+  "schema_version": 1,
+  "results": [
+    {
+      "origin": "COMMIT_EDITMSG",
+      "lints": [
+        "jira-issue-key-missing"
+      ],
+      "problems": [],
+      "suppressed": [
+        "jira-issue-key-missing"
+      ]
+    }
+  ]
+}"#
+            .into()),
+            format_json(&[report])
+        );
+    }
+}
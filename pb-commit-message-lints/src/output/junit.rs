@@ -0,0 +1,182 @@
+use crate::{
+    lints::{LintProblem, Severity},
+    output::{escape_xml, split_help, Report},
+};
+
+/// Every lint that ran is a test case, which fails if it found an error
+///
+/// Warnings don't fail the test case, they're written to its output instead,
+/// and lints the commit message skipped are skipped test cases
+pub(crate) fn format_junit(reports: &[Report]) -> String {
+    let tests = reports
+        .iter()
+        .map(|report| report.lints().len())
+        .sum::<usize>();
+    let failures = reports.iter().map(count_errors).sum::<usize>();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites tests=\"{}\" \
+         failures=\"{}\">\n{}</testsuites>",
+        tests,
+        failures,
+        reports.iter().map(format_test_suite).collect::<String>()
+    )
+}
+
+fn format_test_suite(report: &Report) -> String {
+    format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n{}  </testsuite>\n",
+        escape_xml(report.origin()),
+        report.lints().len(),
+        count_errors(report),
+        report
+            .lints()
+            .iter()
+            .map(|lint| format_test_case(report, lint))
+            .collect::<String>()
+    )
+}
+
+fn count_errors(report: &Report) -> usize {
+    report
+        .problems()
+        .iter()
+        .filter(|problem| problem.severity() == Severity::Error)
+        .count()
+}
+
+fn format_test_case(report: &Report, lint: &str) -> String {
+    let results = report
+        .problems()
+        .iter()
+        .filter(|problem| problem.lint_name() == lint)
+        .map(|problem| match problem.severity() {
+            Severity::Error => format_failure(problem),
+            Severity::Warning | Severity::Off => format_output(problem),
+        })
+        .collect::<String>();
+
+    if report.suppressed().iter().any(|name| name == lint) {
+        format!(
+            "    <testcase name=\"{}\" classname=\"{}\">\n      <skipped message=\"Skipped by the \
+             commit message\"/>\n    </testcase>\n",
+            escape_xml(lint),
+            escape_xml(report.origin())
+        )
+    } else if results.is_empty() {
+        format!(
+            "    <testcase name=\"{}\" classname=\"{}\"/>\n",
+            escape_xml(lint),
+            escape_xml(report.origin())
+        )
+    } else {
+        format!(
+            "    <testcase name=\"{}\" classname=\"{}\">\n{}    </testcase>\n",
+            escape_xml(lint),
+            escape_xml(report.origin()),
+            results
+        )
+    }
+}
+
+fn format_failure(problem: &LintProblem) -> String {
+    let (summary, _) = split_help(problem.help());
+
+    format!(
+        "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+        escape_xml(summary),
+        escape_xml(problem.lint_name()),
+        escape_xml(problem.help().trim())
+    )
+}
+
+fn format_output(problem: &LintProblem) -> String {
+    format!(
+        "      <system-out>{}</system-out>\n",
+        escape_xml(problem.help().trim())
+    )
+}
+
+#[cfg(test)]
+mod tests_format_junit {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        lints::{CommitMessage, LintCode, LintProblem, Lints, Severity},
+        output::{junit::format_junit, Report},
+    };
+
+    #[test]
+    fn lints_without_problems_pass() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n".into()),
+            vec![Lints::DuplicatedTrailers, Lints::JiraIssueKeyMissing],
+            vec![LintProblem::new(
+                "Missing <key>\n\nAdd it\n".into(),
+                LintCode::JiraIssueKeyMissing,
+            )],
+        );
+
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="2" failures="1">
+  <testsuite name="COMMIT_EDITMSG" tests="2" failures="1">
+    <testcase name="duplicated-trailers" classname="COMMIT_EDITMSG"/>
+    <testcase name="jira-issue-key-missing" classname="COMMIT_EDITMSG">
+      <failure message="Missing &lt;key&gt;" type="jira-issue-key-missing">Missing &lt;key&gt;&#10;&#10;Add it</failure>
+    </testcase>
+  </testsuite>
+</testsuites>"#,
+            format_junit(&[report])
+        );
+    }
+
+    #[test]
+    fn warnings_do_not_fail() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n".into()),
+            vec![Lints::JiraIssueKeyMissing],
+            vec![
+                LintProblem::new("Missing\n\nAdd it\n".into(), LintCode::JiraIssueKeyMissing)
+                    .with_severity(Severity::Warning),
+            ],
+        );
+
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="1" failures="0">
+  <testsuite name="COMMIT_EDITMSG" tests="1" failures="0">
+    <testcase name="jira-issue-key-missing" classname="COMMIT_EDITMSG">
+      <system-out>Missing&#10;&#10;Add it</system-out>
+    </testcase>
+  </testsuite>
+</testsuites>"#,
+            format_junit(&[report])
+        );
+    }
+
+    #[test]
+    fn skipped_lints_are_skipped() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n\nLint-Skip: jira-issue-key-missing\n".into()),
+            vec![Lints::JiraIssueKeyMissing],
+            vec![],
+        )
+        .with_suppressed(vec![Lints::JiraIssueKeyMissing]);
+
+        assert_eq!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="1" failures="0">
+  <testsuite name="COMMIT_EDITMSG" tests="1" failures="0">
+    <testcase name="jira-issue-key-missing" classname="COMMIT_EDITMSG">
+      <skipped message="Skipped by the commit message"/>
+    </testcase>
+  </testsuite>
+</testsuites>"#,
+            format_junit(&[report])
+        );
+    }
+}
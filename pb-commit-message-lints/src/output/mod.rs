@@ -0,0 +1,341 @@
+use std::convert::TryFrom;
+
+pub use crate::output::{
+    diagnostic::format_diagnostic,
+    diff::format_fix,
+    explanation::format_explanation,
+    json::JSON_SCHEMA_VERSION,
+};
+use crate::{
+    errors::PbCommitMessageLintsError,
+    external::commits::Commit,
+    lints::{
+        lint,
+        run_plugins,
+        run_scripts,
+        CommitMessage,
+        LintCode,
+        LintConfiguration,
+        LintProblem,
+        ParseConfiguration,
+        PluginContext,
+        Severity,
+    },
+    output::{
+        checkstyle::format_checkstyle,
+        json::format_json,
+        junit::format_junit,
+        sarif::format_sarif,
+    },
+};
+
+/// The formats that lint results can be written in
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Format {
+    Text,
+    Json,
+    Sarif,
+    Junit,
+    Checkstyle,
+}
+
+const FORMAT_TEXT: &str = "text";
+const FORMAT_JSON: &str = "json";
+const FORMAT_SARIF: &str = "sarif";
+const FORMAT_JUNIT: &str = "junit";
+const FORMAT_CHECKSTYLE: &str = "checkstyle";
+
+impl Format {
+    pub fn iterator() -> impl Iterator<Item = Format> {
+        static FORMATS: [Format; 5] = [
+            Format::Text,
+            Format::Json,
+            Format::Sarif,
+            Format::Junit,
+            Format::Checkstyle,
+        ];
+        FORMATS.iter().copied()
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Format::Text => FORMAT_TEXT,
+            Format::Json => FORMAT_JSON,
+            Format::Sarif => FORMAT_SARIF,
+            Format::Junit => FORMAT_JUNIT,
+            Format::Checkstyle => FORMAT_CHECKSTYLE,
+        }
+    }
+
+    /// Whether this format is meant to be read by other tools, and so should
+    /// be written even when there are no problems
+    #[must_use]
+    pub fn is_machine_readable(self) -> bool {
+        self != Format::Text
+    }
+
+    /// Write out the results of linting
+    ///
+    /// # Errors
+    ///
+    /// If the results can't be serialised
+    pub fn format(self, reports: &[Report]) -> Result<String, PbCommitMessageLintsError> {
+        match self {
+            Format::Text => Ok(format_text(reports)),
+            Format::Json => format_json(reports),
+            Format::Sarif => format_sarif(reports),
+            Format::Junit => Ok(format_junit(reports)),
+            Format::Checkstyle => Ok(format_checkstyle(reports)),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl TryFrom<&str> for Format {
+    type Error = PbCommitMessageLintsError;
+
+    fn try_from(from: &str) -> Result<Self, Self::Error> {
+        Format::iterator()
+            .find(|format| format.name() == from)
+            .ok_or_else(|| PbCommitMessageLintsError::FormatNotFoundError(from.into()))
+    }
+}
+
+/// The results of linting a single commit message
+#[derive(Debug, Eq, PartialEq)]
+pub struct Report {
+    origin: String,
+    commit_message: CommitMessage,
+    lints: Vec<String>,
+    problems: Vec<LintProblem>,
+    suppressed: Vec<String>,
+}
+
+impl Report {
+    /// The origin is where the commit message came from, like the path to
+    /// the file or the commit id, and the lints are the ones that ran, by
+    /// name
+    #[must_use]
+    pub fn new<T: Into<String>>(
+        origin: &str,
+        commit_message: CommitMessage,
+        lints: Vec<T>,
+        problems: Vec<LintProblem>,
+    ) -> Report {
+        Report {
+            origin: origin.into(),
+            commit_message,
+            lints: lints.into_iter().map(Into::into).collect(),
+            problems,
+            suppressed: vec![],
+        }
+    }
+
+    /// Record the lints the commit message skipped, so they can be audited
+    #[must_use]
+    pub fn with_suppressed<T: Into<String>>(mut self, suppressed: Vec<T>) -> Report {
+        self.suppressed = suppressed.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Lint a commit that has already been made, using its id as the origin,
+    /// running any scripts and plugins as well as the other lints
+    ///
+    /// # Errors
+    ///
+    /// If any of the scripts or plugins fail
+    pub fn from_commit(
+        commit: &Commit,
+        parse_configuration: &ParseConfiguration,
+        lint_configuration: &LintConfiguration,
+        context: &PluginContext,
+    ) -> Result<Report, PbCommitMessageLintsError> {
+        let commit_message = CommitMessage::parse(commit.message().into(), parse_configuration);
+        let mut problems = lint(&commit_message, lint_configuration);
+        problems.extend(run_scripts(&commit_message, lint_configuration, context)?);
+        problems.extend(run_plugins(&commit_message, lint_configuration, context)?);
+
+        let suppressed = lint_configuration.suppressed(&commit_message);
+
+        Ok(Report::new(
+            commit.id(),
+            commit_message,
+            lint_configuration.names(),
+            problems,
+        )
+        .with_suppressed(suppressed))
+    }
+
+    #[must_use]
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    #[must_use]
+    pub fn commit_message(&self) -> &CommitMessage {
+        &self.commit_message
+    }
+
+    #[must_use]
+    pub fn lints(&self) -> &[String] {
+        &self.lints
+    }
+
+    #[must_use]
+    pub fn problems(&self) -> &[LintProblem] {
+        &self.problems
+    }
+
+    #[must_use]
+    pub fn suppressed(&self) -> &[String] {
+        &self.suppressed
+    }
+
+    /// The code of the last error found, if there were any, as warnings
+    /// shouldn't fail anything
+    #[must_use]
+    pub fn code(&self) -> Option<LintCode> {
+        self.problems
+            .iter()
+            .rev()
+            .find(|problem| problem.severity() == Severity::Error)
+            .map(LintProblem::code)
+    }
+}
+
+/// Diagnostics for every problem, followed by a note for every lint that a
+/// commit message skipped
+fn format_text(reports: &[Report]) -> String {
+    let mut sections = reports
+        .iter()
+        .flat_map(|report| {
+            report.problems().iter().map(move |problem| {
+                format_diagnostic(report.origin(), report.commit_message(), problem)
+            })
+        })
+        .collect::<Vec<_>>();
+    let notes = reports
+        .iter()
+        .flat_map(|report| {
+            report
+                .suppressed()
+                .iter()
+                .map(move |lint| format!("note: {} skips {}\n", report.origin(), lint))
+        })
+        .collect::<String>();
+
+    if !notes.is_empty() {
+        sections.push(notes);
+    }
+
+    sections.join("\n")
+}
+
+/// Split the help of a problem into the headline, and the rest of it
+fn split_help(help: &str) -> (&str, &str) {
+    let help = help.trim();
+
+    match help.find('\n') {
+        Some(index) => (&help[..index], help[index..].trim()),
+        None => (help, ""),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            '&' => "&amp;".into(),
+            '<' => "&lt;".into(),
+            '>' => "&gt;".into(),
+            '"' => "&quot;".into(),
+            '\'' => "&apos;".into(),
+            '\n' => "&#10;".into(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+mod checkstyle;
+
+mod diagnostic;
+
+mod diff;
+
+mod explanation;
+
+mod json;
+
+mod junit;
+
+mod sarif;
+
+#[cfg(test)]
+mod tests_format {
+    use std::convert::TryFrom;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{errors::PbCommitMessageLintsError, output::Format};
+
+    #[test]
+    fn it_can_be_created_from_its_name() {
+        assert_eq!(Ok(Format::Sarif), Format::try_from("sarif"));
+    }
+
+    #[test]
+    fn unknown_formats_are_an_error() {
+        assert_eq!(
+            Err(PbCommitMessageLintsError::FormatNotFoundError(
+                "banana".into()
+            )),
+            Format::try_from("banana")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_format_text {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        lints::{CommitMessage, Lints},
+        output::{Format, Report},
+    };
+
+    #[test]
+    fn skipped_lints_are_noted() {
+        let report = Report::new(
+            "7f8e2a1",
+            CommitMessage::new("Release 1.0.0\n\nLint-Skip: jira-issue-key-missing\n".into()),
+            vec![Lints::JiraIssueKeyMissing],
+            vec![],
+        )
+        .with_suppressed(vec![Lints::JiraIssueKeyMissing]);
+
+        assert_eq!(
+            Ok("note: 7f8e2a1 skips jira-issue-key-missing\n".into()),
+            Format::Text.format(&[report])
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_escape_xml {
+    use pretty_assertions::assert_eq;
+
+    use crate::output::escape_xml;
+
+    #[test]
+    fn markup_is_escaped() {
+        assert_eq!(
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;&#10;",
+            escape_xml("<a href=\"x\">Tom & Jerry's</a>\n")
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use serde_json::{json, Value};
+
+use crate::{
+    errors::PbCommitMessageLintsError,
+    lints::{Label, LintProblem},
+    output::{split_help, Report},
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "pb-git-hooks";
+const TOOL_URI: &str = "https://github.com/PurpleBooth/pb-git-hooks";
+
+pub(crate) fn format_sarif(reports: &[Report]) -> Result<String, PbCommitMessageLintsError> {
+    let mut rules: Vec<&str> = vec![];
+    for lint in reports.iter().flat_map(Report::lints) {
+        if !rules.contains(&lint.as_str()) {
+            rules.push(lint);
+        }
+    }
+    let rules = &rules;
+
+    serde_json::to_string_pretty(&json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_URI,
+                    "rules": rules.iter().map(|lint| json!({ "id": lint })).collect::<Vec<_>>(),
+                }
+            },
+            "results": reports
+                .iter()
+                .flat_map(|report| report
+                    .problems()
+                    .iter()
+                    .map(move |problem| sarif_result(report.origin(), rules, problem)))
+                .collect::<Vec<_>>(),
+        }]
+    }))
+    .map_err(PbCommitMessageLintsError::from)
+}
+
+fn sarif_result(origin: &str, rules: &[&str], problem: &LintProblem) -> Value {
+    let lint = problem.lint_name();
+    let (summary, _) = split_help(problem.help());
+    let mut labels = problem.labels().iter();
+    let primary = labels.next();
+
+    json!({
+        "ruleId": lint,
+        "ruleIndex": rules.iter().position(|rule| *rule == lint),
+        "level": problem.severity().label(),
+        "message": {
+            "text": summary,
+            "markdown": problem.help().trim(),
+        },
+        "locations": [sarif_location(origin, primary)],
+        "relatedLocations": labels
+            .map(|label| sarif_location(origin, Some(label)))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn sarif_location(origin: &str, label: Option<&Label>) -> Value {
+    let artifact = json!({ "uri": origin });
+
+    match label {
+        Some(label) => json!({
+            "physicalLocation": {
+                "artifactLocation": artifact,
+                "region": {
+                    "startLine": label.span().line(),
+                    "startColumn": label.span().start_column(),
+                    "endColumn": label.span().end_column(),
+                }
+            },
+            "message": { "text": label.message() },
+        }),
+        None => json!({
+            "physicalLocation": {
+                "artifactLocation": artifact,
+            }
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests_format_sarif {
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+
+    use crate::{
+        lints::{CommitMessage, Label, LabelKind, LintCode, LintProblem, Lints, Span},
+        output::{sarif::format_sarif, Report},
+    };
+
+    #[test]
+    fn problems_are_results_of_the_rule_for_their_lint() {
+        let report = Report::new(
+            "COMMIT_EDITMSG",
+            CommitMessage::new("An example commit\n".into()),
+            vec![Lints::DuplicatedTrailers, Lints::JiraIssueKeyMissing],
+            vec![
+                LintProblem::new("Missing\n\nAdd it\n".into(), LintCode::JiraIssueKeyMissing)
+                    .with_label(Label::new(
+                        Span::new(1, 1, 18),
+                        LabelKind::Suggestion,
+                        "add it here",
+                    )),
+            ],
+        );
+
+        let actual: Value = serde_json::from_str(&format_sarif(&[report]).unwrap()).unwrap();
+
+        assert_eq!(
+            json!([{ "id": "duplicated-trailers" }, { "id": "jira-issue-key-missing" }]),
+            actual["runs"][0]["tool"]["driver"]["rules"]
+        );
+        assert_eq!(
+            json!([{
+                "ruleId": "jira-issue-key-missing",
+                "ruleIndex": 1,
+                "level": "error",
+                "message": { "text": "Missing", "markdown": "Missing\n\nAdd it" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "COMMIT_EDITMSG" },
+                        "region": { "startLine": 1, "startColumn": 1, "endColumn": 18 }
+                    },
+                    "message": { "text": "add it here" }
+                }],
+                "relatedLocations": []
+            }]),
+            actual["runs"][0]["results"]
+        );
+    }
+}
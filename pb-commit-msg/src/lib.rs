@@ -0,0 +1,253 @@
+use std::{env, fs, process};
+
+use clap::{crate_authors, crate_version, App, Arg};
+
+use crate::PbCommitMessageError::PbCommitMessageLints;
+use pb_commit_message_lints::{
+    errors::PbCommitMessageLintsError,
+    external::{
+        chain::{run_chain, ChainFailure},
+        vcs::Layered,
+    },
+    lints::{
+        apply_fixes,
+        get_fix_configuration,
+        get_lint_configuration,
+        get_parse_configuration,
+        lint,
+        run_plugins,
+        run_scripts,
+        CommitMessage,
+        LintProblem,
+        PluginContext,
+    },
+    output::{format_fix, Format, Report},
+};
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
+
+const COMMIT_FILE_PATH_NAME: &str = "commit-file-path";
+const FORMAT_NAME: &str = "format";
+const FIX_NAME: &str = "fix";
+const HOOK_NAME: &str = "commit-msg";
+
+fn display_err_and_exit<T>(error: &PbCommitMessageError) -> T {
+    eprintln!("{}", error);
+    process::exit(1);
+}
+
+/// Check a commit message, as the `commit-msg` hook
+///
+/// The arguments start with the name it was called by, like those from
+/// [`std::env::args`]
+pub fn run(args: &[String]) {
+    let matches = app().get_matches_from(args);
+
+    let commit_file_path = matches
+        .value_of(COMMIT_FILE_PATH_NAME)
+        .map(PathBuf::from)
+        .expect("Expected file path name");
+
+    let current_dir = env::current_dir()
+        .map_err(|err| PbCommitMessageError::new_io("$PWD".into(), &err))
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let git_config = Layered::try_from(current_dir.clone())
+        .map_err(PbCommitMessageError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let parse_configuration = get_parse_configuration(&git_config)
+        .map_err(PbCommitMessageError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let mut commit_message =
+        CommitMessage::from_path(commit_file_path.clone(), &parse_configuration)
+            .map_err(PbCommitMessageError::from)
+            .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let format = matches
+        .value_of(FORMAT_NAME)
+        .map(Format::try_from)
+        .expect("Expected a format")
+        .map_err(PbCommitMessageError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let lint_configuration = get_lint_configuration(&git_config)
+        .map_err(PbCommitMessageError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+    let mut problems = lint(&commit_message, &lint_configuration);
+
+    let fix = matches.is_present(FIX_NAME)
+        || get_fix_configuration(&git_config)
+            .map_err(PbCommitMessageError::from)
+            .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    if fix {
+        if let Some(fixed) = fix_problems(&commit_file_path, &commit_message, &problems)
+            .unwrap_or_else(|err| display_err_and_exit(&err))
+        {
+            commit_message = CommitMessage::parse(fixed, &parse_configuration);
+            problems = lint(&commit_message, &lint_configuration);
+        }
+    }
+
+    let context = PluginContext::discover(&current_dir);
+    problems.extend(
+        run_scripts(&commit_message, &lint_configuration, &context)
+            .map_err(PbCommitMessageError::from)
+            .unwrap_or_else(|err| display_err_and_exit(&err)),
+    );
+    problems.extend(
+        run_plugins(&commit_message, &lint_configuration, &context)
+            .map_err(PbCommitMessageError::from)
+            .unwrap_or_else(|err| display_err_and_exit(&err)),
+    );
+
+    let has_problems = !problems.is_empty();
+    let suppressed = lint_configuration.suppressed(&commit_message);
+    let report = Report::new(
+        &commit_file_path.to_string_lossy(),
+        commit_message,
+        lint_configuration.names(),
+        problems,
+    )
+    .with_suppressed(suppressed);
+    let exit_code = report.code();
+
+    let output = format
+        .format(&[report])
+        .map_err(PbCommitMessageError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    if format.is_machine_readable() {
+        println!("{}", output);
+    } else if has_problems {
+        eprintln!("{}", output);
+    }
+
+    let chain_failures = run_chain(
+        HOOK_NAME,
+        &current_dir,
+        args.get(1..).unwrap_or_default(),
+        None,
+    )
+    .map_err(PbCommitMessageError::from)
+    .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    for failure in &chain_failures {
+        eprintln!("{}", failure);
+    }
+
+    if let Some(exit_code) = exit_code
+        .map(|code| code as i32)
+        .or_else(|| chain_failures.first().map(ChainFailure::code))
+    {
+        process::exit(exit_code);
+    }
+}
+
+/// Write the fixed commit message back to the file, showing each fix as it
+/// goes, so the message can be linted again
+fn fix_problems(
+    commit_file_path: &PathBuf,
+    commit_message: &CommitMessage,
+    problems: &[LintProblem],
+) -> Result<Option<String>, PbCommitMessageError> {
+    let fixes = problems
+        .iter()
+        .filter_map(LintProblem::fix)
+        .collect::<Vec<_>>();
+
+    if fixes.is_empty() {
+        return Ok(None);
+    }
+
+    for fix in &fixes {
+        eprintln!(
+            "{}",
+            format_fix(&commit_file_path.to_string_lossy(), commit_message, fix)
+        );
+    }
+
+    let fixed = apply_fixes(commit_message, &fixes);
+    fs::write(commit_file_path, &fixed).map_err(|err| {
+        PbCommitMessageError::new_write(commit_file_path.to_string_lossy().into(), &err)
+    })?;
+
+    Ok(Some(fixed))
+}
+
+fn app() -> App<'static, 'static> {
+    App::new(env!("CARGO_PKG_NAME"))
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .arg(
+            Arg::with_name(COMMIT_FILE_PATH_NAME)
+                .help(
+                    "Path to a temporary file that contains the commit message written by the \
+                     developer",
+                )
+                .index(1)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(FORMAT_NAME)
+                .long(FORMAT_NAME)
+                .help("How to write out any problems that are found")
+                .possible_values(&Format::iterator().map(Format::name).collect::<Vec<_>>())
+                .default_value(Format::Text.name()),
+        )
+        .arg(
+            Arg::with_name(FIX_NAME)
+                .long(FIX_NAME)
+                .help("Fix any problems that can be fixed automatically"),
+        )
+}
+
+#[derive(Debug)]
+enum PbCommitMessageError {
+    PbCommitMessageLints(PbCommitMessageLintsError),
+    Io(String, String),
+    Write(String, String),
+}
+
+impl PbCommitMessageError {
+    fn new_io(location: String, error: &std::io::Error) -> PbCommitMessageError {
+        PbCommitMessageError::Io(location, format!("{}", error))
+    }
+
+    fn new_write(location: String, error: &std::io::Error) -> PbCommitMessageError {
+        PbCommitMessageError::Write(location, format!("{}", error))
+    }
+}
+
+impl Display for PbCommitMessageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PbCommitMessageLints(error) => write!(f, "{}", error),
+            PbCommitMessageError::Io(file_source, error) => write!(
+                f,
+                "Failed to read git config from `{}`:\n{}",
+                file_source, error
+            ),
+            PbCommitMessageError::Write(file_source, error) => write!(
+                f,
+                "Failed to write the fixed commit message to `{}`:\n{}",
+                file_source, error
+            ),
+        }
+    }
+}
+
+impl From<PbCommitMessageLintsError> for PbCommitMessageError {
+    fn from(err: PbCommitMessageLintsError) -> Self {
+        PbCommitMessageLints(err)
+    }
+}
+
+impl Error for PbCommitMessageError {}
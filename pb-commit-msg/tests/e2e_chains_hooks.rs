@@ -0,0 +1,142 @@
+use std::{fs, io::Write, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir, write_hook};
+
+const VALID_MESSAGE: &str = "An example commit\n\nThis is an example commit\n";
+
+#[test]
+fn the_local_hook_is_given_the_same_arguments() {
+    let working_dir = setup_working_dir();
+    let arguments_path = working_dir.join("arguments");
+    write_hook(
+        &working_dir,
+        "commit-msg.local",
+        &format!("echo \"$@\" > {}", arguments_path.display()),
+    );
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", VALID_MESSAGE).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!(
+        format!("{}\n", commit_path.path().display()),
+        fs::read_to_string(arguments_path).unwrap()
+    );
+}
+
+#[test]
+fn chained_failures_are_reported_separately() {
+    let working_dir = setup_working_dir();
+    let hook = write_hook(
+        &working_dir,
+        "commit-msg.local",
+        "echo 'Not today' >&2; exit 7",
+    );
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", VALID_MESSAGE).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(
+        &output,
+        "",
+        &format!(
+            "Not today\nerror: The chained hook `{}` failed with exit code 7\n",
+            hook.display()
+        ),
+        false,
+    );
+    assert_eq!(Some(7), output.status.code());
+}
+
+#[test]
+fn hooks_can_be_chained_in_config() {
+    let working_dir = setup_working_dir();
+    let hook = write_hook(&working_dir, "framework-commit-msg", "exit 1");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.hooks.commit-msg.chain")
+        .arg(&hook)
+        .output()
+        .expect("failed to execute process");
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", VALID_MESSAGE).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(
+        &output,
+        "",
+        &format!(
+            "error: The chained hook `{}` failed with exit code 1\n",
+            hook.display()
+        ),
+        false,
+    );
+}
+
+#[test]
+fn hooks_chained_in_the_repository_file_are_not_run() {
+    let working_dir = setup_working_dir();
+    let marker_path = working_dir.join("marker");
+    let hook = write_hook(
+        &working_dir,
+        "framework-commit-msg",
+        &format!("touch {}", marker_path.display()),
+    );
+    fs::write(
+        working_dir.join(".pb-git-hooks.toml"),
+        format!("[hooks.commit-msg]\nchain = \"{}\"\n", hook.display()),
+    )
+    .unwrap();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", VALID_MESSAGE).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true);
+    assert!(!marker_path.exists());
+}
+
+#[test]
+fn lint_failures_take_priority() {
+    let working_dir = setup_working_dir();
+    write_hook(&working_dir, "commit-msg.local", "exit 7");
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(
+        commit_path,
+        "{}\nSigned-off-by: Billie <billie@example.com>\nSigned-off-by: Billie \
+         <billie@example.com>\n",
+        VALID_MESSAGE
+    )
+    .unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_eq!(Some(3), output.status.code());
+}
@@ -0,0 +1,116 @@
+use std::{io::Write, path::Path, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn set_config(working_dir: &Path, key: &str, value: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .arg(value)
+        .output()
+        .expect("failed to execute process");
+}
+
+#[test]
+fn conventional_commits_pass() {
+    let input = r#"feat(search): add a search box
+
+Searches the titles of every page
+"#;
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.conventional-commits", "error");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn types_that_are_not_allowed_fail() {
+    let input = r#"feature: add a search box
+"#;
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.conventional-commits", "error");
+    set_config(
+        &working_dir,
+        "pb.lint.conventional-commits.types",
+        "feat fix",
+    );
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stderr = format!(
+        r#"error: The type `feature` isn't one of the allowed types
+ --> {}:1:1
+  |
+1 | feature: add a search box
+  | ^^^^^^^ not an allowed type
+  |
+
+You can fix this by using one of `feat`, `fix`
+
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn breaking_change_footers_need_the_marker() {
+    let input = r#"feat: remove the v1 endpoints
+
+BREAKING CHANGE: use the v2 endpoints instead
+"#;
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "conventional");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("isn't marked as a breaking change"));
+}
+
+#[test]
+fn breaking_changes_do_not_need_a_footer() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "conventional");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    writeln!(commit_path, "feat!: remove the v1 endpoints").unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
+}
@@ -0,0 +1,132 @@
+use std::{io::Write, path::Path, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn set_config(working_dir: &Path, key: &str, value: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .arg(value)
+        .output()
+        .expect("failed to execute process");
+}
+
+#[test]
+fn forbidden_text_fails() {
+    let input = r#"Quick hotfix for the login page
+
+This fixes the redirect after logging in
+"#;
+    let working_dir = setup_working_dir();
+    set_config(
+        &working_dir,
+        "pb.customlint.no-hotfix.pattern",
+        "(?i)hotfix",
+    );
+    set_config(&working_dir, "pb.customlint.no-hotfix.mode", "forbid");
+    set_config(&working_dir, "pb.customlint.no-hotfix.target", "subject");
+    set_config(
+        &working_dir,
+        "pb.customlint.no-hotfix.help",
+        "Describe what the change does instead of calling it a hotfix",
+    );
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stderr = format!(
+        r#"error: Describe what the change does instead of calling it a hotfix
+ --> {}:1:7
+  |
+1 | Quick hotfix for the login page
+  |       ^^^^^^ not allowed here
+  |
+
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn forbidden_text_elsewhere_passes() {
+    let input = r#"Fix the redirect after logging in
+
+This replaces the hotfix from last week
+"#;
+    let working_dir = setup_working_dir();
+    set_config(
+        &working_dir,
+        "pb.customlint.no-hotfix.pattern",
+        "(?i)hotfix",
+    );
+    set_config(&working_dir, "pb.customlint.no-hotfix.mode", "forbid");
+    set_config(&working_dir, "pb.customlint.no-hotfix.target", "subject");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn disabled_custom_lints_do_not_run() {
+    let input = r#"Quick hotfix for the login page
+"#;
+    let working_dir = setup_working_dir();
+    set_config(
+        &working_dir,
+        "pb.customlint.no-hotfix.pattern",
+        "(?i)hotfix",
+    );
+    set_config(&working_dir, "pb.customlint.no-hotfix.mode", "forbid");
+    set_config(&working_dir, "pb.lint.no-hotfix", "false");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn invalid_patterns_are_reported_with_the_lint_name() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.customlint.service-name.pattern", "[(api");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    writeln!(commit_path, "An example commit").unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("The custom lint \"service-name\" isn't valid"));
+}
@@ -28,21 +28,19 @@ Signed-off-by: Billie Thompson <email@example.com>
     assert_output(
         &output,
         "",
-        r#"An example commit
-
-This is an example commit with duplicate trailers
-
-Signed-off-by: Billie Thompson <email@example.com>
-Signed-off-by: Billie Thompson <email@example.com>
-
-
----
-
-Your commit cannot have the same name duplicated in the "Signed-off-by" field
+        &format!(
+            r#"error: Your commit cannot have the same name duplicated in the "Signed-off-by" field
+ --> {}:6:1
+  |
+6 | Signed-off-by: Billie Thompson <email@example.com>
+  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ duplicated here
+  |
 
 You can fix this by removing the duplicated field when you commit again
 
 "#,
+            commit_path.path().display()
+        ),
         false,
     )
 }
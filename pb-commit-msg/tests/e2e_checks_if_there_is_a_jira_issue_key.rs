@@ -1,4 +1,4 @@
-use std::{io::Write, process::Command};
+use std::{fs, io::Write, process::Command};
 
 use tempfile::NamedTempFile;
 
@@ -59,21 +59,185 @@ This is an example commit without the JIRA Issue Key
         vec![commit_path.path().to_str().unwrap()],
     );
 
-    let expected_stderr = r#"An example commit
+    let expected_stderr = format!(
+        r#"error: Your commit is missing a JIRA Issue Key
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding a key like `JRA-123` here
+  |
 
-This is an example commit without the JIRA Issue Key
+You can fix this by adding a key like `JRA-123` to the commit message
 
+"#,
+        commit_path.path().display()
+    );
 
----
+    assert_output(&output, "", &expected_stderr, false)
+}
 
+#[test]
+fn warned() {
+    let input = r#"An example commit
 
-Your commit is missing a JIRA Issue Key
+This is an example commit without the JIRA Issue Key
+"#;
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("warn")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stderr = format!(
+        r#"warning: Your commit is missing a JIRA Issue Key
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding a key like `JRA-123` here
+  |
 
 You can fix this by adding a key like `JRA-123` to the commit message
 
+"#,
+        commit_path.path().to_str().unwrap()
+    );
+
+    assert_output(&output, "", &expected_stderr, true)
+}
+
+#[test]
+fn skipped() {
+    let input = r#"Release 1.0.0
+
+Lint-Skip: jira-issue-key-missing
+"#;
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn skipping_is_forbidden() {
+    let input = r#"Release 1.0.0
+
+Lint-Skip: jira-issue-key-missing
+"#;
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.allow-lint-skip")
+        .arg("false")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn enabled_in_the_repo_file() {
+    let input = r#"An example commit
+
+This is an example commit without the JIRA Issue Key
+"#;
+    let working_dir = setup_working_dir();
+    fs::write(
+        working_dir.join(".pb-git-hooks.toml"),
+        "[lint]\njira-issue-key-missing = \"error\"\n",
+    )
+    .unwrap();
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn git_config_overrides_the_repo_file() {
+    let input = r#"An example commit
+
+This is an example commit without the JIRA Issue Key
 "#;
+    let working_dir = setup_working_dir();
+    fs::write(
+        working_dir.join(".pb-git-hooks.toml"),
+        "[lint]\njira-issue-key-missing = \"error\"\n",
+    )
+    .unwrap();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("off")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
 
-    assert_output(&output, "", expected_stderr, false)
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
 }
 
 #[test]
@@ -102,3 +266,114 @@ This is an example commit without the jira issue key
     );
     assert_output(&output, "", r#""#, true)
 }
+
+#[test]
+fn key_below_the_scissors_is_ignored() {
+    let input = r#"An example commit
+
+This is an example commit without the JIRA Issue Key
+# Please enter the commit message for your changes.
+# ------------------------ >8 ------------------------
+# Do not modify or remove the line above.
+diff --git a/README.md b/README.md
++JRA-123
+"#;
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn key_in_a_comment_is_ignored() {
+    let input = r#"An example commit
+
+This is an example commit without the JIRA Issue Key
+; JRA-123
+"#;
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("core.commentChar")
+        .arg(";")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn the_projects_can_be_limited() {
+    let input = r#"An example commit
+
+This is an example commit with a key from another project
+
+ABC-123
+"#;
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing.projects")
+        .arg("JRA CORE")
+        .output()
+        .expect("failed to execute process");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Your commit is missing a JIRA Issue Key"));
+}
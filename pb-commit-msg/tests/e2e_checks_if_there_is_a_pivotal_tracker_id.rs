@@ -59,15 +59,13 @@ This is an example commit without the pivotal tracker id
         vec![commit_path.path().to_str().unwrap()],
     );
 
-    let expected_stderr = r#"An example commit
-
-This is an example commit without the pivotal tracker id
-
-
----
-
-
-Your commit is missing a Pivotal Tracker Id
+    let expected_stderr = format!(
+        r#"error: Your commit is missing a Pivotal Tracker Id
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding an id like `[#12345678]` here
+  |
 
 You can fix this by adding the Id in one of the styles below to the commit message
 [Delivers #12345678]
@@ -78,9 +76,11 @@ You can fix this by adding the Id in one of the styles below to the commit messa
 [#12345678],[#12345884]
 This will address [#12345884]
 
-"#;
+"#,
+        commit_path.path().display()
+    );
 
-    assert_output(&output, "", expected_stderr, false)
+    assert_output(&output, "", &expected_stderr, false)
 }
 
 #[test]
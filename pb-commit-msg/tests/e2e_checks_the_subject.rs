@@ -0,0 +1,126 @@
+use std::{io::Write, path::Path, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn set_config(working_dir: &Path, key: &str, value: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .arg(value)
+        .output()
+        .expect("failed to execute process");
+}
+
+#[test]
+fn the_subject_lints_are_off_by_default() {
+    let input = r#"  add a search box that searches the title of every page.
+"#;
+    let working_dir = setup_working_dir();
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn subjects_longer_than_the_max_length_fail() {
+    let input = r#"fixup! Add a search box
+
+Searches the titles of every page
+"#;
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.subject-too-long", "error");
+    set_config(&working_dir, "pb.lint.subject-too-long.max-length", "10");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stderr = format!(
+        r#"error: Your commit's subject is longer than 10 characters
+ --> {}:1:18
+  |
+1 | fixup! Add a search box
+  |                  ^^^^^^ 6 characters too long
+  |
+
+You can fix this by moving some of the detail into the body
+
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn the_max_length_cannot_be_more_than_72() {
+    let input = r#"Add a search box
+"#;
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.subject-too-long", "error");
+    set_config(&working_dir, "pb.lint.subject-too-long.max-length", "100");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("\"100\" is not a number from 1 to 72"));
+}
+
+#[test]
+fn each_subject_lint_can_be_picked_on_its_own() {
+    let input = r#"add a search box.
+"#;
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.subject-ends-with-period", "error");
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stderr = format!(
+        r#"error: Your commit's subject ends with a period
+ --> {}:1:17
+  |
+1 | add a search box.
+  |                 ^ remove this period
+  |
+
+You can fix this by removing it, as the subject is a title rather than a sentence
+
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
@@ -0,0 +1,90 @@
+use std::{fs, io::Write, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+const DUPLICATED_TRAILERS: &str = r#"An example commit
+
+This is an example commit with duplicate trailers
+
+Signed-off-by: Billie Thompson <email@example.com>
+Signed-off-by: Billie Thompson <email@example.com>
+"#;
+
+const FIXED: &str = r#"An example commit
+
+This is an example commit with duplicate trailers
+
+Signed-off-by: Billie Thompson <email@example.com>
+"#;
+
+#[test]
+fn fixes_are_applied_and_shown() {
+    let working_dir = setup_working_dir();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", DUPLICATED_TRAILERS).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec!["--fix", commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stderr = format!(
+        r#"fixed: Remove the duplicated trailer
+ --> {}:6
+  |
+6 - Signed-off-by: Billie Thompson <email@example.com>
+  |
+
+"#,
+        commit_path.path().to_str().unwrap()
+    );
+
+    assert_output(&output, "", &expected_stderr, true);
+    assert_eq!(FIXED, fs::read_to_string(commit_path.path()).unwrap());
+}
+
+#[test]
+fn fixes_can_be_turned_on_in_config() {
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.fix")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", DUPLICATED_TRAILERS).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(output.status.success());
+    assert_eq!(FIXED, fs::read_to_string(commit_path.path()).unwrap());
+}
+
+#[test]
+fn nothing_changes_without_fixing() {
+    let working_dir = setup_working_dir();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", DUPLICATED_TRAILERS).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+    assert_eq!(
+        DUPLICATED_TRAILERS,
+        fs::read_to_string(commit_path.path()).unwrap()
+    );
+}
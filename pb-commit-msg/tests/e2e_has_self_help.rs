@@ -12,12 +12,17 @@ Billie Thompson <billie+pb-commit-msg@billiecodes.com>
 Validate the commit message that a user has input
 
 USAGE:
-    pb-commit-msg <commit-file-path>
+    pb-commit-msg [FLAGS] [OPTIONS] <commit-file-path>
 
 FLAGS:
+        --fix        Fix any problems that can be fixed automatically
     -h, --help       Prints help information
     -V, --version    Prints version information
 
+OPTIONS:
+        --format <format>    How to write out any problems that are found [default: text]  [possible values: text, json,
+                             sarif, junit, checkstyle]
+
 ARGS:
     <commit-file-path>    Path to a temporary file that contains the commit message written by the developer
 "#,
@@ -40,12 +45,17 @@ Billie Thompson <billie+pb-commit-msg@billiecodes.com>
 Validate the commit message that a user has input
 
 USAGE:
-    pb-commit-msg <commit-file-path>
+    pb-commit-msg [FLAGS] [OPTIONS] <commit-file-path>
 
 FLAGS:
+        --fix        Fix any problems that can be fixed automatically
     -h, --help       Prints help information
     -V, --version    Prints version information
 
+OPTIONS:
+        --format <format>    How to write out any problems that are found [default: text]  [possible values: text, json,
+                             sarif, junit, checkstyle]
+
 ARGS:
     <commit-file-path>    Path to a temporary file that contains the commit message written by the developer
 "#,
@@ -63,7 +73,7 @@ fn invalid_command() {
     let expected = r#"error: Found argument '--banana' which wasn't expected, or isn't valid in this context
 
 USAGE:
-    pb-commit-msg <commit-file-path>
+    pb-commit-msg [FLAGS] [OPTIONS] <commit-file-path>
 
 For more information try --help
 "#;
@@ -0,0 +1,128 @@
+#![cfg(unix)]
+
+use std::{fs, io::Write, path::Path, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn set_config(working_dir: &Path, key: &str, value: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .arg(value)
+        .output()
+        .expect("failed to execute process");
+}
+
+fn run_with_plugin(working_dir: &Path, command: &str) -> (std::process::Output, NamedTempFile) {
+    set_config(working_dir, "pb.plugin.check-service.command", command);
+
+    let mut commit_path = NamedTempFile::new().unwrap();
+    writeln!(commit_path, "An example commit").unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir.to_path_buf(),
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    (output, commit_path)
+}
+
+#[test]
+fn problems_from_plugins_are_shown() {
+    let working_dir = setup_working_dir();
+    let (output, commit_path) = run_with_plugin(
+        &working_dir,
+        r#"grep -q '"branch":"' && echo '[{"help": "Mention the service this changes", "location": {"line": 1, "message": "in here"}}]'"#,
+    );
+
+    let expected_stderr = format!(
+        r#"error: Mention the service this changes
+ --> {}:1:1
+  |
+1 | An example commit
+  | ^^^^^^^^^^^^^^^^^ in here
+  |
+
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn plugins_without_problems_pass() {
+    let working_dir = setup_working_dir();
+    let (output, _commit_path) = run_with_plugin(&working_dir, "cat > /dev/null; echo '[]'");
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn failing_plugins_are_reported() {
+    let working_dir = setup_working_dir();
+    let (output, _commit_path) =
+        run_with_plugin(&working_dir, "echo 'No service list found' >&2; exit 2");
+
+    assert_output(
+        &output,
+        "",
+        "The plugin \"check-service\" failed with exit code 2:\nNo service list found\n",
+        false,
+    )
+}
+
+#[test]
+fn slow_plugins_are_stopped() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.plugin.check-service.timeout", "1");
+    let (output, _commit_path) = run_with_plugin(&working_dir, "exec sleep 10");
+
+    assert_output(
+        &output,
+        "",
+        "The plugin \"check-service\" didn't finish within 1 seconds, so it was stopped\n",
+        false,
+    )
+}
+
+#[test]
+fn malformed_output_is_reported() {
+    let working_dir = setup_working_dir();
+    let (output, _commit_path) = run_with_plugin(&working_dir, "echo 'Looks good to me'");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("The plugin \"check-service\" didn't write a list of problems as JSON:\n"));
+}
+
+#[test]
+fn plugins_in_the_repository_file_are_not_run() {
+    let working_dir = setup_working_dir();
+    let marker_path = working_dir.join("marker");
+    fs::write(
+        working_dir.join(".pb-git-hooks.toml"),
+        format!(
+            "[plugin.check-service]\ncommand = \"touch {}\"\n",
+            marker_path.display()
+        ),
+    )
+    .unwrap();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    writeln!(commit_path, "An example commit").unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    assert_output(&output, "", "", true);
+    assert!(!marker_path.exists());
+}
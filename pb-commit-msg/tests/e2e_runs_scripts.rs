@@ -0,0 +1,89 @@
+use std::{fs, io::Write, path::Path, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+const REVERT_NEEDS_A_REASON: &str = r#"
+if commit.subject.starts_with("Revert") && !commit.body.contains("because") {
+    [#{ help: "Say why the commit is being reverted", location: #{ line: 1 } }]
+}
+"#;
+
+fn add_script(working_dir: &Path, source: &str) {
+    fs::create_dir_all(working_dir.join("lints")).unwrap();
+    fs::write(working_dir.join("lints/revert-reason.rhai"), source).unwrap();
+
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.script.revert-reason.path")
+        .arg("lints/revert-reason.rhai")
+        .output()
+        .expect("failed to execute process");
+}
+
+fn run_hook(working_dir: &Path, input: &str) -> (std::process::Output, NamedTempFile) {
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir.to_path_buf(),
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    );
+
+    (output, commit_path)
+}
+
+#[test]
+fn problems_from_scripts_are_shown() {
+    let working_dir = setup_working_dir();
+    add_script(&working_dir, REVERT_NEEDS_A_REASON);
+
+    let (output, commit_path) = run_hook(&working_dir, "Revert the login page\n\nIt broke\n");
+
+    let expected_stderr = format!(
+        r#"error: Say why the commit is being reverted
+ --> {}:1:1
+  |
+1 | Revert the login page
+  | ^^^^^^^^^^^^^^^^^^^^^
+  |
+
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn scripts_without_problems_pass() {
+    let working_dir = setup_working_dir();
+    add_script(&working_dir, REVERT_NEEDS_A_REASON);
+
+    let (output, _commit_path) = run_hook(
+        &working_dir,
+        "Revert the login page\n\nThis is because it broke\n",
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn scripts_over_their_budget_are_stopped() {
+    let working_dir = setup_working_dir();
+    add_script(&working_dir, "loop {}");
+
+    let (output, _commit_path) = run_hook(&working_dir, "An example commit\n");
+
+    assert_output(
+        &output,
+        "",
+        "The script \"revert-reason\" used more than its budget of 100000 operations, so it was \
+         stopped\n",
+        false,
+    )
+}
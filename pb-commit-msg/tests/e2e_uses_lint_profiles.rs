@@ -0,0 +1,92 @@
+use std::{io::Write, path::Path, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn set_config(working_dir: &Path, key: &str, value: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .arg(value)
+        .output()
+        .expect("failed to execute process");
+}
+
+fn run_commit_msg(working_dir: &Path, input: &str) -> std::process::Output {
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    pb_hook_test_helper::run_hook(
+        &working_dir.to_path_buf(),
+        "pb-commit-msg",
+        vec![commit_path.path().to_str().unwrap()],
+    )
+}
+
+#[test]
+fn the_profile_enables_its_lints() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "jira-team");
+
+    let output = run_commit_msg(
+        &working_dir,
+        "An example commit\n\nThis is an example commit without the Jira Issue Key\n",
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("missing a JIRA Issue Key"));
+}
+
+#[test]
+fn lints_can_be_changed_on_top_of_the_profile() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "jira-team");
+    set_config(&working_dir, "pb.lint.jira-issue-key-missing", "off");
+
+    let output = run_commit_msg(
+        &working_dir,
+        "An example commit\n\nThis is an example commit without the Jira Issue Key\n",
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn profiles_can_be_defined_in_config() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "platform");
+    set_config(
+        &working_dir,
+        "pb.profile.platform.jira-issue-key-missing",
+        "error",
+    );
+    set_config(
+        &working_dir,
+        "pb.profile.platform.jira-issue-key-missing.projects",
+        "PLAT",
+    );
+
+    let passing = run_commit_msg(&working_dir, "An example commit\n\nPart of PLAT-123\n");
+    let failing = run_commit_msg(&working_dir, "An example commit\n\nPart of JRA-123\n");
+
+    assert_output(&passing, "", "", true);
+    assert!(!failing.status.success());
+}
+
+#[test]
+fn unknown_profiles_are_an_error() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "platform");
+
+    let output = run_commit_msg(&working_dir, "An example commit\n");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("There's no profile called \"platform\""));
+}
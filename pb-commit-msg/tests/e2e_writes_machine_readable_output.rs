@@ -0,0 +1,105 @@
+use std::io::Write;
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+#[test]
+fn json_lists_the_problems() {
+    let input = r#"An example commit
+
+This is an example commit with duplicate trailers
+
+Signed-off-by: Billie Thompson <email@example.com>
+Signed-off-by: Billie Thompson <email@example.com>
+"#;
+    let working_dir = setup_working_dir();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec!["--format", "json", commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stdout = format!(
+        r#"{{
+  "schema_version": 1,
+  "results": [
+    {{
+      "origin": "{}",
+      "lints": [
+        "duplicated-trailers"
+      ],
+      "problems": [
+        {{
+          "lint": "duplicated-trailers",
+          "code": 3,
+          "severity": "error",
+          "help": "Your commit cannot have the same name duplicated in the \"Signed-off-by\" field\n\nYou can fix this by removing the duplicated field when you commit again\n",
+          "labels": [
+            {{
+              "kind": "problem",
+              "line": 6,
+              "start_column": 1,
+              "end_column": 51,
+              "message": "duplicated here"
+            }}
+          ]
+        }}
+      ],
+      "suppressed": []
+    }}
+  ]
+}}
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, &expected_stdout, "", false)
+}
+
+#[test]
+fn machine_readable_output_is_written_when_there_are_no_problems() {
+    let input = r#"An example commit
+
+This is an example commit
+"#;
+    let working_dir = setup_working_dir();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", input).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec!["--format", "junit", commit_path.path().to_str().unwrap()],
+    );
+
+    let expected_stdout = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites tests="1" failures="0">
+  <testsuite name="{0}" tests="1" failures="0">
+    <testcase name="duplicated-trailers" classname="{0}"/>
+  </testsuite>
+</testsuites>
+"#,
+        commit_path.path().display()
+    );
+
+    assert_output(&output, &expected_stdout, "", true)
+}
+
+#[test]
+fn unknown_formats_are_rejected() {
+    let working_dir = setup_working_dir();
+    let commit_path = NamedTempFile::new().unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-commit-msg",
+        vec!["--format", "banana", commit_path.path().to_str().unwrap()],
+    );
+
+    assert!(!output.status.success());
+}
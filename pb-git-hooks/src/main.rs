@@ -3,24 +3,100 @@ use std::{
     env,
     error::Error,
     fmt::{Display, Formatter},
+    path::{Path, PathBuf},
     process,
 };
 
-use clap::{crate_authors, crate_version, App, Arg, ArgMatches};
+use clap::{crate_authors, crate_version, App, AppSettings, Arg, ArgMatches};
 use git2::{Config, Repository};
+use serde_json::{json, Value};
 
 use pb_commit_message_lints::{
     errors::PbCommitMessageLintsError,
-    external::vcs::{Git2, Vcs},
-    lints::{set_lint_status, Lints},
+    external::{
+        commits::{commits_in_range, default_range},
+        hooks::{find_repositories, install, repository_hooks_dir, uninstall, SharedHooks},
+        vcs::{ConfigSource, Git2, Layered, TomlFile, Vcs, REPO_CONFIG_FILE},
+    },
+    lints::{
+        apply_profile,
+        get_custom_lints,
+        get_lint_configuration,
+        get_parse_configuration,
+        get_plugins,
+        get_profile,
+        get_profiles,
+        get_scripts,
+        set_lint_option,
+        set_lint_severity,
+        set_lint_status,
+        LintRegistry,
+        Lints,
+        PluginContext,
+        Severity,
+    },
+    output::{format_explanation, Format, Report},
 };
 
 const LOCAL_SCOPE: &str = "local";
+const WORKTREE_SCOPE: &str = "worktree";
+const GLOBAL_SCOPE: &str = "global";
+const SYSTEM_SCOPE: &str = "system";
+const REPO_FILE_SCOPE: &str = "repo-file";
 const LINT_NAME_ARGUMENT: &str = "lint";
 const COMMAND_LINT: &str = "lint";
 const COMMAND_LINT_ENABLE: &str = "enable";
 const COMMAND_LINT_DISABLE: &str = "disable";
+const COMMAND_LINT_CHECK: &str = "check";
+const COMMAND_LINT_LIST: &str = "list";
+const COMMAND_LINT_STATUS: &str = "status";
+const COMMAND_LINT_EXPLAIN: &str = "explain";
+const COMMAND_LINT_CONFIGURE: &str = "configure";
+const COMMAND_LINT_PROFILE: &str = "profile";
+const COMMAND_PROFILE_APPLY: &str = "apply";
+const COMMAND_PROFILE_LIST: &str = "list";
+const LINT_COMMANDS: [&str; 8] = [
+    COMMAND_LINT_ENABLE,
+    COMMAND_LINT_DISABLE,
+    COMMAND_LINT_CONFIGURE,
+    COMMAND_LINT_PROFILE,
+    COMMAND_LINT_CHECK,
+    COMMAND_LINT_LIST,
+    COMMAND_LINT_STATUS,
+    COMMAND_LINT_EXPLAIN,
+];
+const PROFILE_NAME_ARGUMENT: &str = "name";
+const COMMAND_HOOK: &str = "hook";
+const COMMAND_INSTALL: &str = "install";
+const COMMAND_UNINSTALL: &str = "uninstall";
+const RANGE_ARGUMENT: &str = "range";
+const FORMAT_ARGUMENT: &str = "format";
 const SCOPE_ARGUMENT: &str = "scope";
+const FILE_ARGUMENT: &str = "file";
+const SEVERITY_ARGUMENT: &str = "severity";
+const OPTION_ARGUMENT: &str = "option";
+const VALUE_ARGUMENT: &str = "value";
+const GLOBAL_ARGUMENT: &str = "global";
+const TEMPLATE_ARGUMENT: &str = "template";
+const ALL_REPOS_ARGUMENT: &str = "all-repos";
+const FORCE_ARGUMENT: &str = "force";
+const HOOK_NAME_ARGUMENT: &str = "name";
+const HOOK_ARGUMENTS_ARGUMENT: &str = "arguments";
+const MULTICALL_PREFIX: &str = "pb-";
+
+/// Runs a command with the arguments it was called with
+type Run = fn(&[String]);
+
+/// The commands that can be run from this binary, by the name they're called
+const MULTICALL: [(&str, Run); 7] = [
+    ("commit-msg", pb_commit_msg::run),
+    ("pre-commit", pb_pre_commit::run),
+    ("pre-push", pb_pre_push::run),
+    ("pre-receive", pb_pre_receive::run),
+    ("update", pb_pre_receive::run),
+    ("prepare-commit-msg", pb_prepare_commit_msg::run),
+    ("git-authors", git_authors::run),
+];
 
 fn display_err_and_exit<T>(error: &PbGitHooksError) -> T {
     eprintln!("{}", error);
@@ -28,22 +104,164 @@ fn display_err_and_exit<T>(error: &PbGitHooksError) -> T {
 }
 
 fn main() {
-    let matches = app().get_matches();
+    let args = env::args().collect::<Vec<_>>();
+
+    if let Some(run) = args.first().and_then(|name| multicall(name)) {
+        run(&args);
+        return;
+    }
+
+    let matches = app().get_matches_from(&args);
+
+    if let Some(hook_args) = matches.subcommand_matches(COMMAND_HOOK) {
+        let name = hook_args
+            .value_of(HOOK_NAME_ARGUMENT)
+            .expect("Hook name not given");
+        let run = multicall(name).expect("Unknown hook name");
+        let mut hook_arguments = vec![name.to_string()];
+        hook_arguments.extend(
+            hook_args
+                .values_of(HOOK_ARGUMENTS_ARGUMENT)
+                .into_iter()
+                .flatten()
+                .map(String::from),
+        );
+
+        run(&hook_arguments);
+        return;
+    }
 
     let current_dir = env::current_dir()
         .map_err(|error| PbGitHooksError::new_io("$PWD".into(), &error))
         .unwrap_or_else(|err| display_err_and_exit(&err));
-    let git_config = match matches.value_of(SCOPE_ARGUMENT) {
-        Some(LOCAL_SCOPE) => {
-            Repository::discover(current_dir).and_then(|repo: Repository| repo.config())
-        },
-        _ => Config::open_default(),
+
+    if let Some(install_args) = matches.subcommand_matches(COMMAND_INSTALL) {
+        install_hooks(install_args, &current_dir).unwrap_or_else(|err| display_err_and_exit(&err));
+        return;
     }
-    .expect("Couldn't load any git config");
-    let mut vcs = Git2::new(git_config);
+
+    if let Some(uninstall_args) = matches.subcommand_matches(COMMAND_UNINSTALL) {
+        uninstall_hooks(uninstall_args, &current_dir)
+            .unwrap_or_else(|err| display_err_and_exit(&err));
+        return;
+    }
+
+    // Only opened by the commands that use it, so the others work outside of
+    // a repository
+    let open_config = || {
+        open_config(
+            matches.value_of(SCOPE_ARGUMENT),
+            matches.value_of(FILE_ARGUMENT),
+            current_dir.clone(),
+        )
+        .unwrap_or_else(|err| display_err_and_exit(&err))
+    };
 
     if let Some(value) = matches.subcommand_matches(COMMAND_LINT) {
-        manage_lints(value, &mut vcs).unwrap_or_else(|err| display_err_and_exit(&err));
+        if let Some(check_args) = value.subcommand_matches(COMMAND_LINT_CHECK) {
+            let repository = env::current_dir()
+                .map_err(|error| PbGitHooksError::new_io("$PWD".into(), &error))
+                .and_then(|current_dir| {
+                    Repository::discover(current_dir)
+                        .map_err(PbCommitMessageLintsError::from)
+                        .map_err(PbGitHooksError::from)
+                })
+                .unwrap_or_else(|err| display_err_and_exit(&err));
+
+            check_commits(check_args, open_config().as_ref(), &repository)
+                .unwrap_or_else(|err| display_err_and_exit(&err));
+        } else if let Some(list_args) = value.subcommand_matches(COMMAND_LINT_LIST) {
+            let output = list_lints(list_args).unwrap_or_else(|err| display_err_and_exit(&err));
+
+            println!("{}", output);
+        } else if let Some(status_args) = value.subcommand_matches(COMMAND_LINT_STATUS) {
+            let output = lint_status(status_args, open_config().as_ref())
+                .unwrap_or_else(|err| display_err_and_exit(&err));
+
+            println!("{}", output);
+        } else if let Some(list_args) = value
+            .subcommand_matches(COMMAND_LINT_PROFILE)
+            .and_then(|profile_args| profile_args.subcommand_matches(COMMAND_PROFILE_LIST))
+        {
+            let output = list_profiles(list_args, open_config().as_ref())
+                .unwrap_or_else(|err| display_err_and_exit(&err));
+
+            println!("{}", output);
+        } else if let Some(explain_args) = value.subcommand_matches(COMMAND_LINT_EXPLAIN) {
+            let lint = lint_names(explain_args)
+                .into_iter()
+                .next()
+                .expect("Lint name not given");
+
+            print!("{}", format_explanation(lint));
+        } else {
+            manage_lints(value, open_config().as_mut())
+                .unwrap_or_else(|err| display_err_and_exit(&err));
+        }
+    }
+}
+
+/// The command to run if this binary was called by the name of one of the
+/// others, such as a hook linked to it, with or without their `pb-` prefix
+fn multicall(called_as: &str) -> Option<Run> {
+    let file_name = Path::new(called_as).file_name()?.to_str()?;
+    let name = file_name.trim_end_matches(".exe");
+    let name = name.strip_prefix(MULTICALL_PREFIX).unwrap_or(name);
+
+    MULTICALL
+        .iter()
+        .find(|(command, _)| *command == name)
+        .map(|(_, run)| *run)
+}
+
+/// The local and worktree scopes read the repository's config file under
+/// its git config, and the repo file scope only uses the file
+///
+/// A file given by name is used on its own, whatever the scope
+fn open_config(
+    scope: Option<&str>,
+    file: Option<&str>,
+    current_dir: PathBuf,
+) -> Result<Box<dyn Vcs>, PbGitHooksError> {
+    if let Some(file) = file {
+        return Ok(Box::new(Git2::new(
+            Config::open(Path::new(file)).map_err(PbCommitMessageLintsError::from)?,
+        )));
+    }
+
+    match scope {
+        Some(LOCAL_SCOPE) => {
+            let repository =
+                Repository::discover(current_dir).map_err(PbCommitMessageLintsError::from)?;
+
+            Ok(Box::new(Layered::for_repository(&repository)?))
+        },
+        Some(WORKTREE_SCOPE) => {
+            let repository =
+                Repository::discover(current_dir).map_err(PbCommitMessageLintsError::from)?;
+            let config = Git2::for_worktree(&repository)?;
+
+            Ok(Box::new(Layered::with_repo_file(&repository, config)?))
+        },
+        Some(SYSTEM_SCOPE) => Ok(Box::new(Git2::new(
+            Config::find_system()
+                .and_then(|path| Config::open(&path))
+                .map_err(PbCommitMessageLintsError::from)?,
+        ))),
+        Some(REPO_FILE_SCOPE) => {
+            let repository =
+                Repository::discover(current_dir).map_err(PbCommitMessageLintsError::from)?;
+            let workdir = repository.workdir().ok_or_else(|| {
+                PbCommitMessageLintsError::RepoFileError(
+                    "This repository has no working directory to keep the file in".into(),
+                )
+            })?;
+
+            Ok(Box::new(TomlFile::open(workdir.join(REPO_CONFIG_FILE))?))
+        },
+        _ => Ok(Box::new(Git2::new(
+            Config::open_default().map_err(PbCommitMessageLintsError::from)?,
+        ))),
     }
 }
 
@@ -59,6 +277,31 @@ fn app() -> App<'static, 'static> {
                 .collect::<Vec<_>>()
                 .as_slice(),
         );
+    let managed_lint_argument = Arg::with_name(LINT_NAME_ARGUMENT)
+        .help("The lint to enable, either built in, custom, a script or a plugin")
+        .required(true)
+        .multiple(true)
+        .min_values(1);
+    let table_format_argument = Arg::with_name(FORMAT_ARGUMENT)
+        .long(FORMAT_ARGUMENT)
+        .help("How to write out the lints")
+        .possible_values(&[Format::Text.name(), Format::Json.name()])
+        .default_value(Format::Text.name());
+    let hooks_arguments = [
+        Arg::with_name(GLOBAL_ARGUMENT)
+            .long(GLOBAL_ARGUMENT)
+            .help("Use the hooks every repository runs, from core.hooksPath")
+            .conflicts_with_all(&[TEMPLATE_ARGUMENT, ALL_REPOS_ARGUMENT]),
+        Arg::with_name(TEMPLATE_ARGUMENT)
+            .long(TEMPLATE_ARGUMENT)
+            .help("Use the hooks new repositories start with, from init.templateDir")
+            .conflicts_with(ALL_REPOS_ARGUMENT),
+        Arg::with_name(ALL_REPOS_ARGUMENT)
+            .long(ALL_REPOS_ARGUMENT)
+            .help("Use every repository in this directory, or any directory under it")
+            .takes_value(true)
+            .value_name("dir"),
+    ];
     App::new(env!("CARGO_PKG_NAME"))
         .version(crate_version!())
         .author(crate_authors!())
@@ -67,54 +310,588 @@ fn app() -> App<'static, 'static> {
             Arg::with_name(SCOPE_ARGUMENT)
                 .long("scope")
                 .short("s")
-                .possible_values(&[LOCAL_SCOPE, "global"])
+                .help("Which config to read and change lints in")
+                .possible_values(&[
+                    LOCAL_SCOPE,
+                    WORKTREE_SCOPE,
+                    GLOBAL_SCOPE,
+                    SYSTEM_SCOPE,
+                    REPO_FILE_SCOPE,
+                ])
                 .default_value(LOCAL_SCOPE),
         )
+        .arg(
+            Arg::with_name(FILE_ARGUMENT)
+                .long(FILE_ARGUMENT)
+                .short("f")
+                .help("Read and change lints in this git config file, rather than a scope")
+                .takes_value(true)
+                .value_name("path"),
+        )
+        .subcommand(
+            App::new(COMMAND_HOOK)
+                .about("Run a hook, or git-authors, as if it had been called by its own name")
+                .setting(AppSettings::TrailingVarArg)
+                .setting(AppSettings::AllowLeadingHyphen)
+                .arg(
+                    Arg::with_name(HOOK_NAME_ARGUMENT)
+                        .help("The hook to run")
+                        .required(true)
+                        .index(1)
+                        .possible_values(
+                            &MULTICALL.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+                        ),
+                )
+                .arg(
+                    Arg::with_name(HOOK_ARGUMENTS_ARGUMENT)
+                        .help("The arguments git gives the hook")
+                        .index(2)
+                        .multiple(true)
+                        .allow_hyphen_values(true),
+                ),
+        )
+        .subcommand(
+            App::new(COMMAND_INSTALL)
+                .about("Install the hooks, in this repository unless told otherwise")
+                .args(&hooks_arguments)
+                .arg(
+                    Arg::with_name(FORCE_ARGUMENT)
+                        .long(FORCE_ARGUMENT)
+                        .help("Replace hooks that are already there, chaining them after ours"),
+                ),
+        )
+        .subcommand(
+            App::new(COMMAND_UNINSTALL)
+                .about("Remove the installed hooks, putting back any that were replaced")
+                .args(&hooks_arguments),
+        )
         .subcommand(
             App::new(COMMAND_LINT)
                 .about("Manage active lints")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
                 .subcommand(
                     App::new(COMMAND_LINT_ENABLE)
                         .about("Enable a lint")
-                        .arg(lint_argument.clone()),
+                        .arg(managed_lint_argument.clone())
+                        .arg(
+                            Arg::with_name(SEVERITY_ARGUMENT)
+                                .long(SEVERITY_ARGUMENT)
+                                .help("Whether problems found by the lint fail or only warn")
+                                .possible_values(&[
+                                    Severity::Error.name(),
+                                    Severity::Warning.name(),
+                                ])
+                                .default_value(Severity::Error.name()),
+                        ),
+                )
+                .subcommand(
+                    App::new(COMMAND_LINT_DISABLE).about("Disable a lint").arg(
+                        managed_lint_argument.help(
+                            "The lint to disable, either built in, custom, a script or a plugin",
+                        ),
+                    ),
+                )
+                .subcommand(
+                    App::new(COMMAND_LINT_CONFIGURE)
+                        .about("Change one of the options of a lint")
+                        .arg(
+                            Arg::with_name(LINT_NAME_ARGUMENT)
+                                .help("The lint to configure")
+                                .required(true)
+                                .index(1)
+                                .possible_values(
+                                    Lints::iterator()
+                                        .filter(|lint| !lint.options().is_empty())
+                                        .map(Lints::name)
+                                        .collect::<Vec<_>>()
+                                        .as_slice(),
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name(OPTION_ARGUMENT)
+                                .help("The option to change, which `lint explain` lists")
+                                .required(true)
+                                .index(2),
+                        )
+                        .arg(
+                            Arg::with_name(VALUE_ARGUMENT)
+                                .help("The value to give it, with lists separated by spaces")
+                                .required(true)
+                                .index(3),
+                        ),
+                )
+                .subcommand(
+                    App::new(COMMAND_LINT_PROFILE)
+                        .about("Enable and configure a set of lints at once")
+                        .setting(AppSettings::SubcommandRequiredElseHelp)
+                        .subcommand(
+                            App::new(COMMAND_PROFILE_APPLY)
+                                .about("Set the severities and options the profile has")
+                                .arg(
+                                    Arg::with_name(PROFILE_NAME_ARGUMENT)
+                                        .help("The profile to apply, built in or from config")
+                                        .required(true)
+                                        .index(1),
+                                ),
+                        )
+                        .subcommand(
+                            App::new(COMMAND_PROFILE_LIST)
+                                .about("List every profile, with what it's for")
+                                .arg(table_format_argument.clone()),
+                        ),
+                )
+                .subcommand(
+                    App::new(COMMAND_LINT_CHECK)
+                        .about("Lint the messages of commits that have already been made")
+                        .arg(
+                            Arg::with_name(RANGE_ARGUMENT)
+                                .help(
+                                    "The commits to check, like \"main..HEAD\" [default: the \
+                                     commits since this branch left the default branch of origin]",
+                                )
+                                .index(1),
+                        )
+                        .arg(
+                            Arg::with_name(FORMAT_ARGUMENT)
+                                .long(FORMAT_ARGUMENT)
+                                .help("How to write out any problems that are found")
+                                .possible_values(
+                                    &Format::iterator().map(Format::name).collect::<Vec<_>>(),
+                                )
+                                .default_value(Format::Text.name()),
+                        ),
+                )
+                .subcommand(
+                    App::new(COMMAND_LINT_LIST)
+                        .about("List every lint, with what it checks for")
+                        .arg(table_format_argument.clone()),
+                )
+                .subcommand(
+                    App::new(COMMAND_LINT_STATUS)
+                        .about("Show the severity of every lint, and where it was set")
+                        .arg(table_format_argument),
                 )
                 .subcommand(
-                    App::new(COMMAND_LINT_DISABLE)
-                        .about("Disable a lint")
-                        .arg(lint_argument.clone()),
+                    App::new(COMMAND_LINT_EXPLAIN)
+                        .about("Describe what a lint checks for, and why, with examples")
+                        .arg(
+                            lint_argument
+                                .clone()
+                                .help("The lint to explain")
+                                .multiple(false),
+                        ),
                 ),
         )
 }
 
-fn manage_lints(args: &ArgMatches, config: &mut dyn Vcs) -> Result<(), PbGitHooksError> {
-    args.subcommand_matches(COMMAND_LINT_ENABLE)
-        .map(|enable_args| (enable_args, true))
-        .or_else(|| {
-            args.subcommand_matches(COMMAND_LINT_DISABLE)
-                .map(|disable_args| (disable_args, false))
+fn install_hooks(args: &ArgMatches, current_dir: &Path) -> Result<(), PbGitHooksError> {
+    let force = args.is_present(FORCE_ARGUMENT);
+
+    if let Some(shared) = shared_hooks(args) {
+        let mut config = global_config()?;
+        let dir = shared.find_or_configure(&mut config, &default_shared_dir(shared)?)?;
+        let installed = install(&dir, force)?;
+        println!("Installed {} in {}", installed.join(", "), dir.display());
+
+        return Ok(());
+    }
+
+    for_each_hooks_dir(args, current_dir, |dir| {
+        let installed = install(dir, force)?;
+        println!("Installed {} in {}", installed.join(", "), dir.display());
+
+        Ok(())
+    })
+}
+
+fn uninstall_hooks(args: &ArgMatches, current_dir: &Path) -> Result<(), PbGitHooksError> {
+    if let Some(shared) = shared_hooks(args) {
+        let mut config = global_config()?;
+        if let Some(dir) = shared.find(&config)? {
+            print_removed(&uninstall(&dir)?, &dir);
+            shared.remove_if_empty(&mut config)?;
+        }
+
+        return Ok(());
+    }
+
+    for_each_hooks_dir(args, current_dir, |dir| {
+        print_removed(&uninstall(dir)?, dir);
+
+        Ok(())
+    })
+}
+
+fn print_removed(removed: &[String], dir: &Path) {
+    if removed.is_empty() {
+        println!("Nothing to remove in {}", dir.display());
+    } else {
+        println!("Removed {} from {}", removed.join(", "), dir.display());
+    }
+}
+
+fn shared_hooks(args: &ArgMatches) -> Option<SharedHooks> {
+    if args.is_present(GLOBAL_ARGUMENT) {
+        Some(SharedHooks::Global)
+    } else if args.is_present(TEMPLATE_ARGUMENT) {
+        Some(SharedHooks::Template)
+    } else {
+        None
+    }
+}
+
+fn global_config() -> Result<Git2, PbGitHooksError> {
+    Ok(Git2::new(
+        Config::open_default().map_err(PbCommitMessageLintsError::from)?,
+    ))
+}
+
+/// Where the shared hooks go if git hasn't been told about a directory yet,
+/// next to the rest of the git config in the home directory
+fn default_shared_dir(shared: SharedHooks) -> Result<PathBuf, PbGitHooksError> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or(PbGitHooksError::NoHomeDirectory)?
+        .join("git");
+
+    Ok(match shared {
+        SharedHooks::Global => config_dir.join("hooks"),
+        SharedHooks::Template => config_dir.join("init-template"),
+    })
+}
+
+/// Run a change on the hooks directory of this repository, or of every
+/// repository in the directory given, carrying on past any that fail
+fn for_each_hooks_dir(
+    args: &ArgMatches,
+    current_dir: &Path,
+    change: impl Fn(&Path) -> Result<(), PbGitHooksError>,
+) -> Result<(), PbGitHooksError> {
+    let repositories = match args.value_of(ALL_REPOS_ARGUMENT) {
+        Some(dir) => find_repositories(Path::new(dir))
+            .iter()
+            .map(Repository::open)
+            .collect::<Result<Vec<_>, _>>(),
+        None => Repository::discover(current_dir).map(|repository| vec![repository]),
+    }
+    .map_err(PbCommitMessageLintsError::from)?;
+
+    let mut dirs = repositories
+        .iter()
+        .map(repository_hooks_dir)
+        .collect::<Result<Vec<_>, _>>()?;
+    dirs.sort();
+    dirs.dedup();
+
+    let mut errors = dirs
+        .iter()
+        .filter_map(|dir| change(dir).err())
+        .collect::<Vec<_>>();
+
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        count => {
+            errors.iter().for_each(|err| eprintln!("{}\n", err));
+
+            Err(PbGitHooksError::HooksNotChanged(count))
+        },
+    }
+}
+
+fn list_lints(args: &ArgMatches) -> Result<String, PbGitHooksError> {
+    if args.value_of(FORMAT_ARGUMENT) == Some(Format::Json.name()) {
+        return to_json(
+            &Lints::iterator()
+                .map(|lint| {
+                    json!({
+                        "name": lint.name(),
+                        "description": lint.description(),
+                        "default": lint.default_severity().name(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    Ok(format_table(
+        &["NAME", "DEFAULT", "DESCRIPTION"],
+        &Lints::iterator()
+            .map(|lint| {
+                vec![
+                    lint.name().into(),
+                    lint.default_severity().name().into(),
+                    lint.description().into(),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
+
+fn list_profiles(args: &ArgMatches, config: &dyn Vcs) -> Result<String, PbGitHooksError> {
+    let profiles = get_profiles(config)?;
+
+    if args.value_of(FORMAT_ARGUMENT) == Some(Format::Json.name()) {
+        return to_json(
+            &profiles
+                .iter()
+                .map(|profile| {
+                    json!({
+                        "name": profile.name(),
+                        "description": profile.description(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    Ok(format_table(
+        &["NAME", "DESCRIPTION"],
+        &profiles
+            .iter()
+            .map(|profile| vec![profile.name().into(), profile.description().into()])
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// The lints that aren't set anywhere have their default severity, or the
+/// one the profile gives them
+fn lint_status(args: &ArgMatches, config: &dyn Vcs) -> Result<String, PbGitHooksError> {
+    let lint_configuration = get_lint_configuration(config)?;
+    let built_in = Lints::iterator().map(|lint| {
+        (
+            lint.name(),
+            lint.config_key(),
+            lint_configuration.severity(lint),
+        )
+    });
+    let custom = lint_configuration
+        .custom_lints()
+        .iter()
+        .map(|(lint, severity)| (lint.name(), lint.config_key(), *severity));
+    let scripts = lint_configuration
+        .scripts()
+        .iter()
+        .map(|(script, severity)| (script.name(), script.config_key(), *severity));
+    let plugins = lint_configuration
+        .plugins()
+        .iter()
+        .map(|(plugin, severity)| (plugin.name(), plugin.config_key(), *severity));
+    let statuses = built_in
+        .chain(custom)
+        .chain(scripts)
+        .chain(plugins)
+        .map(|(name, key, severity)| {
+            let default = match lint_configuration.profile() {
+                Some(profile) if profile.severity(name).is_some() => ConfigSource::Profile,
+                _ => ConfigSource::Default,
+            };
+
+            config
+                .get_source(&key)
+                .map(|source| (name, severity, source.unwrap_or(default)))
         })
-        .ok_or_else(|| PbGitHooksError::UnrecognisedLintCommand)
-        .and_then(|(subcommand_args, enable)| {
-            set_lint_status(
-                &subcommand_args
-                    .values_of(LINT_NAME_ARGUMENT)
-                    .expect("Lint name not given")
-                    .map(|name| {
-                        Lints::try_from(name)
-                            .map_err(PbGitHooksError::from)
-                            .unwrap_or_else(|err| display_err_and_exit(&err))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if args.value_of(FORMAT_ARGUMENT) == Some(Format::Json.name()) {
+        return to_json(
+            &statuses
+                .iter()
+                .map(|(lint, severity, source)| {
+                    json!({
+                        "name": lint,
+                        "severity": severity.name(),
+                        "source": source.name(),
                     })
-                    .collect::<Vec<_>>(),
-                config,
-                enable,
-            )
-            .map_err(PbGitHooksError::from)
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    Ok(format_table(
+        &["NAME", "SEVERITY", "SOURCE"],
+        &statuses
+            .iter()
+            .map(|(lint, severity, source)| {
+                vec![
+                    (*lint).to_string(),
+                    severity.name().into(),
+                    source.name().into(),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
+
+fn to_json(value: &[Value]) -> Result<String, PbGitHooksError> {
+    serde_json::to_string_pretty(value)
+        .map_err(PbCommitMessageLintsError::from)
+        .map_err(PbGitHooksError::from)
+}
+
+/// Line up the columns, leaving the last one unpadded
+fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(index, header)| {
+            rows.iter()
+                .map(|row| row[index].len())
+                .chain(std::iter::once(header.len()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let header = headers
+        .iter()
+        .map(|header| (*header).to_string())
+        .collect::<Vec<_>>();
+
+    std::iter::once(&header)
+        .chain(rows.iter())
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn check_commits(
+    args: &ArgMatches,
+    config: &dyn Vcs,
+    repository: &Repository,
+) -> Result<(), PbGitHooksError> {
+    let format = Format::try_from(args.value_of(FORMAT_ARGUMENT).expect("Expected a format"))?;
+    let range = match args.value_of(RANGE_ARGUMENT) {
+        Some(range) => range.into(),
+        None => default_range(repository)?,
+    };
+    let parse_configuration = get_parse_configuration(config)?;
+    let lint_configuration = get_lint_configuration(config)?;
+
+    let context = PluginContext::discover(repository.path());
+
+    let reports = commits_in_range(repository, &range)?
+        .iter()
+        .map(|commit| {
+            Report::from_commit(commit, &parse_configuration, &lint_configuration, &context)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let exit_code = reports.iter().rev().find_map(Report::code);
+    let output = format.format(&reports)?;
+
+    if format.is_machine_readable() {
+        println!("{}", output);
+    } else if reports
+        .iter()
+        .any(|report| !report.problems().is_empty() || !report.suppressed().is_empty())
+    {
+        eprintln!("{}", output);
+    }
+
+    if let Some(exit_code) = exit_code {
+        process::exit(exit_code as i32);
+    }
+
+    Ok(())
+}
+
+fn manage_lints(args: &ArgMatches, config: &mut dyn Vcs) -> Result<(), PbGitHooksError> {
+    if let Some(enable_args) = args.subcommand_matches(COMMAND_LINT_ENABLE) {
+        let severity = Severity::try_from(
+            enable_args
+                .value_of(SEVERITY_ARGUMENT)
+                .expect("Expected a severity"),
+        )?;
+
+        let lints = managed_lint_names(enable_args, config)?;
+
+        set_lint_severity(&lints, config, severity).map_err(PbGitHooksError::from)
+    } else if let Some(disable_args) = args.subcommand_matches(COMMAND_LINT_DISABLE) {
+        let lints = managed_lint_names(disable_args, config)?;
+
+        set_lint_status(&lints, config, false).map_err(PbGitHooksError::from)
+    } else if let Some(configure_args) = args.subcommand_matches(COMMAND_LINT_CONFIGURE) {
+        let lint = lint_names(configure_args)
+            .into_iter()
+            .next()
+            .expect("Lint name not given");
+
+        set_lint_option(
+            &lint,
+            configure_args
+                .value_of(OPTION_ARGUMENT)
+                .expect("Option not given"),
+            configure_args
+                .value_of(VALUE_ARGUMENT)
+                .expect("Value not given"),
+            config,
+        )
+        .map_err(PbGitHooksError::from)
+    } else if let Some(apply_args) = args
+        .subcommand_matches(COMMAND_LINT_PROFILE)
+        .and_then(|profile_args| profile_args.subcommand_matches(COMMAND_PROFILE_APPLY))
+    {
+        let profile = get_profile(
+            config,
+            apply_args
+                .value_of(PROFILE_NAME_ARGUMENT)
+                .expect("Profile name not given"),
+        )?;
+
+        apply_profile(&profile, &LintRegistry::new(), config).map_err(PbGitHooksError::from)
+    } else {
+        Err(PbGitHooksError::UnrecognisedLintCommand)
+    }
+}
+
+/// Built-in lints, or custom lints, scripts and plugins defined in the config
+/// being changed
+fn managed_lint_names(args: &ArgMatches, config: &dyn Vcs) -> Result<Vec<String>, PbGitHooksError> {
+    let custom = get_custom_lints(config)?;
+    let scripts = get_scripts(config)?;
+    let plugins = get_plugins(config)?;
+
+    args.values_of(LINT_NAME_ARGUMENT)
+        .expect("Lint name not given")
+        .map(|name| {
+            if Lints::try_from(name).is_ok()
+                || custom.iter().any(|lint| lint.name() == name)
+                || scripts.iter().any(|script| script.name() == name)
+                || plugins.iter().any(|plugin| plugin.name() == name)
+            {
+                Ok(name.to_string())
+            } else {
+                Err(PbCommitMessageLintsError::LintNotFoundError(name.into()).into())
+            }
         })
+        .collect()
+}
+
+fn lint_names(args: &ArgMatches) -> Vec<Lints> {
+    args.values_of(LINT_NAME_ARGUMENT)
+        .expect("Lint name not given")
+        .map(|name| {
+            Lints::try_from(name)
+                .map_err(PbGitHooksError::from)
+                .unwrap_or_else(|err| display_err_and_exit(&err))
+        })
+        .collect::<Vec<_>>()
 }
 
 #[derive(Debug)]
 enum PbGitHooksError {
     UnrecognisedLintCommand,
+    NoHomeDirectory,
+    HooksNotChanged(usize),
     PbCommitMessageLintsError(PbCommitMessageLintsError),
     Io(String, String),
 }
@@ -122,10 +899,23 @@ enum PbGitHooksError {
 impl Display for PbGitHooksError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            PbGitHooksError::UnrecognisedLintCommand => write!(
+            PbGitHooksError::UnrecognisedLintCommand => {
+                let (last, rest) = LINT_COMMANDS.split_last().unwrap_or((&"", &[]));
+
+                write!(
+                    f,
+                    "Unrecognised Lint command, you may only {} or {}",
+                    rest.join(", "),
+                    last
+                )
+            },
+            PbGitHooksError::NoHomeDirectory => write!(
                 f,
-                "Unrecognised Lint command, only you may only enable or disable"
+                "Couldn't find your home directory, set $HOME or $XDG_CONFIG_HOME"
             ),
+            PbGitHooksError::HooksNotChanged(count) => {
+                write!(f, "Couldn't change the hooks of {} repositories", count)
+            },
             PbGitHooksError::PbCommitMessageLintsError(error) => write!(f, "{}", error),
             PbGitHooksError::Io(file_source, error) => write!(
                 f,
@@ -0,0 +1,124 @@
+use std::{path::PathBuf, process::Command};
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn config_setting(working_dir: &PathBuf, key: &str) -> String {
+    let output = Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .output()
+        .expect("failed to execute process");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn set_config(working_dir: &PathBuf, key: &str, value: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .arg(value)
+        .output()
+        .expect("failed to execute process");
+}
+
+#[test]
+fn applying_a_profile_sets_its_lints_and_options() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "profile", "apply", "oss-dco"],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!(
+        "error\n",
+        config_setting(&working_dir, "pb.lint.duplicated-trailers")
+    );
+    assert_eq!(
+        "off\n",
+        config_setting(&working_dir, "pb.lint.jira-issue-key-missing")
+    );
+    assert_eq!(
+        "Signed-off-by Co-authored-by Reviewed-by Acked-by\n",
+        config_setting(&working_dir, "pb.lint.duplicated-trailers.trailers")
+    );
+}
+
+#[test]
+fn unknown_profiles_are_not_applied() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "profile", "apply", "platform"],
+    );
+
+    assert_output(
+        &output,
+        "",
+        "There's no profile called \"platform\"\n",
+        false,
+    );
+}
+
+#[test]
+fn every_profile_is_listed() {
+    let working_dir = setup_working_dir();
+    set_config(
+        &working_dir,
+        "pb.profile.platform.jira-issue-key-missing",
+        "error",
+    );
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "profile", "list"],
+    );
+
+    assert_output(
+        &output,
+        r#"NAME          DESCRIPTION
+jira-team     Require a Jira Issue Key, and check for duplicated trailers
+pivotal-team  Require a Pivotal Tracker Id, and check for duplicated trailers
+oss-dco       Check sign-offs and review trailers aren't duplicated, without an issue tracker
+conventional  Require Conventional Commits, and check for duplicated trailers
+platform      Defined in config
+"#,
+        "",
+        true,
+    )
+}
+
+#[test]
+fn the_status_shows_lints_set_by_the_profile() {
+    let working_dir = setup_working_dir();
+    set_config(&working_dir, "pb.lint.profile", "jira-team");
+    set_config(&working_dir, "pb.lint.duplicated-trailers", "warn");
+
+    let output =
+        pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint", "status"]);
+
+    assert_output(
+        &output,
+        r#"NAME                        SEVERITY  SOURCE
+duplicated-trailers         warn      local
+pivotal-tracker-id-missing  off       profile
+jira-issue-key-missing      error     profile
+conventional-commits        off       default
+subject-too-long            off       default
+subject-ends-with-period    off       default
+subject-not-capitalised     off       default
+subject-extra-whitespace    off       default
+"#,
+        "",
+        true,
+    )
+}
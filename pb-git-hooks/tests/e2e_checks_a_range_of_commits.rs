@@ -0,0 +1,199 @@
+use std::{fs, process::Command};
+
+use pb_hook_test_helper::{assert_output, make_commit, setup_working_dir};
+
+#[test]
+fn commits_in_the_range_are_checked() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "Missing a key, but not in the range");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    make_commit(&working_dir, "JRA-123 Has a key");
+    let missing = make_commit(&working_dir, "An example commit");
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "check", &format!("{}..HEAD", base)],
+    );
+
+    let expected_stderr = format!(
+        r#"error: Your commit is missing a JIRA Issue Key
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding a key like `JRA-123` here
+  |
+
+You can fix this by adding a key like `JRA-123` to the commit message
+
+"#,
+        missing
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn the_range_defaults_to_the_commits_since_the_default_branch() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "Missing a key, but already on main");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("update-ref")
+        .arg("refs/remotes/origin/main")
+        .arg(&base)
+        .output()
+        .expect("failed to execute process");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("symbolic-ref")
+        .arg("refs/remotes/origin/HEAD")
+        .arg("refs/remotes/origin/main")
+        .output()
+        .expect("failed to execute process");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    make_commit(&working_dir, "JRA-123 Has a key");
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint", "check"]);
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn without_a_default_branch_a_range_is_needed() {
+    let working_dir = setup_working_dir();
+    make_commit(&working_dir, "An example commit");
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint", "check"]);
+
+    assert_output(
+        &output,
+        "",
+        "Couldn't find the default branch of \"origin\", you can give a range of commits to check \
+         instead\n",
+        false,
+    )
+}
+
+#[test]
+fn skipped_lints_are_noted() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "JRA-123 Has a key");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+    let skipped = make_commit(
+        &working_dir,
+        "Release 1.0.0\n\nLint-Skip: jira-issue-key-missing\n",
+    );
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "check", &format!("{}..HEAD", base)],
+    );
+
+    let expected_stderr = format!("note: {} skips jira-issue-key-missing\n\n", skipped);
+
+    assert_output(&output, "", &expected_stderr, true)
+}
+
+#[test]
+fn scripts_are_run_on_the_commits_in_the_range() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "An example commit");
+    fs::create_dir_all(working_dir.join("lints")).unwrap();
+    fs::write(
+        working_dir.join("lints/revert-reason.rhai"),
+        r#"
+if commit.subject.starts_with("Revert") && !commit.body.contains("because") {
+    [#{ help: "Say why the commit is being reverted", location: #{ line: 1 } }]
+}
+"#,
+    )
+    .unwrap();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.script.revert-reason.path")
+        .arg("lints/revert-reason.rhai")
+        .output()
+        .expect("failed to execute process");
+    let revert = make_commit(&working_dir, "Revert the login page\n\nIt broke\n");
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "check", &format!("{}..HEAD", base)],
+    );
+
+    let expected_stderr = format!(
+        r#"error: Say why the commit is being reverted
+ --> {}:1:1
+  |
+1 | Revert the login page
+  | ^^^^^^^^^^^^^^^^^^^^^
+  |
+
+"#,
+        revert
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[cfg(unix)]
+#[test]
+fn plugins_are_run_on_the_commits_in_the_range() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "An example commit");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.plugin.check-service.command")
+        .arg(r#"echo '[{"help": "Mention the service this changes", "location": {"line": 1}}]'"#)
+        .output()
+        .expect("failed to execute process");
+    let missing = make_commit(&working_dir, "Change the login page");
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "check", &format!("{}..HEAD", base)],
+    );
+
+    let expected_stderr = format!(
+        r#"error: Mention the service this changes
+ --> {}:1:1
+  |
+1 | Change the login page
+  | ^^^^^^^^^^^^^^^^^^^^^
+  |
+
+"#,
+        missing
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
@@ -0,0 +1,85 @@
+use std::{path::Path, process::Command};
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn option_setting(working_dir: &Path) -> String {
+    let output = Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing.projects")
+        .output()
+        .expect("failed to execute process");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn options_can_be_set() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "lint",
+            "configure",
+            "jira-issue-key-missing",
+            "projects",
+            "JRA CORE",
+        ],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!("JRA CORE\n", option_setting(&working_dir));
+}
+
+#[test]
+fn invalid_values_are_not_set() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "lint",
+            "configure",
+            "jira-issue-key-missing",
+            "projects",
+            "jra",
+        ],
+    );
+
+    assert_output(
+        &output,
+        "",
+        "The value \"jra\" is not valid for \"pb.lint.jira-issue-key-missing.projects\":\n\"jra\" \
+         is not a Jira project key, like `JRA`\n",
+        false,
+    );
+    assert_eq!("", option_setting(&working_dir));
+}
+
+#[test]
+fn unknown_options_are_an_error() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "lint",
+            "configure",
+            "duplicated-trailers",
+            "projects",
+            "JRA",
+        ],
+    );
+
+    assert_output(
+        &output,
+        "",
+        "Lint \"duplicated-trailers\" has no option \"projects\"\n",
+        false,
+    );
+}
@@ -0,0 +1,116 @@
+use std::process::Command;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn config_setting(working_dir: &std::path::PathBuf, key: &str) -> String {
+    let output = Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg(key)
+        .output()
+        .expect("failed to execute process");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn lint_setting(working_dir: &std::path::PathBuf) -> String {
+    config_setting(working_dir, "pb.lint.jira-issue-key-missing")
+}
+
+#[test]
+fn lints_are_enabled_as_errors() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "enable", "jira-issue-key-missing"],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!("error\n", lint_setting(&working_dir));
+}
+
+#[test]
+fn lints_can_be_enabled_as_warnings() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "lint",
+            "enable",
+            "--severity",
+            "warn",
+            "jira-issue-key-missing",
+        ],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!("warn\n", lint_setting(&working_dir));
+}
+
+#[test]
+fn custom_lints_can_be_disabled() {
+    let working_dir = setup_working_dir();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.customlint.no-hotfix.pattern")
+        .arg("hotfix")
+        .output()
+        .expect("failed to execute process");
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["--scope", "local", "lint", "disable", "no-hotfix"],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!("false\n", config_setting(&working_dir, "pb.lint.no-hotfix"));
+}
+
+#[test]
+fn lints_that_do_not_exist_are_not_enabled() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["--scope", "local", "lint", "enable", "no-hotfix"],
+    );
+
+    assert_output(&output, "", "Lint \"no-hotfix\" not found\n", false);
+    assert_eq!("", config_setting(&working_dir, "pb.lint.no-hotfix"));
+}
+
+#[test]
+fn every_lint_command_is_listed_when_none_is_given() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    for command in &[
+        "enable",
+        "disable",
+        "configure",
+        "profile",
+        "check",
+        "list",
+        "status",
+        "explain",
+    ] {
+        assert!(
+            stderr.contains(&format!("\n    {} ", command)),
+            "{} is missing from:\n{}",
+            command,
+            stderr
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use tempfile::TempDir;
+
+use pb_hook_test_helper::setup_working_dir;
+
+#[test]
+fn the_documentation_is_shown() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "explain", "duplicated-trailers"],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with(
+        "duplicated-trailers: Detect duplicated `Signed-off-by` and `Co-authored-by` \
+         trailers\nDefault: error\n"
+    ));
+    assert!(stdout.contains("\nFails\n\n    An example commit\n\n    Signed-off-by: Billie"));
+}
+
+#[test]
+fn lints_can_be_explained_outside_of_a_repository() {
+    let dir = TempDir::new().map(TempDir::into_path).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &dir,
+        "pb-git-hooks",
+        vec!["lint", "explain", "duplicated-trailers"],
+    );
+
+    assert!(output.status.success());
+}
+
+#[test]
+fn unknown_lints_are_rejected() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "explain", "banana"],
+    );
+
+    assert!(!output.status.success());
+}
@@ -0,0 +1,87 @@
+use std::fs;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+use tempfile::TempDir;
+
+#[test]
+fn hooks_are_installed_and_uninstalled() {
+    let working_dir = setup_working_dir();
+    let hooks_dir = working_dir.join(".git").join("hooks");
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["install"]);
+
+    assert_output(
+        &output,
+        &format!(
+            "Installed commit-msg, pre-commit, pre-push, prepare-commit-msg in {}\n",
+            hooks_dir.display()
+        ),
+        "",
+        true,
+    );
+    assert!(fs::read_to_string(hooks_dir.join("commit-msg"))
+        .unwrap()
+        .contains("exec pb-commit-msg"));
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["uninstall"]);
+
+    assert_output(
+        &output,
+        &format!(
+            "Removed commit-msg, pre-commit, pre-push, prepare-commit-msg from {}\n",
+            hooks_dir.display()
+        ),
+        "",
+        true,
+    );
+    assert!(!hooks_dir.join("commit-msg").exists());
+}
+
+#[test]
+fn other_hooks_are_only_replaced_when_forced() {
+    let working_dir = setup_working_dir();
+    let hooks_dir = working_dir.join(".git").join("hooks");
+    fs::create_dir_all(&hooks_dir).unwrap();
+    fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\nmake lint\n").unwrap();
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["install"]);
+
+    assert!(!output.status.success());
+    assert!(!hooks_dir.join("commit-msg").exists());
+
+    let output =
+        pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["install", "--force"]);
+
+    assert!(output.status.success());
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["uninstall"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        "#!/bin/sh\nmake lint\n",
+        fs::read_to_string(hooks_dir.join("pre-commit")).unwrap()
+    );
+}
+
+#[test]
+fn every_repository_in_a_directory_can_be_installed_to() {
+    let first = setup_working_dir();
+    let second = setup_working_dir();
+    let parent = TempDir::new().unwrap();
+    fs::rename(&first, parent.path().join("first")).unwrap();
+    fs::create_dir(parent.path().join("nested")).unwrap();
+    fs::rename(&second, parent.path().join("nested").join("second")).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &parent.path().to_path_buf(),
+        "pb-git-hooks",
+        vec!["install", "--all-repos", parent.path().to_str().unwrap()],
+    );
+
+    assert!(output.status.success());
+    assert!(parent.path().join("first/.git/hooks/commit-msg").exists());
+    assert!(parent
+        .path()
+        .join("nested/second/.git/hooks/commit-msg")
+        .exists());
+}
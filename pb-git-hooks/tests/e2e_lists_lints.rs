@@ -0,0 +1,137 @@
+use std::{fs, process::Command};
+
+use tempfile::TempDir;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+#[test]
+fn every_lint_is_listed() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint", "list"]);
+
+    assert_output(
+        &output,
+        r#"NAME                        DEFAULT  DESCRIPTION
+duplicated-trailers         error    Detect duplicated `Signed-off-by` and `Co-authored-by` trailers
+pivotal-tracker-id-missing  off      Detect a missing Pivotal Tracker Id
+jira-issue-key-missing      off      Detect a missing Jira Issue Key
+conventional-commits        off      Check the subject follows Conventional Commits
+subject-too-long            off      Detect a subject that is too long
+subject-ends-with-period    off      Detect a subject ending with a period
+subject-not-capitalised     off      Detect a subject that doesn't start with a capital
+subject-extra-whitespace    off      Detect whitespace at the start or end of the subject
+"#,
+        "",
+        true,
+    )
+}
+
+#[test]
+fn lints_can_be_listed_outside_of_a_repository() {
+    let dir = TempDir::new().map(TempDir::into_path).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(&dir, "pb-git-hooks", vec!["lint", "list"]);
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("NAME                        DEFAULT  DESCRIPTION\n"));
+}
+
+#[test]
+fn the_status_shows_where_each_lint_was_set() {
+    let working_dir = setup_working_dir();
+    fs::write(
+        working_dir.join(".pb-git-hooks.toml"),
+        "[lint]\npivotal-tracker-id-missing = \"warn\"\n",
+    )
+    .unwrap();
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+
+    let output =
+        pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint", "status"]);
+
+    assert_output(
+        &output,
+        r#"NAME                        SEVERITY  SOURCE
+duplicated-trailers         error     default
+pivotal-tracker-id-missing  warn      repo-file
+jira-issue-key-missing      error     local
+conventional-commits        off       default
+subject-too-long            off       default
+subject-ends-with-period    off       default
+subject-not-capitalised     off       default
+subject-extra-whitespace    off       default
+"#,
+        "",
+        true,
+    )
+}
+
+#[test]
+fn the_status_can_be_json() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec!["lint", "status", "--format", "json"],
+    );
+
+    assert_output(
+        &output,
+        r#"[
+  {
+    "name": "duplicated-trailers",
+    "severity": "error",
+    "source": "default"
+  },
+  {
+    "name": "pivotal-tracker-id-missing",
+    "severity": "off",
+    "source": "default"
+  },
+  {
+    "name": "jira-issue-key-missing",
+    "severity": "off",
+    "source": "default"
+  },
+  {
+    "name": "conventional-commits",
+    "severity": "off",
+    "source": "default"
+  },
+  {
+    "name": "subject-too-long",
+    "severity": "off",
+    "source": "default"
+  },
+  {
+    "name": "subject-ends-with-period",
+    "severity": "off",
+    "source": "default"
+  },
+  {
+    "name": "subject-not-capitalised",
+    "severity": "off",
+    "source": "default"
+  },
+  {
+    "name": "subject-extra-whitespace",
+    "severity": "off",
+    "source": "default"
+  }
+]
+"#,
+        "",
+        true,
+    )
+}
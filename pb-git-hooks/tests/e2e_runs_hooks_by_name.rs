@@ -0,0 +1,76 @@
+use std::{fs, io::Write, process::Command};
+
+use tempfile::NamedTempFile;
+
+use pb_hook_test_helper::setup_working_dir;
+
+const DUPLICATED_TRAILERS: &str = r#"An example commit
+
+Signed-off-by: Billie Thompson <email@example.com>
+Signed-off-by: Billie Thompson <email@example.com>
+"#;
+
+#[test]
+fn hooks_can_be_run_as_a_subcommand() {
+    let working_dir = setup_working_dir();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", DUPLICATED_TRAILERS).unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "hook",
+            "commit-msg",
+            "--format",
+            "json",
+            commit_path.path().to_str().unwrap(),
+        ],
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(Some(3), output.status.code());
+    assert!(
+        stdout.contains("\"lint\": \"duplicated-trailers\""),
+        "{}",
+        stdout
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn hooks_can_be_run_by_the_name_they_are_called() {
+    let working_dir = setup_working_dir();
+    let hook = working_dir.join(".git").join("hooks").join("commit-msg");
+    fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(env!("CARGO_BIN_EXE_pb-git-hooks"), &hook).unwrap();
+    let mut commit_path = NamedTempFile::new().unwrap();
+    write!(commit_path, "{}", DUPLICATED_TRAILERS).unwrap();
+
+    let output = Command::new(&hook)
+        .current_dir(&working_dir)
+        .arg(commit_path.path())
+        .output()
+        .expect("failed to execute process");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(Some(3), output.status.code());
+    assert!(
+        stderr.starts_with(
+            "error: Your commit cannot have the same name duplicated in the \"Signed-off-by\" \
+             field"
+        ),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn unknown_hooks_are_rejected() {
+    let working_dir = setup_working_dir();
+
+    let output =
+        pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["hook", "post-commit"]);
+
+    assert!(!output.status.success());
+}
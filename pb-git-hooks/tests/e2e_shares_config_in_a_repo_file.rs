@@ -0,0 +1,56 @@
+use std::fs;
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+#[test]
+fn lints_can_be_enabled_in_the_repo_file() {
+    let working_dir = setup_working_dir();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "--scope",
+            "repo-file",
+            "lint",
+            "enable",
+            "--severity",
+            "warn",
+            "jira-issue-key-missing",
+        ],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!(
+        "[lint]\njira-issue-key-missing = \"warn\"\n",
+        fs::read_to_string(working_dir.join(".pb-git-hooks.toml")).unwrap()
+    );
+}
+
+#[test]
+fn lints_can_be_disabled_in_the_repo_file() {
+    let working_dir = setup_working_dir();
+    fs::write(
+        working_dir.join(".pb-git-hooks.toml"),
+        "[lint]\njira-issue-key-missing = true\n",
+    )
+    .unwrap();
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "--scope",
+            "repo-file",
+            "lint",
+            "disable",
+            "jira-issue-key-missing",
+        ],
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!(
+        "[lint]\njira-issue-key-missing = false\n",
+        fs::read_to_string(working_dir.join(".pb-git-hooks.toml")).unwrap()
+    );
+}
@@ -0,0 +1,103 @@
+use std::{fs, path::Path, process::Command};
+
+use pb_hook_test_helper::{assert_output, setup_working_dir};
+
+fn git_config(working_dir: &Path, args: &[&str]) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .args(args)
+        .output()
+        .expect("failed to execute process");
+}
+
+fn enable_worktree_config(working_dir: &Path) {
+    git_config(working_dir, &["core.repositoryformatversion", "1"]);
+    git_config(working_dir, &["extensions.worktreeConfig", "true"]);
+}
+
+#[test]
+fn lints_can_be_enabled_for_the_worktree() {
+    let working_dir = setup_working_dir();
+    enable_worktree_config(&working_dir);
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "--scope",
+            "worktree",
+            "lint",
+            "enable",
+            "jira-issue-key-missing",
+        ],
+    );
+
+    assert_output(&output, "", "", true);
+    let worktree_config =
+        fs::read_to_string(working_dir.join(".git").join("config.worktree")).unwrap();
+    assert!(
+        worktree_config.contains("jira-issue-key-missing = error"),
+        "{}",
+        worktree_config
+    );
+}
+
+#[test]
+fn the_status_shows_lints_set_for_the_worktree() {
+    let working_dir = setup_working_dir();
+    enable_worktree_config(&working_dir);
+    git_config(&working_dir, &["pb.lint.jira-issue-key-missing", "off"]);
+    git_config(
+        &working_dir,
+        &["--worktree", "pb.lint.jira-issue-key-missing", "warn"],
+    );
+
+    let output =
+        pb_hook_test_helper::run_hook(&working_dir, "pb-git-hooks", vec!["lint", "status"]);
+
+    assert_output(
+        &output,
+        r#"NAME                        SEVERITY  SOURCE
+duplicated-trailers         error     default
+pivotal-tracker-id-missing  off       default
+jira-issue-key-missing      warn      worktree
+conventional-commits        off       default
+subject-too-long            off       default
+subject-ends-with-period    off       default
+subject-not-capitalised     off       default
+subject-extra-whitespace    off       default
+"#,
+        "",
+        true,
+    )
+}
+
+#[test]
+fn lints_can_be_enabled_in_any_file() {
+    let working_dir = setup_working_dir();
+    let file = working_dir.join("shared.gitconfig");
+
+    let output = pb_hook_test_helper::run_hook(
+        &working_dir,
+        "pb-git-hooks",
+        vec![
+            "--file",
+            file.to_str().unwrap(),
+            "lint",
+            "enable",
+            "jira-issue-key-missing",
+        ],
+    );
+
+    assert_output(&output, "", "", true);
+    let written = fs::read_to_string(&file).unwrap();
+    assert!(
+        written.contains("jira-issue-key-missing = error"),
+        "{}",
+        written
+    );
+    assert!(!fs::read_to_string(working_dir.join(".git").join("config"))
+        .unwrap()
+        .contains("jira-issue-key-missing"));
+}
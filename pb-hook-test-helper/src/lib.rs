@@ -3,8 +3,10 @@ use std::{
     error::Error,
     fmt,
     fmt::{Display, Formatter},
-    path::PathBuf,
-    process::{Command, Output},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
     str,
     time::Duration,
 };
@@ -26,6 +28,36 @@ pub fn run_hook(working_dir: &PathBuf, package: &str, arguments: Vec<&str>) -> O
         .expect("failed to execute process")
 }
 
+#[must_use]
+pub fn run_hook_with_stdin(
+    working_dir: &PathBuf,
+    package: &str,
+    arguments: Vec<&str>,
+    stdin: &str,
+) -> Output {
+    let toml_path = calculate_cargo_toml_path(package);
+    let mut cargo_arguments = vec!["run", "--quiet", "--manifest-path", &toml_path, "--"];
+    cargo_arguments.extend(arguments);
+
+    let mut child = Command::new("cargo")
+        .current_dir(working_dir)
+        .args(cargo_arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to execute process");
+
+    child
+        .stdin
+        .take()
+        .expect("failed to open stdin")
+        .write_all(stdin.as_bytes())
+        .expect("failed to write to stdin");
+
+    child.wait_with_output().expect("failed to execute process")
+}
+
 #[derive(Debug)]
 struct PathError;
 
@@ -70,6 +102,56 @@ pub fn set_author_expires(expiration_time: Duration, working_dir: &PathBuf) {
         .expect("failed to execute process");
 }
 
+/// Make an empty commit without running any hooks, returning its id
+pub fn make_commit(working_dir: &PathBuf, message: &str) -> String {
+    Command::new("git")
+        .current_dir(working_dir)
+        .args([
+            "-c",
+            "user.name=Billie Thompson",
+            "-c",
+            "user.email=billie@example.com",
+            "commit",
+            "--allow-empty",
+            "--cleanup=verbatim",
+            "--no-verify",
+            "-m",
+            message,
+        ])
+        .output()
+        .expect("failed to execute process");
+
+    let output = Command::new("git")
+        .current_dir(working_dir)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .expect("failed to execute process");
+
+    str::from_utf8(&output.stdout)
+        .expect("stdout couldn't be parsed")
+        .trim()
+        .into()
+}
+
+/// Write an executable script into the hooks directory of the repository
+pub fn write_hook(working_dir: &Path, name: &str, script: &str) -> PathBuf {
+    let hooks_dir = working_dir.join(".git").join("hooks");
+    let path = hooks_dir.join(name);
+    fs::create_dir_all(&hooks_dir).expect("Couldn't create the hooks directory");
+    fs::write(&path, format!("#!/bin/sh\n{}\n", script)).expect("Couldn't write the hook");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .expect("Couldn't make the hook executable");
+    }
+
+    path
+}
+
 #[must_use]
 pub fn calculate_cargo_toml_path(package: &str) -> String {
     let boxed_path_error = || Box::from(PathError);
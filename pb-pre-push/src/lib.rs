@@ -0,0 +1,212 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+    io::Read,
+    process,
+};
+
+use clap::{crate_authors, crate_version, App, Arg};
+use git2::Repository;
+
+use pb_commit_message_lints::{
+    errors::PbCommitMessageLintsError,
+    external::{
+        chain::{run_chain, ChainFailure},
+        commits::{remote_default_branch, updated_commits},
+        vcs::Layered,
+    },
+    lints::{get_lint_configuration, get_parse_configuration, PluginContext},
+    output::{Format, Report},
+};
+
+const REMOTE_NAME_ARGUMENT: &str = "remote-name";
+const REMOTE_URL_ARGUMENT: &str = "remote-url";
+const HOOK_NAME: &str = "pre-push";
+const DEFAULT_REMOTE: &str = "origin";
+
+fn display_err_and_exit<T>(error: &PbPrePushError) -> T {
+    eprintln!("{}", error);
+    process::exit(1);
+}
+
+/// Check the commits being pushed, as the `pre-push` hook
+///
+/// The arguments start with the name it was called by, like those from
+/// [`std::env::args`]
+pub fn run(args: &[String]) {
+    let matches = App::new(env!("CARGO_PKG_NAME"))
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .arg(
+            Arg::with_name(REMOTE_NAME_ARGUMENT)
+                .help("The name of the remote being pushed to")
+                .index(1)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(REMOTE_URL_ARGUMENT)
+                .help("The URL of the remote being pushed to")
+                .index(2)
+                .required(true),
+        )
+        .get_matches_from(args);
+
+    let remote_name = matches
+        .value_of(REMOTE_NAME_ARGUMENT)
+        .expect("Expected a remote name");
+    let remote_url = matches
+        .value_of(REMOTE_URL_ARGUMENT)
+        .expect("Expected a remote URL");
+
+    let current_dir = env::current_dir()
+        .map_err(|err| PbPrePushError::new_io("<current_dir>".into(), &err))
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let repository = Repository::discover(&current_dir)
+        .map_err(PbCommitMessageLintsError::from)
+        .map_err(PbPrePushError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let git_config = Layered::for_repository(&repository)
+        .map_err(PbPrePushError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let parse_configuration = get_parse_configuration(&git_config)
+        .map_err(PbPrePushError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let lint_configuration = get_lint_configuration(&git_config)
+        .map_err(PbPrePushError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    // Anything the remote already has has been checked before
+    let remote_name = pushed_remote(&repository, remote_name, remote_url);
+    let known_refs = format!("refs/remotes/{}/*", remote_name);
+    let base = remote_default_branch(&repository, &remote_name);
+    let context = PluginContext::discover(&current_dir);
+    let mut reports = vec![];
+
+    // Kept so the same refs can be given to any chained hooks
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|err| PbPrePushError::new_io("<stdin>".into(), &err))
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    for line in input.lines() {
+        let commits = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [_local_ref, local_sha, _remote_ref, remote_sha] => {
+                updated_commits(&repository, remote_sha, local_sha, &known_refs, base)
+                    .map_err(PbPrePushError::from)
+                    .unwrap_or_else(|err| display_err_and_exit(&err))
+            },
+            _ => display_err_and_exit(&PbPrePushError::UnrecognisedRefLine(line.into())),
+        };
+
+        for commit in &commits {
+            reports.push(
+                Report::from_commit(commit, &parse_configuration, &lint_configuration, &context)
+                    .map_err(PbPrePushError::from)
+                    .unwrap_or_else(|err| display_err_and_exit(&err)),
+            );
+        }
+    }
+
+    if reports.iter().any(|report| !report.problems().is_empty()) {
+        let output = Format::Text
+            .format(&reports)
+            .map_err(PbPrePushError::from)
+            .unwrap_or_else(|err| display_err_and_exit(&err));
+
+        eprintln!("{}", output);
+    }
+
+    let chain_failures = run_chain(
+        HOOK_NAME,
+        &current_dir,
+        args.get(1..).unwrap_or_default(),
+        Some(input.as_bytes()),
+    )
+    .map_err(PbPrePushError::from)
+    .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    for failure in &chain_failures {
+        eprintln!("{}", failure);
+    }
+
+    if let Some(exit_code) = reports
+        .iter()
+        .rev()
+        .find_map(Report::code)
+        .map(|code| code as i32)
+        .or_else(|| chain_failures.first().map(ChainFailure::code))
+    {
+        process::exit(exit_code);
+    }
+}
+
+/// Pushing to a URL rather than a remote gives the URL as the name too, so
+/// use the remote with that URL, or `origin` if none has it
+fn pushed_remote(repository: &Repository, name: &str, url: &str) -> String {
+    if name != url {
+        return name.into();
+    }
+
+    repository
+        .remotes()
+        .ok()
+        .and_then(|remotes| {
+            remotes
+                .iter()
+                .flatten()
+                .find(|remote| {
+                    repository
+                        .find_remote(remote)
+                        .map(|remote| remote.url() == Some(url))
+                        .unwrap_or_default()
+                })
+                .map(String::from)
+        })
+        .unwrap_or_else(|| DEFAULT_REMOTE.into())
+}
+
+#[derive(Debug)]
+enum PbPrePushError {
+    PbCommitMessageLintsError(PbCommitMessageLintsError),
+    Io(String, String),
+    UnrecognisedRefLine(String),
+}
+
+impl Display for PbPrePushError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PbPrePushError::PbCommitMessageLintsError(error) => write!(f, "{}", error),
+            PbPrePushError::Io(file_source, error) => {
+                write!(f, "Failed to read from `{}`:\n{}", file_source, error)
+            },
+            PbPrePushError::UnrecognisedRefLine(line) => write!(
+                f,
+                "Expected \"<local ref> <local sha> <remote ref> <remote sha>\", instead got \
+                 \"{}\"",
+                line
+            ),
+        }
+    }
+}
+
+impl From<PbCommitMessageLintsError> for PbPrePushError {
+    fn from(from: PbCommitMessageLintsError) -> Self {
+        PbPrePushError::PbCommitMessageLintsError(from)
+    }
+}
+
+impl Error for PbPrePushError {}
+
+impl PbPrePushError {
+    fn new_io(source: String, error: &io::Error) -> PbPrePushError {
+        PbPrePushError::Io(source, format!("{}", error))
+    }
+}
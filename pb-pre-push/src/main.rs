@@ -0,0 +1,5 @@
+use std::env;
+
+fn main() {
+    pb_pre_push::run(&env::args().collect::<Vec<_>>());
+}
@@ -0,0 +1,245 @@
+use std::process::Command;
+
+use pb_hook_test_helper::{assert_output, make_commit, setup_working_dir, write_hook};
+
+const ZERO_ID: &str = "0000000000000000000000000000000000000000";
+
+fn enable_jira_lint(working_dir: &std::path::PathBuf) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("config")
+        .arg("--local")
+        .arg("pb.lint.jira-issue-key-missing")
+        .arg("true")
+        .output()
+        .expect("failed to execute process");
+}
+
+fn set_default_branch(working_dir: &std::path::PathBuf, remote: &str, commit: &str) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("update-ref")
+        .arg(format!("refs/remotes/{}/main", remote))
+        .arg(commit)
+        .output()
+        .expect("failed to execute process");
+    Command::new("git")
+        .current_dir(working_dir)
+        .arg("symbolic-ref")
+        .arg(format!("refs/remotes/{}/HEAD", remote))
+        .arg(format!("refs/remotes/{}/main", remote))
+        .output()
+        .expect("failed to execute process");
+}
+
+#[test]
+fn commits_the_remote_does_not_have_are_checked() {
+    let working_dir = setup_working_dir();
+    let remote = make_commit(&working_dir, "Missing a key, but already pushed");
+    enable_jira_lint(&working_dir);
+    make_commit(&working_dir, "JRA-123 Has a key");
+    let local = make_commit(&working_dir, "An example commit");
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec!["origin", "git@example.com:example/repository.git"],
+        &format!("refs/heads/main {} refs/heads/main {}\n", local, remote),
+    );
+
+    let expected_stderr = format!(
+        r#"error: Your commit is missing a JIRA Issue Key
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding a key like `JRA-123` here
+  |
+
+You can fix this by adding a key like `JRA-123` to the commit message
+
+"#,
+        local
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn new_branches_are_compared_to_the_default_branch_of_the_remote() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "Missing a key, but already on main");
+    set_default_branch(&working_dir, "origin", &base);
+    enable_jira_lint(&working_dir);
+    let local = make_commit(&working_dir, "JRA-123 Has a key");
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec!["origin", "git@example.com:example/repository.git"],
+        &format!(
+            "refs/heads/feature {} refs/heads/feature {}\n",
+            local, ZERO_ID
+        ),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn new_branches_pushed_to_a_url_are_compared_to_the_remote_with_that_url() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "Missing a key, but already on main");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("remote")
+        .arg("add")
+        .arg("upstream")
+        .arg("https://example.com/example/repository.git")
+        .output()
+        .expect("failed to execute process");
+    set_default_branch(&working_dir, "upstream", &base);
+    enable_jira_lint(&working_dir);
+    let local = make_commit(&working_dir, "JRA-123 Has a key");
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec![
+            "https://example.com/example/repository.git",
+            "https://example.com/example/repository.git",
+        ],
+        &format!(
+            "refs/heads/feature {} refs/heads/feature {}\n",
+            local, ZERO_ID
+        ),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn new_branches_pushed_to_an_unknown_url_are_compared_to_origin() {
+    let working_dir = setup_working_dir();
+    let base = make_commit(&working_dir, "Missing a key, but already on main");
+    set_default_branch(&working_dir, "origin", &base);
+    enable_jira_lint(&working_dir);
+    let local = make_commit(&working_dir, "JRA-123 Has a key");
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec![
+            "https://example.com/example/fork.git",
+            "https://example.com/example/fork.git",
+        ],
+        &format!(
+            "refs/heads/feature {} refs/heads/feature {}\n",
+            local, ZERO_ID
+        ),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn new_annotated_tags_only_check_commits_the_remote_does_not_have() {
+    let working_dir = setup_working_dir();
+    let pushed = make_commit(&working_dir, "Missing a key, but already pushed");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .arg("update-ref")
+        .arg("refs/remotes/origin/main")
+        .arg(&pushed)
+        .output()
+        .expect("failed to execute process");
+    enable_jira_lint(&working_dir);
+    let local = make_commit(&working_dir, "An example commit");
+    Command::new("git")
+        .current_dir(&working_dir)
+        .args([
+            "-c",
+            "user.name=Billie Thompson",
+            "-c",
+            "user.email=billie@example.com",
+            "tag",
+            "--annotate",
+            "--message",
+            "A release",
+            "v1.0.0",
+        ])
+        .output()
+        .expect("failed to execute process");
+    let tag = Command::new("git")
+        .current_dir(&working_dir)
+        .arg("rev-parse")
+        .arg("v1.0.0")
+        .output()
+        .map(|output| String::from_utf8(output.stdout).unwrap().trim().to_string())
+        .expect("failed to execute process");
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec!["origin", "git@example.com:example/repository.git"],
+        &format!("refs/tags/v1.0.0 {} refs/tags/v1.0.0 {}\n", tag, ZERO_ID),
+    );
+
+    let expected_stderr = format!(
+        r#"error: Your commit is missing a JIRA Issue Key
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding a key like `JRA-123` here
+  |
+
+You can fix this by adding a key like `JRA-123` to the commit message
+
+"#,
+        local
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn deleting_a_branch_checks_nothing() {
+    let working_dir = setup_working_dir();
+    enable_jira_lint(&working_dir);
+    let remote = make_commit(&working_dir, "An example commit");
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec!["origin", "git@example.com:example/repository.git"],
+        &format!("(delete) {} refs/heads/feature {}\n", ZERO_ID, remote),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn the_local_hook_is_given_the_same_input() {
+    let working_dir = setup_working_dir();
+    let remote = make_commit(&working_dir, "JRA-123 Already pushed");
+    let local = make_commit(&working_dir, "JRA-123 Has a key");
+    let input_path = working_dir.join("input");
+    write_hook(
+        &working_dir,
+        "pre-push.local",
+        &format!("echo \"$@\" > {0}; cat >> {0}", input_path.display()),
+    );
+    let input = format!("refs/heads/main {} refs/heads/main {}\n", local, remote);
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &working_dir,
+        "pb-pre-push",
+        vec!["origin", "git@example.com:example/repository.git"],
+        &input,
+    );
+
+    assert_output(&output, "", "", true);
+    assert_eq!(
+        format!("origin git@example.com:example/repository.git\n{}", input),
+        std::fs::read_to_string(input_path).unwrap()
+    );
+}
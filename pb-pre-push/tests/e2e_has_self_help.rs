@@ -0,0 +1,74 @@
+use pb_hook_test_helper::assert_output;
+
+#[test]
+fn help_returned_by_long_flag() {
+    let working_dir = pb_hook_test_helper::setup_working_dir();
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-pre-push", vec!["--help"]);
+    assert_output(
+        &output,
+        &format!(
+            r#"pb-pre-push {}
+Billie Thompson <billie+pb-pre-push@billiecodes.com>
+Run before a push, and check the messages of every commit that's about to be sent to the remote
+
+USAGE:
+    pb-pre-push <remote-name> <remote-url>
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+ARGS:
+    <remote-name>    The name of the remote being pushed to
+    <remote-url>     The URL of the remote being pushed to
+"#,
+            env!("CARGO_PKG_VERSION")
+        ),
+        "",
+        true,
+    )
+}
+
+#[test]
+fn help_returned_by_short_flag() {
+    let working_dir = pb_hook_test_helper::setup_working_dir();
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-pre-push", vec!["-h"]);
+    assert_output(
+        &output,
+        &format!(
+            r#"pb-pre-push {}
+Billie Thompson <billie+pb-pre-push@billiecodes.com>
+Run before a push, and check the messages of every commit that's about to be sent to the remote
+
+USAGE:
+    pb-pre-push <remote-name> <remote-url>
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+ARGS:
+    <remote-name>    The name of the remote being pushed to
+    <remote-url>     The URL of the remote being pushed to
+"#,
+            env!("CARGO_PKG_VERSION")
+        ),
+        "",
+        true,
+    )
+}
+
+#[test]
+fn short_help_returned_when_a_wrong_message_commands_passed() {
+    let working_dir = pb_hook_test_helper::setup_working_dir();
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-pre-push", vec!["--banana"]);
+    let expected = r#"error: Found argument '--banana' which wasn't expected, or isn't valid in this context
+
+USAGE:
+    pb-pre-push <remote-name> <remote-url>
+
+For more information try --help
+"#;
+
+    assert_output(&output, "", expected, false)
+}
@@ -0,0 +1,198 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+    io::BufRead,
+    process,
+};
+
+use clap::{crate_authors, crate_version, App, Arg};
+use git2::Repository;
+
+use pb_commit_message_lints::{
+    errors::PbCommitMessageLintsError,
+    external::{commits::updated_commits, vcs::Git2},
+    lints::{get_lint_configuration, get_parse_configuration, get_ref_filter, PluginContext},
+    output::{Format, Report},
+};
+
+const REF_NAME_ARGUMENT: &str = "ref-name";
+const OLD_ID_ARGUMENT: &str = "old-id";
+const NEW_ID_ARGUMENT: &str = "new-id";
+const RECEIVED_REFS: &str = "refs/*";
+
+fn display_err_and_exit<T>(error: &PbPreReceiveError) -> T {
+    eprintln!("{}", error);
+    process::exit(1);
+}
+
+/// Check the commits being received, as the `pre-receive` or `update` hook
+///
+/// The arguments start with the name it was called by, like those from
+/// [`std::env::args`]
+pub fn run(args: &[String]) {
+    let matches = App::new(env!("CARGO_PKG_NAME"))
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about(env!("CARGO_PKG_DESCRIPTION"))
+        .after_help(
+            "With no arguments the updates are read from stdin, like a pre-receive hook. Give \
+             them as arguments to use this as an update hook instead.",
+        )
+        .arg(
+            Arg::with_name(REF_NAME_ARGUMENT)
+                .help("The name of the ref being updated")
+                .index(1)
+                .requires_all(&[OLD_ID_ARGUMENT, NEW_ID_ARGUMENT]),
+        )
+        .arg(
+            Arg::with_name(OLD_ID_ARGUMENT)
+                .help("The id the ref pointed to before the push")
+                .index(2),
+        )
+        .arg(
+            Arg::with_name(NEW_ID_ARGUMENT)
+                .help("The id the ref will point to after the push")
+                .index(3),
+        )
+        .get_matches_from(args);
+
+    let repository = Repository::open_from_env()
+        .map_err(PbCommitMessageLintsError::from)
+        .map_err(PbPreReceiveError::from)
+        .unwrap_or_else(|err| display_err_and_exit(&err));
+
+    let updates = match (
+        matches.value_of(REF_NAME_ARGUMENT),
+        matches.value_of(OLD_ID_ARGUMENT),
+        matches.value_of(NEW_ID_ARGUMENT),
+    ) {
+        (Some(ref_name), Some(old_id), Some(new_id)) => {
+            vec![Update::new(ref_name, old_id, new_id)]
+        },
+        _ => read_updates().unwrap_or_else(|err| display_err_and_exit(&err)),
+    };
+
+    let reports =
+        lint_updates(&repository, &updates).unwrap_or_else(|err| display_err_and_exit(&err));
+
+    if reports.iter().any(|report| !report.problems().is_empty()) {
+        let output = Format::Text
+            .format(&reports)
+            .map_err(PbPreReceiveError::from)
+            .unwrap_or_else(|err| display_err_and_exit(&err));
+
+        eprintln!("{}", output);
+    }
+
+    if let Some(exit_code) = reports.iter().rev().find_map(Report::code) {
+        process::exit(exit_code as i32);
+    }
+}
+
+/// A ref being moved by the push
+struct Update {
+    ref_name: String,
+    old_id: String,
+    new_id: String,
+}
+
+impl Update {
+    fn new(ref_name: &str, old_id: &str, new_id: &str) -> Update {
+        Update {
+            ref_name: ref_name.into(),
+            old_id: old_id.into(),
+            new_id: new_id.into(),
+        }
+    }
+}
+
+/// Read the `<old id> <new id> <ref name>` lines a pre-receive hook is given
+fn read_updates() -> Result<Vec<Update>, PbPreReceiveError> {
+    io::stdin()
+        .lock()
+        .lines()
+        .map(|line| {
+            let line = line.map_err(|err| PbPreReceiveError::new_io("<stdin>".into(), &err))?;
+
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [old_id, new_id, ref_name] => Ok(Update::new(ref_name, old_id, new_id)),
+                _ => Err(PbPreReceiveError::UnrecognisedRefLine(line.clone())),
+            }
+        })
+        .collect()
+}
+
+/// New refs are checked for the commits no existing ref has, as the refs
+/// haven't been moved yet
+fn lint_updates(
+    repository: &Repository,
+    updates: &[Update],
+) -> Result<Vec<Report>, PbPreReceiveError> {
+    let git_config = Git2::for_repository(repository)?;
+    let parse_configuration = get_parse_configuration(&git_config)?;
+    let lint_configuration = get_lint_configuration(&git_config)?;
+    let ref_filter = get_ref_filter(&git_config)?;
+    let context = PluginContext::discover(repository.path());
+
+    let mut reports = vec![];
+
+    for update in updates
+        .iter()
+        .filter(|update| ref_filter.matches(&update.ref_name))
+    {
+        for commit in updated_commits(
+            repository,
+            &update.old_id,
+            &update.new_id,
+            RECEIVED_REFS,
+            None,
+        )? {
+            reports.push(Report::from_commit(
+                &commit,
+                &parse_configuration,
+                &lint_configuration,
+                &context,
+            )?);
+        }
+    }
+
+    Ok(reports)
+}
+
+#[derive(Debug)]
+enum PbPreReceiveError {
+    PbCommitMessageLintsError(PbCommitMessageLintsError),
+    Io(String, String),
+    UnrecognisedRefLine(String),
+}
+
+impl Display for PbPreReceiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PbPreReceiveError::PbCommitMessageLintsError(error) => write!(f, "{}", error),
+            PbPreReceiveError::Io(file_source, error) => {
+                write!(f, "Failed to read from `{}`:\n{}", file_source, error)
+            },
+            PbPreReceiveError::UnrecognisedRefLine(line) => write!(
+                f,
+                "Expected \"<old id> <new id> <ref name>\", instead got \"{}\"",
+                line
+            ),
+        }
+    }
+}
+
+impl From<PbCommitMessageLintsError> for PbPreReceiveError {
+    fn from(from: PbCommitMessageLintsError) -> Self {
+        PbPreReceiveError::PbCommitMessageLintsError(from)
+    }
+}
+
+impl Error for PbPreReceiveError {}
+
+impl PbPreReceiveError {
+    fn new_io(source: String, error: &io::Error) -> PbPreReceiveError {
+        PbPreReceiveError::Io(source, format!("{}", error))
+    }
+}
@@ -0,0 +1,5 @@
+use std::env;
+
+fn main() {
+    pb_pre_receive::run(&env::args().collect::<Vec<_>>());
+}
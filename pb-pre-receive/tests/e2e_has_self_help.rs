@@ -0,0 +1,82 @@
+use pb_hook_test_helper::assert_output;
+
+#[test]
+fn help_returned_by_long_flag() {
+    let working_dir = pb_hook_test_helper::setup_working_dir();
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-pre-receive", vec!["--help"]);
+    assert_output(
+        &output,
+        &format!(
+            r#"pb-pre-receive {}
+Billie Thompson <billie+pb-pre-receive@billiecodes.com>
+Run on the server when a push is received, and reject it if the messages of any new commits don't pass the lints
+
+USAGE:
+    pb-pre-receive [ARGS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+ARGS:
+    <ref-name>    The name of the ref being updated
+    <old-id>      The id the ref pointed to before the push
+    <new-id>      The id the ref will point to after the push
+
+With no arguments the updates are read from stdin, like a pre-receive hook. Give them as arguments to use this as an
+update hook instead.
+"#,
+            env!("CARGO_PKG_VERSION")
+        ),
+        "",
+        true,
+    )
+}
+
+#[test]
+fn help_returned_by_short_flag() {
+    let working_dir = pb_hook_test_helper::setup_working_dir();
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-pre-receive", vec!["-h"]);
+    assert_output(
+        &output,
+        &format!(
+            r#"pb-pre-receive {}
+Billie Thompson <billie+pb-pre-receive@billiecodes.com>
+Run on the server when a push is received, and reject it if the messages of any new commits don't pass the lints
+
+USAGE:
+    pb-pre-receive [ARGS]
+
+FLAGS:
+    -h, --help       Prints help information
+    -V, --version    Prints version information
+
+ARGS:
+    <ref-name>    The name of the ref being updated
+    <old-id>      The id the ref pointed to before the push
+    <new-id>      The id the ref will point to after the push
+
+With no arguments the updates are read from stdin, like a pre-receive hook. Give them as arguments to use this as an
+update hook instead.
+"#,
+            env!("CARGO_PKG_VERSION")
+        ),
+        "",
+        true,
+    )
+}
+
+#[test]
+fn short_help_returned_when_a_wrong_message_commands_passed() {
+    let working_dir = pb_hook_test_helper::setup_working_dir();
+    let output = pb_hook_test_helper::run_hook(&working_dir, "pb-pre-receive", vec!["--banana"]);
+    let expected = r#"error: Found argument '--banana' which wasn't expected, or isn't valid in this context
+
+USAGE:
+    pb-pre-receive [ARGS]
+
+For more information try --help
+"#;
+
+    assert_output(&output, "", expected, false)
+}
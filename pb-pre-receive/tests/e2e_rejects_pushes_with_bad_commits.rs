@@ -0,0 +1,197 @@
+use std::{path::PathBuf, process::Command};
+
+use tempfile::TempDir;
+
+use pb_hook_test_helper::{assert_output, make_commit, setup_working_dir};
+
+const ZERO_ID: &str = "0000000000000000000000000000000000000000";
+
+fn git(working_dir: &PathBuf, arguments: &[&str]) {
+    Command::new("git")
+        .current_dir(working_dir)
+        .args(arguments)
+        .output()
+        .expect("failed to execute process");
+}
+
+/// Make an annotated tag of `HEAD`, returning the id of the tag
+fn annotated_tag(working_dir: &PathBuf, name: &str) -> String {
+    git(
+        working_dir,
+        &[
+            "-c",
+            "user.name=Billie Thompson",
+            "-c",
+            "user.email=billie@example.com",
+            "tag",
+            "--annotate",
+            "--message",
+            "A release",
+            name,
+        ],
+    );
+
+    let output = Command::new("git")
+        .current_dir(working_dir)
+        .arg("rev-parse")
+        .arg(name)
+        .output()
+        .expect("failed to execute process");
+
+    String::from_utf8(output.stdout).unwrap().trim().into()
+}
+
+/// Clone the working directory into a bare repository, like the one on a
+/// server, with the jira lint enabled
+fn bare_clone(working_dir: &PathBuf) -> PathBuf {
+    let bare_dir = TempDir::new()
+        .map(TempDir::into_path)
+        .map(|path| path.join("bare.git"))
+        .expect("Unable to make path");
+    git(
+        working_dir,
+        &[
+            "clone",
+            "--bare",
+            working_dir.to_str().unwrap(),
+            bare_dir.to_str().unwrap(),
+        ],
+    );
+    git(
+        &bare_dir,
+        &["config", "pb.lint.jira-issue-key-missing", "true"],
+    );
+
+    bare_dir
+}
+
+#[test]
+fn new_commits_are_checked() {
+    let working_dir = setup_working_dir();
+    let old = make_commit(&working_dir, "Missing a key, but already received");
+    make_commit(&working_dir, "JRA-123 Has a key");
+    let new = make_commit(&working_dir, "An example commit");
+    let bare_dir = bare_clone(&working_dir);
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &bare_dir,
+        "pb-pre-receive",
+        vec![],
+        &format!("{} {} refs/heads/main\n", old, new),
+    );
+
+    let expected_stderr = format!(
+        r#"error: Your commit is missing a JIRA Issue Key
+ --> {}:1:1
+  |
+1 | An example commit
+  | ----------------- consider adding a key like `JRA-123` here
+  |
+
+You can fix this by adding a key like `JRA-123` to the commit message
+
+"#,
+        new
+    );
+
+    assert_output(&output, "", &expected_stderr, false)
+}
+
+#[test]
+fn updates_can_be_given_as_arguments() {
+    let working_dir = setup_working_dir();
+    let old = make_commit(&working_dir, "JRA-123 Has a key");
+    let new = make_commit(&working_dir, "An example commit");
+    let bare_dir = bare_clone(&working_dir);
+
+    let output = pb_hook_test_helper::run_hook(
+        &bare_dir,
+        "pb-pre-receive",
+        vec!["refs/heads/main", &old, &new],
+    );
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn new_branches_only_check_commits_no_other_ref_has() {
+    let working_dir = setup_working_dir();
+    make_commit(
+        &working_dir,
+        "Missing a key, but already on the default branch",
+    );
+    git(&working_dir, &["checkout", "-b", "feature"]);
+    let new = make_commit(&working_dir, "JRA-123 Has a key");
+    git(&working_dir, &["checkout", "-"]);
+    let bare_dir = bare_clone(&working_dir);
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &bare_dir,
+        "pb-pre-receive",
+        vec![],
+        &format!("{} {} refs/heads/feature\n", ZERO_ID, new),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn new_annotated_tags_only_check_commits_no_other_ref_has() {
+    let working_dir = setup_working_dir();
+    make_commit(&working_dir, "Missing a key, but already received");
+    make_commit(&working_dir, "JRA-123 Has a key");
+    let tag = annotated_tag(&working_dir, "v1.0.0");
+    let bare_dir = bare_clone(&working_dir);
+    git(&bare_dir, &["tag", "--delete", "v1.0.0"]);
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &bare_dir,
+        "pb-pre-receive",
+        vec![],
+        &format!("{} {} refs/tags/v1.0.0\n", ZERO_ID, tag),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn excluded_refs_are_not_checked() {
+    let working_dir = setup_working_dir();
+    let old = make_commit(&working_dir, "JRA-123 Has a key");
+    let new = make_commit(&working_dir, "An example commit");
+    let bare_dir = bare_clone(&working_dir);
+    git(
+        &bare_dir,
+        &["config", "pb.receive.exclude-refs", "refs/heads/wip/*"],
+    );
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &bare_dir,
+        "pb-pre-receive",
+        vec![],
+        &format!("{} {} refs/heads/wip/example\n", old, new),
+    );
+
+    assert_output(&output, "", "", true)
+}
+
+#[test]
+fn only_included_refs_are_checked() {
+    let working_dir = setup_working_dir();
+    let old = make_commit(&working_dir, "JRA-123 Has a key");
+    let new = make_commit(&working_dir, "An example commit");
+    let bare_dir = bare_clone(&working_dir);
+    git(
+        &bare_dir,
+        &["config", "pb.receive.include-refs", "refs/heads/main"],
+    );
+
+    let output = pb_hook_test_helper::run_hook_with_stdin(
+        &bare_dir,
+        "pb-pre-receive",
+        vec![],
+        &format!("{} {} refs/heads/feature\n", old, new),
+    );
+
+    assert_output(&output, "", "", true)
+}